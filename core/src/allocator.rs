@@ -5,6 +5,8 @@ use std::{convert::TryInto, ptr};
 
 mod rust;
 pub use rust::RustAllocator;
+mod counting;
+pub use counting::{AllocationCounter, CountingAllocator};
 
 /// Raw memory pointer
 #[cfg_attr(feature = "doc-cfg", doc(cfg(feature = "allocator")))]
@@ -0,0 +1,188 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use super::{Allocator, RawMemPtr, RustAllocator};
+
+/// Counters shared between a [`CountingAllocator`] and the code observing it.
+#[derive(Debug, Default)]
+pub struct AllocationCounter {
+    current: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+impl AllocationCounter {
+    /// The amount of bytes currently allocated.
+    pub fn current_bytes(&self) -> usize {
+        self.current.load(Ordering::Acquire)
+    }
+
+    /// The largest amount of bytes which was allocated at the same time.
+    pub fn peak_bytes(&self) -> usize {
+        self.peak.load(Ordering::Acquire)
+    }
+
+    fn add(&self, size: usize) {
+        let current = self.current.fetch_add(size, Ordering::AcqRel) + size;
+        self.peak.fetch_max(current, Ordering::AcqRel);
+    }
+
+    fn sub(&self, size: usize) {
+        self.current.fetch_sub(size, Ordering::AcqRel);
+    }
+}
+
+/// An allocator which keeps track of the amount of memory allocated by an other allocator.
+///
+/// Sizes are counted as reported by the usable size of the inner allocator, so they include any
+/// rounding the inner allocator does. The counters can be read through the handle returned by
+/// [`CountingAllocator::counter`] while the allocator is owned by the runtime.
+///
+/// # Example
+/// ```
+/// # use rquickjs::{allocator::CountingAllocator, Context, Runtime};
+/// let allocator = CountingAllocator::default();
+/// let counter = allocator.counter();
+///
+/// let rt = Runtime::new_with_alloc(allocator).unwrap();
+/// let ctx = Context::full(&rt).unwrap();
+/// ctx.with(|ctx| ctx.eval::<(), _>("globalThis.data = new Array(1000).fill(1)").unwrap());
+/// assert!(counter.current_bytes() > 0);
+/// assert!(counter.peak_bytes() >= counter.current_bytes());
+/// ```
+pub struct CountingAllocator<A = RustAllocator> {
+    inner: A,
+    counter: Arc<AllocationCounter>,
+}
+
+impl Default for CountingAllocator<RustAllocator> {
+    fn default() -> Self {
+        Self::new(RustAllocator)
+    }
+}
+
+impl<A: Allocator> CountingAllocator<A> {
+    /// Create a new counting allocator wrapping the given allocator.
+    pub fn new(inner: A) -> Self {
+        CountingAllocator {
+            inner,
+            counter: Arc::new(AllocationCounter::default()),
+        }
+    }
+
+    /// Returns a handle to the counters of this allocator.
+    pub fn counter(&self) -> Arc<AllocationCounter> {
+        self.counter.clone()
+    }
+
+    /// The amount of bytes currently allocated.
+    pub fn current_bytes(&self) -> usize {
+        self.counter.current_bytes()
+    }
+
+    /// The largest amount of bytes which was allocated at the same time.
+    pub fn peak_bytes(&self) -> usize {
+        self.counter.peak_bytes()
+    }
+}
+
+impl<A: Allocator> Allocator for CountingAllocator<A> {
+    unsafe fn alloc(&mut self, size: usize) -> RawMemPtr {
+        let res = self.inner.alloc(size);
+        // A zero sized allocation may return either null or a unique pointer.
+        if !res.is_null() {
+            self.counter.add(A::usable_size(res));
+        }
+        res
+    }
+
+    unsafe fn dealloc(&mut self, ptr: RawMemPtr) {
+        if ptr.is_null() {
+            return;
+        }
+        self.counter.sub(A::usable_size(ptr));
+        self.inner.dealloc(ptr);
+    }
+
+    unsafe fn realloc(&mut self, ptr: RawMemPtr, new_size: usize) -> RawMemPtr {
+        // Follow the libc semantics QuickJS expects: reallocating null allocates and
+        // reallocating to zero frees the memory.
+        if ptr.is_null() {
+            return self.alloc(new_size);
+        }
+        if new_size == 0 {
+            self.dealloc(ptr);
+            return std::ptr::null_mut();
+        }
+
+        let old_size = A::usable_size(ptr);
+        let res = self.inner.realloc(ptr, new_size);
+        // On failure the old allocation is left untouched so it is still counted.
+        if !res.is_null() {
+            self.counter.sub(old_size);
+            self.counter.add(A::usable_size(res));
+        }
+        res
+    }
+
+    fn usable_size(ptr: RawMemPtr) -> usize {
+        A::usable_size(ptr)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::CountingAllocator;
+    use crate::{allocator::Allocator, Context, Runtime};
+
+    #[test]
+    fn zero_sized() {
+        let mut allocator = CountingAllocator::default();
+        unsafe {
+            let ptr = allocator.realloc(std::ptr::null_mut(), 16);
+            assert!(!ptr.is_null());
+            assert_eq!(allocator.current_bytes(), 16);
+
+            let ptr = allocator.realloc(ptr, 64);
+            assert_eq!(allocator.current_bytes(), 64);
+
+            assert!(allocator.realloc(ptr, 0).is_null());
+            assert_eq!(allocator.current_bytes(), 0);
+            assert_eq!(allocator.peak_bytes(), 64);
+
+            let ptr = allocator.alloc(0);
+            allocator.dealloc(ptr);
+            assert_eq!(allocator.current_bytes(), 0);
+        }
+    }
+
+    #[test]
+    fn runtime_usage() {
+        let allocator = CountingAllocator::default();
+        let counter = allocator.counter();
+        let rt = Runtime::new_with_alloc(allocator).unwrap();
+        let ctx = Context::full(&rt).unwrap();
+
+        let before = counter.current_bytes();
+        ctx.with(|ctx| {
+            ctx.eval::<(), _>(
+                "globalThis.data = new Array(100_000).fill(0).map((_, i) => ({ i }))",
+            )
+            .unwrap();
+        });
+        let during = counter.current_bytes();
+        assert!(during > before);
+
+        ctx.with(|ctx| {
+            ctx.eval::<(), _>("globalThis.data = undefined").unwrap();
+        });
+        rt.run_gc();
+        assert!(counter.current_bytes() < during);
+        assert!(counter.peak_bytes() >= during);
+
+        drop(ctx);
+        drop(rt);
+        assert_eq!(counter.current_bytes(), 0);
+    }
+}
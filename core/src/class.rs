@@ -333,13 +333,8 @@ impl<'js> Object<'js> {
             return false;
         }
 
-        let p = unsafe {
-            qjs::JS_GetOpaque2(
-                self.0.ctx.as_ptr(),
-                self.0.as_js_value(),
-                C::class_id().get(),
-            )
-        };
+        // JS_GetOpaque2 would throw a TypeError for objects of another class.
+        let p = unsafe { qjs::JS_GetOpaque(self.0.as_js_value(), C::class_id().get()) };
         !p.is_null()
     }
 
@@ -563,6 +558,99 @@ mod test {
         })
     }
 
+    #[test]
+    fn mutable_methods() {
+        use crate::{
+            class::{OwnedBorrow, OwnedBorrowMut},
+            CatchResultExt,
+        };
+
+        pub struct Counter {
+            count: u32,
+        }
+
+        impl<'js> Trace<'js> for Counter {
+            fn trace<'a>(&self, _tracer: Tracer<'a, 'js>) {}
+        }
+
+        impl<'js> JsClass<'js> for Counter {
+            const NAME: &'static str = "Counter";
+
+            type Mutable = Writable;
+
+            fn class_id() -> &'static ClassId {
+                static ID: ClassId = ClassId::new();
+                &ID
+            }
+
+            fn prototype(ctx: &crate::Ctx<'js>) -> crate::Result<Option<Object<'js>>> {
+                let proto = Object::new(ctx.clone())?;
+                proto.set(
+                    "increment",
+                    Function::new(
+                        ctx.clone(),
+                        |mut this: This<OwnedBorrowMut<'js, Counter>>| {
+                            this.count += 1;
+                            this.count
+                        },
+                    )?,
+                )?;
+                proto.set(
+                    "count",
+                    Function::new(ctx.clone(), |this: This<OwnedBorrow<'js, Counter>>| {
+                        this.count
+                    })?,
+                )?;
+                proto.set(
+                    "incrementWith",
+                    Function::new(
+                        ctx.clone(),
+                        |mut this: This<OwnedBorrowMut<'js, Counter>>,
+                         f: Function<'js>|
+                         -> crate::Result<()> {
+                            this.count += 1;
+                            f.call(())
+                        },
+                    )?,
+                )?;
+                Ok(Some(proto))
+            }
+
+            fn constructor(_ctx: &crate::Ctx<'js>) -> crate::Result<Option<Constructor<'js>>> {
+                Ok(None)
+            }
+        }
+
+        test_with(|ctx| {
+            let counter = Class::instance(ctx.clone(), Counter { count: 0 }).unwrap();
+            ctx.globals().set("counter", counter.clone()).unwrap();
+
+            let res: u32 = ctx
+                .eval("counter.increment(); counter.increment(); counter.increment()")
+                .unwrap();
+            assert_eq!(res, 3);
+            assert_eq!(counter.borrow().count, 3);
+
+            // Mutably borrowing the object again while a method holds a mutable borrow fails.
+            let err = ctx
+                .eval::<(), _>("counter.incrementWith(() => counter.increment())")
+                .catch(&ctx)
+                .unwrap_err();
+            assert!(err.to_string().contains("already borrowed"));
+            let err = ctx
+                .eval::<(), _>("counter.incrementWith(() => counter.count())")
+                .catch(&ctx)
+                .unwrap_err();
+            assert!(err.to_string().contains("already borrowed"));
+
+            // The borrows are released after the failed calls.
+            let res: u32 = ctx
+                .eval("counter.incrementWith(() => {}); counter.count()")
+                .unwrap();
+            assert_eq!(res, 6);
+        })
+    }
+
     #[test]
     fn register_twice() {
         pub struct X;
@@ -595,4 +683,86 @@ mod test {
             Class::<X>::register(&ctx).unwrap();
         })
     }
+
+    #[test]
+    fn downcast() {
+        pub struct A(i32);
+        pub struct B;
+
+        impl<'js> Trace<'js> for A {
+            fn trace<'a>(&self, _tracer: Tracer<'a, 'js>) {}
+        }
+
+        impl<'js> Trace<'js> for B {
+            fn trace<'a>(&self, _tracer: Tracer<'a, 'js>) {}
+        }
+
+        impl<'js> JsClass<'js> for A {
+            const NAME: &'static str = "A";
+
+            type Mutable = Writable;
+
+            fn class_id() -> &'static ClassId {
+                static ID: ClassId = ClassId::new();
+                &ID
+            }
+
+            fn prototype(ctx: &crate::Ctx<'js>) -> crate::Result<Option<Object<'js>>> {
+                Object::new(ctx.clone()).map(Some)
+            }
+
+            fn constructor(_ctx: &crate::Ctx<'js>) -> crate::Result<Option<Constructor<'js>>> {
+                Ok(None)
+            }
+        }
+
+        impl<'js> JsClass<'js> for B {
+            const NAME: &'static str = "B";
+
+            type Mutable = Readable;
+
+            fn class_id() -> &'static ClassId {
+                static ID: ClassId = ClassId::new();
+                &ID
+            }
+
+            fn prototype(ctx: &crate::Ctx<'js>) -> crate::Result<Option<Object<'js>>> {
+                Object::new(ctx.clone()).map(Some)
+            }
+
+            fn constructor(_ctx: &crate::Ctx<'js>) -> crate::Result<Option<Constructor<'js>>> {
+                Ok(None)
+            }
+        }
+
+        test_with(|ctx| {
+            let a = Class::instance(ctx.clone(), A(1)).unwrap();
+            let b = Class::instance(ctx.clone(), B).unwrap();
+            let increment = Function::new(ctx.clone(), |a: Class<A>| {
+                a.borrow_mut().0 += 1;
+                a.borrow().0
+            })
+            .unwrap();
+            ctx.globals().set("a", a.clone()).unwrap();
+            ctx.globals().set("b", b.clone()).unwrap();
+            ctx.globals().set("increment", increment).unwrap();
+
+            let res: i32 = ctx.eval("increment(a)").unwrap();
+            assert_eq!(res, 2);
+            assert_eq!(a.borrow().0, 2);
+
+            assert!(b.as_inner().as_class::<A>().is_none());
+            assert!(!ctx.catch().is_object());
+            assert!(Class::<A>::from_js(&ctx, b.into_value()).is_err());
+            assert!(!ctx.catch().is_object());
+            assert!(
+                Class::<A>::from_js(&ctx, Object::new(ctx.clone()).unwrap().into_value()).is_err()
+            );
+
+            let rejected: bool = ctx
+                .eval("try { increment(b); false } catch (e) { e.message.includes(\"'A'\") }")
+                .unwrap();
+            assert!(rejected);
+        })
+    }
 }
@@ -183,6 +183,10 @@ trace_impls! {
     crate::Symbol,
     crate::Exception,
     crate::String,
+    crate::Map,
+    crate::Set,
+    crate::Date,
+    crate::RegExp,
 }
 
 trace_impls! {
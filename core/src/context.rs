@@ -2,9 +2,19 @@
 
 mod builder;
 pub use builder::{intrinsic, ContextBuilder, Intrinsic};
+mod clone;
+pub(crate) use clone::ValueCloner;
+mod console;
+pub use console::{ConsoleCallback, ConsoleLevel};
+#[cfg(feature = "crypto")]
+mod crypto;
 mod ctx;
 mod r#ref;
+#[cfg(feature = "futures")]
+mod timers;
 pub use ctx::{Ctx, EvalOptions};
+#[cfg(feature = "futures")]
+pub use timers::SleepCallback;
 #[cfg(feature = "multi-ctx")]
 mod multi_with_impl;
 
@@ -1,6 +1,11 @@
 use std::{future::Future, mem, pin::Pin, ptr::NonNull};
 
-use crate::{markers::ParallelSend, qjs, runtime::AsyncRuntime, Ctx, Error, Result};
+use crate::{
+    markers::ParallelSend,
+    qjs,
+    runtime::{raw, AsyncRuntime},
+    Ctx, Error, Result,
+};
 
 use self::future::WithFuture;
 
@@ -132,7 +137,7 @@ impl Drop for Inner {
             }
         };
         guard.runtime.update_stack_top();
-        unsafe { qjs::JS_FreeContext(self.ctx.as_ptr()) }
+        unsafe { raw::free_context(self.ctx.as_ptr()) }
         // Explicitly drop the guard to ensure it is valid during the entire use of runtime
         mem::drop(guard);
     }
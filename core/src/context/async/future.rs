@@ -126,8 +126,8 @@ where
 
                 lock.runtime.is_job_pending();
                 match lock.runtime.execute_pending_job() {
-                    Ok(false) => break,
-                    Ok(true) => {}
+                    Ok(None) => break,
+                    Ok(Some(_)) => {}
                     Err(_ctx) => {
                         //TODO figure out what to do with job errors.
                     }
@@ -1,6 +1,9 @@
 use std::{mem, ptr::NonNull};
 
-use crate::{class::Class, function::RustFunction, qjs, Ctx, Error, Result, Runtime};
+use crate::{
+    class::Class, function::RustFunction, qjs, runtime::raw, Ctx, Error, RegisteryKey, Result,
+    Runtime, Value,
+};
 
 use super::{intrinsic, r#ref::ContextRef, ContextBuilder, Intrinsic};
 
@@ -121,6 +124,32 @@ impl Context {
         f(ctx)
     }
 
+    /// Store a value in the registry of the runtime, see [`Ctx::store`].
+    ///
+    /// The value is held by this context, so it is released when this context is dropped even
+    /// if the value was created in another context of the runtime. Can be called during
+    /// [`Context::with`] as it does not lock the runtime.
+    pub fn store(&self, value: &Value) -> RegisteryKey {
+        assert_eq!(
+            self.get_runtime_ptr(),
+            unsafe { qjs::JS_GetRuntime(value.ctx().as_ptr()) },
+            "tried to store a value from an unrelated runtime"
+        );
+        // A value only exists while the runtime is locked, so the registry can be accessed.
+        let ctx = unsafe { Ctx::new(self) };
+        let value = unsafe { Value::from_js_value_const(ctx.clone(), value.as_js_value()) };
+        ctx.store(value)
+    }
+
+    /// Remove a value from the registry, dropping the held reference.
+    ///
+    /// This function locks the runtime, use [`Ctx::remove`] during [`Context::with`].
+    pub fn remove(&self, key: RegisteryKey) {
+        self.with(|ctx| {
+            ctx.remove(key);
+        })
+    }
+
     pub(crate) unsafe fn init_raw(ctx: *mut qjs::JSContext) {
         Class::<RustFunction>::register(&Ctx::from_ptr(ctx))
             .expect("failed to initialized callback class");
@@ -135,7 +164,7 @@ impl Drop for Context {
             None => {
                 let p = unsafe { &mut *(self.0.ctx.as_ptr() as *mut qjs::JSRefCountHeader) };
                 if p.ref_count <= 1 {
-                    // Lock was poisoned, this should only happen on a panic.
+                    // The runtime is still in use, this should only happen on a panic.
                     // We should still free the context.
                     // TODO see if there is a way to recover from a panic which could cause the
                     // following assertion to trigger
@@ -146,7 +175,7 @@ impl Drop for Context {
             }
         };
         guard.update_stack_top();
-        unsafe { qjs::JS_FreeContext(self.0.ctx.as_ptr()) }
+        unsafe { raw::free_context(self.0.ctx.as_ptr()) }
         // Explicitly drop the guard to ensure it is valid during the entire use of runtime
         mem::drop(guard);
     }
@@ -178,6 +207,22 @@ mod test {
         });
     }
 
+    #[test]
+    fn usable_after_panic() {
+        let rt = Runtime::new().unwrap();
+        let ctx = Context::full(&rt).unwrap();
+        let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            ctx.with(|_| panic!("panic while holding the runtime lock"))
+        }));
+        assert!(res.is_err());
+
+        rt.run_gc();
+        ctx.with(|ctx| {
+            let res: i32 = ctx.eval("1 + 1").unwrap();
+            assert_eq!(res, 2);
+        });
+    }
+
     #[test]
     fn minimal() {
         let rt = Runtime::new().unwrap();
@@ -1,8 +1,16 @@
 use std::{marker::PhantomData, ptr::NonNull};
 
 #[cfg(feature = "futures")]
-use crate::{context::AsyncContext, runtime::AsyncRuntime};
-use crate::{qjs, Context, Result, Runtime};
+use std::{future::Future, io, time::Duration};
+
+#[cfg(feature = "futures")]
+use crate::{context::AsyncContext, runtime::AsyncRuntime, Error};
+use crate::{
+    markers::{ParallelSend, ParallelSync},
+    qjs, Context, Ctx, Result, Runtime, Value,
+};
+
+use super::{console, ConsoleCallback, ConsoleLevel};
 
 /// The internal trait to add JS builtins
 pub trait Intrinsic {
@@ -12,7 +20,37 @@ pub trait Intrinsic {
 }
 
 /// Used for building a [`Context`](struct.Context.html) with a specific set of intrinsics
-pub struct ContextBuilder<I>(PhantomData<I>);
+pub struct ContextBuilder<I> {
+    globals: Globals,
+    marker: PhantomData<I>,
+}
+
+/// The globals which are defined after the context is created.
+#[derive(Default)]
+struct Globals {
+    console: Option<ConsoleCallback>,
+    #[cfg(feature = "crypto")]
+    crypto: bool,
+    #[cfg(feature = "futures")]
+    timers: Option<super::SleepCallback>,
+}
+
+impl Globals {
+    fn install(self, ctx: &Ctx<'_>) -> Result<()> {
+        if let Some(callback) = self.console {
+            console::install(ctx, callback)?;
+        }
+        #[cfg(feature = "crypto")]
+        if self.crypto {
+            super::crypto::install(ctx)?;
+        }
+        #[cfg(feature = "futures")]
+        if let Some(sleep) = self.timers {
+            super::timers::install(ctx, sleep)?;
+        }
+        Ok(())
+    }
+}
 
 macro_rules! intrinsic_impls {
     (@builtin: $($(#[$meta:meta])* $name:ident $func:ident $(($($args:expr),*))*,)*) => {
@@ -135,21 +173,259 @@ intrinsic_impls! {
 
 impl Default for ContextBuilder<()> {
     fn default() -> Self {
-        ContextBuilder(PhantomData)
+        ContextBuilder {
+            globals: Globals::default(),
+            marker: PhantomData,
+        }
     }
 }
 
 impl<I: Intrinsic> ContextBuilder<I> {
     pub fn with<J: Intrinsic>(self) -> ContextBuilder<(I, J)> {
-        ContextBuilder(PhantomData)
+        ContextBuilder {
+            globals: self.globals,
+            marker: PhantomData,
+        }
+    }
+
+    /// Intercept `console` calls of the context with a callback.
+    ///
+    /// The `log`, `debug`, `info`, `warn`, `error` and `trace` methods of the global `console`
+    /// object are replaced with functions which call the callback with the level and the
+    /// arguments of the call, leaving formatting and routing to the host.
+    ///
+    /// This is also the way to capture the output of scripts: the `std` and `os` modules of
+    /// `quickjs-libc` are not part of this library, so scripts can only produce output through
+    /// `console` or functions provided by the host.
+    #[must_use]
+    pub fn with_console<F>(mut self, callback: F) -> Self
+    where
+        F: for<'js> Fn(ConsoleLevel, &[Value<'js>]) + ParallelSend + ParallelSync + 'static,
+    {
+        self.globals.console = Some(Box::new(callback));
+        self
+    }
+
+    /// Define a global `crypto` object with a `getRandomValues` method.
+    ///
+    /// `crypto.getRandomValues(array)` fills an integer typed array with random values from the
+    /// random number generator of the operating system and returns it. As required by the Web
+    /// Crypto specification it throws a `QuotaExceededError` when the array is larger than 65536
+    /// bytes.
+    #[cfg(feature = "crypto")]
+    #[cfg_attr(feature = "doc-cfg", doc(cfg(feature = "crypto")))]
+    #[must_use]
+    pub fn with_crypto(mut self) -> Self {
+        self.globals.crypto = true;
+        self
+    }
+
+    /// Define the `setTimeout`, `setInterval`, `clearTimeout` and `clearInterval` globals.
+    ///
+    /// The timers are spawned on the async runtime and wait on the futures returned by `sleep`,
+    /// which makes them usable with any executor, e.g. with `tokio::time::sleep`. Clearing a
+    /// timer finishes its future immediately, so [`AsyncRuntime::idle`] doesn't wait for cleared
+    /// timers. Exceptions thrown by timer callbacks are reported like unhandled promise
+    /// rejections to the
+    /// [rejection tracker](crate::Runtime::set_host_promise_rejection_tracker) of the runtime.
+    ///
+    /// As timers need the async runtime they are only defined by
+    /// [`build_async`](Self::build_async), [`build`](Self::build) returns an error if they are
+    /// enabled.
+    #[cfg(feature = "futures")]
+    #[cfg_attr(feature = "doc-cfg", doc(cfg(feature = "futures")))]
+    #[must_use]
+    pub fn with_timers<F, S>(mut self, sleep: F) -> Self
+    where
+        F: Fn(Duration) -> S + ParallelSend + ParallelSync + 'static,
+        S: Future<Output = ()> + 'static,
+    {
+        self.globals.timers = Some(Box::new(move |duration| Box::pin(sleep(duration))));
+        self
     }
 
     pub fn build(self, runtime: &Runtime) -> Result<Context> {
-        Context::custom::<I>(runtime)
+        // Timers are driven by the spawner of the async runtime.
+        #[cfg(feature = "futures")]
+        if self.globals.timers.is_some() {
+            return Err(Error::Io(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "timers can only be defined by `build_async`",
+            )));
+        }
+        let context = Context::custom::<I>(runtime)?;
+        let globals = self.globals;
+        context.with(|ctx| globals.install(&ctx))?;
+        Ok(context)
     }
 
     #[cfg(feature = "futures")]
     pub async fn build_async(self, runtime: &AsyncRuntime) -> Result<AsyncContext> {
-        AsyncContext::custom::<I>(runtime).await
+        let context = AsyncContext::custom::<I>(runtime).await?;
+        let globals = self.globals;
+        context.with(|ctx| globals.install(&ctx)).await?;
+        Ok(context)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::context::ConsoleLevel;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn with_console() {
+        let records = Arc::new(Mutex::new(Vec::new()));
+        let rt = Runtime::new().unwrap();
+        let ctx = Context::builder()
+            .with::<intrinsic::All>()
+            .with_console({
+                let records = records.clone();
+                move |level, args| records.lock().unwrap().push((level, args.len()))
+            })
+            .build(&rt)
+            .unwrap();
+
+        ctx.with(|ctx| {
+            ctx.eval::<(), _>(r#"console.warn("a", 1); console.log(); console.error({})"#)
+                .unwrap();
+        });
+
+        assert_eq!(
+            *records.lock().unwrap(),
+            [
+                (ConsoleLevel::Warn, 2),
+                (ConsoleLevel::Log, 0),
+                (ConsoleLevel::Error, 1)
+            ]
+        );
+    }
+
+    #[cfg(feature = "crypto")]
+    #[test]
+    fn with_crypto() {
+        use crate::CatchResultExt;
+
+        let rt = Runtime::new().unwrap();
+        let ctx = Context::builder()
+            .with::<intrinsic::All>()
+            .with_crypto()
+            .build(&rt)
+            .unwrap();
+
+        ctx.with(|ctx| {
+            let filled: bool = ctx
+                .eval(
+                    r#"
+                    const array = new Uint8Array(16);
+                    crypto.getRandomValues(array) === array && array.some((x) => x !== 0)
+                    "#,
+                )
+                .unwrap();
+            assert!(filled);
+            let filled: bool = ctx
+                .eval("crypto.getRandomValues(new Uint32Array(new ArrayBuffer(32), 16)).some((x) => x !== 0)")
+                .unwrap();
+            assert!(filled);
+
+            let err = ctx
+                .eval::<(), _>("crypto.getRandomValues(new Uint8Array(65537))")
+                .catch(&ctx)
+                .unwrap_err();
+            assert!(err.to_string().contains("QuotaExceededError"));
+            let ok: bool = ctx
+                .eval("crypto.getRandomValues(new Uint8Array(65536)).length === 65536")
+                .unwrap();
+            assert!(ok);
+
+            let is_type_error: bool = ctx
+                .eval("try { crypto.getRandomValues(new Float64Array(2)); false } catch (e) { e instanceof TypeError }")
+                .unwrap();
+            assert!(is_type_error);
+            let is_type_error: bool = ctx
+                .eval("try { crypto.getRandomValues([1, 2]); false } catch (e) { e instanceof TypeError }")
+                .unwrap();
+            assert!(is_type_error);
+        });
+    }
+
+    #[cfg(feature = "futures")]
+    #[tokio::test]
+    async fn with_timers() {
+        use crate::async_with;
+
+        let rt = AsyncRuntime::new().unwrap();
+        let ctx = Context::builder()
+            .with::<intrinsic::All>()
+            .with_timers(tokio::time::sleep)
+            .build_async(&rt)
+            .await
+            .unwrap();
+
+        async_with!(ctx => |ctx| {
+            ctx.eval::<(), _>(
+                r#"
+                globalThis.log = [];
+                setTimeout((a, b) => log.push("timeout " + a + b), 50, 1, 2);
+                setTimeout(() => log.push("first"));
+                clearTimeout(setTimeout(() => log.push("cleared"), 10));
+                clearTimeout(setTimeout(() => log.push("long"), 1e9));
+                let count = 0;
+                const interval = setInterval(() => {
+                    log.push("interval");
+                    if (++count == 3) clearInterval(interval);
+                }, 5);
+                "#,
+            )
+            .unwrap();
+        })
+        .await;
+        rt.idle().await;
+
+        async_with!(ctx => |ctx| {
+            let log: String = ctx.eval("log.join()").unwrap();
+            assert_eq!(log, "first,interval,interval,interval,timeout 12");
+        })
+        .await;
+
+        let sync_rt = Runtime::new().unwrap();
+        let res = Context::builder()
+            .with_timers(tokio::time::sleep)
+            .build(&sync_rt);
+        assert!(res.is_err());
+    }
+
+    #[cfg(feature = "futures")]
+    #[tokio::test]
+    async fn timer_errors() {
+        use crate::async_with;
+
+        let reasons = Arc::new(Mutex::new(Vec::new()));
+        let rt = AsyncRuntime::new().unwrap();
+        rt.set_host_promise_rejection_tracker(Some(Box::new({
+            let reasons = reasons.clone();
+            move |_ctx, _promise, reason, is_handled| {
+                if !is_handled {
+                    let message: String = reason.as_object().unwrap().get("message").unwrap();
+                    reasons.lock().unwrap().push(message);
+                }
+            }
+        })))
+        .await;
+        let ctx = Context::builder()
+            .with::<intrinsic::All>()
+            .with_timers(tokio::time::sleep)
+            .build_async(&rt)
+            .await
+            .unwrap();
+
+        async_with!(ctx => |ctx| {
+            ctx.eval::<(), _>(r#"setTimeout(() => { throw new Error("failed") })"#)
+                .unwrap();
+        })
+        .await;
+        rt.idle().await;
+        assert_eq!(*reasons.lock().unwrap(), ["failed"]);
     }
 }
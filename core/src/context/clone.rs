@@ -0,0 +1,165 @@
+use std::{collections::HashMap, mem};
+
+use crate::{
+    atom::PredefinedAtom, qjs, Array, Atom, Ctx, Error, Filter, Object, Result, Type, Value,
+};
+
+/// The maximum nesting depth of copied arrays and objects
+const MAX_DEPTH: usize = 256;
+
+/// Helper for structurally copying values between contexts.
+///
+/// Plain objects and arrays are copied property by property, other objects and, between
+/// runtimes, primitives which are owned by the runtime are copied through the serialization
+/// format of QuickJS. Symbol keys are copied like symbol values, so they are only cloneable
+/// within a runtime.
+pub(crate) struct ValueCloner<'from, 'to> {
+    source: Ctx<'from>,
+    target: Ctx<'to>,
+    object_proto: Object<'from>,
+    same_runtime: bool,
+    skip_uncloneable: bool,
+    depth: usize,
+    /// Objects which were already copied, used to preserve shared references and cycles.
+    visited: HashMap<Object<'from>, Value<'to>>,
+}
+
+impl<'from, 'to> ValueCloner<'from, 'to> {
+    pub fn new(source: &Ctx<'from>, target: &Ctx<'to>, skip_uncloneable: bool) -> Result<Self> {
+        let object_proto = source
+            .globals()
+            .get::<_, Object>(PredefinedAtom::Object)?
+            .get(PredefinedAtom::Prototype)?;
+        let same_runtime =
+            unsafe { qjs::JS_GetRuntime(source.as_ptr()) == qjs::JS_GetRuntime(target.as_ptr()) };
+        Ok(ValueCloner {
+            source: source.clone(),
+            target: target.clone(),
+            object_proto,
+            same_runtime,
+            skip_uncloneable,
+            depth: 0,
+            visited: HashMap::new(),
+        })
+    }
+
+    /// Copy a value, skipped values are replaced with `undefined`.
+    pub fn clone(mut self, value: &Value<'from>) -> Result<Value<'to>> {
+        Ok(self
+            .clone_value(value)?
+            .unwrap_or_else(|| Value::new_undefined(self.target.clone())))
+    }
+
+    /// Copy a value, returns `None` if the value was skipped.
+    fn clone_value(&mut self, value: &Value<'from>) -> Result<Option<Value<'to>>> {
+        match value.type_of() {
+            Type::Array | Type::Object if self.depth >= MAX_DEPTH => {
+                Err(Error::new_from_js_message(
+                    value.type_name(),
+                    "value",
+                    "Maximum nesting depth exceeded",
+                ))
+            }
+            Type::Array => {
+                let array = value.as_array().expect("value should be an array");
+                if let Some(copy) = self.visited.get(array.as_object()) {
+                    return Ok(Some(copy.clone()));
+                }
+                let copy = Array::new(self.target.clone())?;
+                self.visited
+                    .insert(array.as_object().clone(), copy.as_value().clone());
+                self.depth += 1;
+                for (idx, elem) in array.iter::<Value>().enumerate() {
+                    match self.clone_value(&elem?)? {
+                        Some(elem) => copy.set(idx, elem)?,
+                        None => copy.set(idx, Value::new_undefined(self.target.clone()))?,
+                    }
+                }
+                self.depth -= 1;
+                Ok(Some(copy.into_value()))
+            }
+            Type::Object => {
+                let object = value.as_object().expect("value should be an object");
+                if let Some(copy) = self.visited.get(object) {
+                    return Ok(Some(copy.clone()));
+                }
+                let proto = object.get_prototype()?;
+                // Typed arrays, array buffers and other builtin objects are serialized
+                if matches!(proto, Some(ref proto) if *proto != self.object_proto) {
+                    return self.clone_serialized(value);
+                }
+                let copy = Object::new(self.target.clone())?;
+                if proto.is_none() {
+                    copy.set_prototype(None)?;
+                }
+                self.visited.insert(object.clone(), copy.as_value().clone());
+                self.depth += 1;
+                let filter = Filter::new().string().symbol().enum_only();
+                for prop in object.own_props::<Atom, Value>(filter) {
+                    let (key, value) = prop?;
+                    let Some(key) = self.clone_value(&key.to_property_key()?)? else {
+                        continue;
+                    };
+                    if let Some(value) = self.clone_value(&value)? {
+                        // Defined instead of set, so a `__proto__` key doesn't call the setter
+                        copy.define_value(key, value)?;
+                    }
+                }
+                self.depth -= 1;
+                Ok(Some(copy.into_value()))
+            }
+            Type::Function | Type::Constructor | Type::Exception | Type::Unknown => {
+                self.uncloneable(value)
+            }
+            Type::String | Type::Symbol | Type::BigInt | Type::Module if !self.same_runtime => {
+                self.clone_serialized(value)
+            }
+            // Primitives which are either not reference counted or owned by the same runtime
+            _ => Ok(Some(unsafe {
+                Value::from_js_value_const(self.target.clone(), value.as_js_value())
+            })),
+        }
+    }
+
+    fn clone_serialized(&self, value: &Value<'from>) -> Result<Option<Value<'to>>> {
+        let mut len = mem::MaybeUninit::uninit();
+        let bytes = unsafe {
+            let buf = qjs::JS_WriteObject(
+                self.source.as_ptr(),
+                len.as_mut_ptr(),
+                value.as_js_value(),
+                qjs::JS_WRITE_OBJ_REFERENCE as i32,
+            );
+            if buf.is_null() {
+                // The value is not supported by the serialization
+                let _ = self.source.catch();
+                return self.uncloneable(value);
+            }
+            let bytes = std::slice::from_raw_parts(buf, len.assume_init() as _).to_vec();
+            qjs::js_free(self.source.as_ptr(), buf as _);
+            bytes
+        };
+        unsafe {
+            let value = qjs::JS_ReadObject(
+                self.target.as_ptr(),
+                bytes.as_ptr(),
+                bytes.len() as _,
+                qjs::JS_READ_OBJ_REFERENCE as i32,
+            );
+            let value = self.target.handle_exception(value)?;
+            Ok(Some(Value::from_js_value(self.target.clone(), value)))
+        }
+    }
+
+    fn uncloneable(&self, value: &Value<'from>) -> Result<Option<Value<'to>>> {
+        if self.skip_uncloneable {
+            Ok(None)
+        } else {
+            Err(Error::new_from_js_message(
+                value.type_name(),
+                "value",
+                "Value can not be structurally cloned",
+            ))
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use std::fmt;
+
+use crate::{function::Rest, Ctx, Function, Object, Ref, Result, Value};
+
+/// The level of a `console` call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConsoleLevel {
+    Log,
+    Debug,
+    Info,
+    Warn,
+    Error,
+    Trace,
+}
+
+impl ConsoleLevel {
+    const ALL: [ConsoleLevel; 6] = [
+        ConsoleLevel::Log,
+        ConsoleLevel::Debug,
+        ConsoleLevel::Info,
+        ConsoleLevel::Warn,
+        ConsoleLevel::Error,
+        ConsoleLevel::Trace,
+    ];
+
+    /// The name of the `console` method for this level.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConsoleLevel::Log => "log",
+            ConsoleLevel::Debug => "debug",
+            ConsoleLevel::Info => "info",
+            ConsoleLevel::Warn => "warn",
+            ConsoleLevel::Error => "error",
+            ConsoleLevel::Trace => "trace",
+        }
+    }
+}
+
+impl fmt::Display for ConsoleLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+/// The type of the callback receiving `console` calls.
+#[cfg(not(feature = "parallel"))]
+pub type ConsoleCallback = Box<dyn for<'js> Fn(ConsoleLevel, &[Value<'js>]) + 'static>;
+/// The type of the callback receiving `console` calls.
+#[cfg(feature = "parallel")]
+pub type ConsoleCallback =
+    Box<dyn for<'js> Fn(ConsoleLevel, &[Value<'js>]) + Send + Sync + 'static>;
+
+/// Define the `console` methods of the global object to forward to the callback.
+pub(crate) fn install(ctx: &Ctx<'_>, callback: ConsoleCallback) -> Result<()> {
+    let globals = ctx.globals();
+    let console = match globals.get::<_, Option<Object>>("console")? {
+        Some(console) => console,
+        None => {
+            let console = Object::new(ctx.clone())?;
+            globals.set("console", console.clone())?;
+            console
+        }
+    };
+
+    let callback = Ref::new(callback);
+    for level in ConsoleLevel::ALL {
+        let callback = callback.clone();
+        let func = Function::new(ctx.clone(), move |args: Rest<Value>| {
+            callback(level, &args.0);
+        })?
+        .with_name(level.as_str())?;
+        console.set(level.as_str(), func)?;
+    }
+    Ok(())
+}
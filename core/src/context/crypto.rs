@@ -0,0 +1,58 @@
+use crate::{Ctx, Error, ErrorKind, Exception, Function, Object, Result, TypedArray, Value};
+
+/// The maximum number of bytes `getRandomValues` fills in one call, as required by the spec.
+const MAX_BYTES: usize = 65536;
+
+/// Define the global `crypto` object with a `getRandomValues` method backed by the OS RNG.
+pub(crate) fn install(ctx: &Ctx<'_>) -> Result<()> {
+    let globals = ctx.globals();
+    let crypto = match globals.get::<_, Option<Object>>("crypto")? {
+        Some(crypto) => crypto,
+        None => {
+            let crypto = Object::new(ctx.clone())?;
+            globals.set("crypto", crypto.clone())?;
+            crypto
+        }
+    };
+
+    let func = Function::new(ctx.clone(), get_random_values)?.with_name("getRandomValues")?;
+    crypto.set("getRandomValues", func)?;
+    Ok(())
+}
+
+fn get_random_values<'js>(ctx: Ctx<'js>, array: Value<'js>) -> Result<Value<'js>> {
+    let is_float_array = array
+        .as_object()
+        .map(|obj| obj.is_typed_array::<f32>() || obj.is_typed_array::<f64>())
+        .unwrap_or(false);
+    let raw = if is_float_array {
+        None
+    } else {
+        TypedArray::<u8>::get_raw_bytes(&array)
+    };
+    let (_, len, ptr, _) = raw.ok_or_else(|| {
+        Error::new_throw(
+            ErrorKind::Type,
+            "The provided value is not an integer typed array",
+        )
+    })?;
+
+    if len > MAX_BYTES {
+        let message =
+            format!("The requested length of {len} bytes exceeds the maximum of {MAX_BYTES} bytes");
+        let exception = Exception::from_message(ctx, &message)?;
+        exception.as_object().set("name", "QuotaExceededError")?;
+        return Err(exception.throw());
+    }
+
+    // SAFETY: the pointer and length describe the bytes of the typed array which is kept alive
+    // by `array`, no script code runs while the slice exists.
+    let bytes = unsafe { std::slice::from_raw_parts_mut(ptr.as_ptr(), len) };
+    getrandom::getrandom(bytes).map_err(|error| {
+        Error::new_throw(
+            ErrorKind::Internal,
+            format!("Unable to get random values: {error}"),
+        )
+    })?;
+    Ok(array)
+}
@@ -4,16 +4,24 @@ use std::{
     fs, mem,
     path::Path,
     ptr::NonNull,
+    time::{Duration, Instant},
 };
 
 #[cfg(feature = "futures")]
 use std::future::Future;
 
+#[cfg(feature = "multi-ctx")]
+use super::clone::ValueCloner;
 #[cfg(feature = "futures")]
 use crate::AsyncContext;
 use crate::{
-    markers::Invariant, qjs, runtime::raw::Opaque, Context, Error, FromJs, Function, IntoJs,
-    Module, Object, Result, String, Value,
+    atom::PredefinedAtom,
+    convert::Coerced,
+    markers::Invariant,
+    qjs,
+    runtime::raw::{update_interrupt_handler, Opaque},
+    Atom, Context, Error, FromJs, Function, IntoJs, Module, Object, RegisteryKey, Result,
+    StdString, String, Value,
 };
 
 /// Eval options.
@@ -121,14 +129,99 @@ impl<'js> Ctx<'js> {
         let src = source.into();
         let len = src.len();
         let src = CString::new(src)?;
-        let val = qjs::JS_Eval(
-            self.ctx.as_ptr(),
-            src.as_ptr(),
-            len as _,
-            file_name.as_ptr(),
-            flag,
-        );
-        self.handle_exception(val)
+        self.evaluate(|| {
+            let val = qjs::JS_Eval(
+                self.ctx.as_ptr(),
+                src.as_ptr(),
+                len as _,
+                file_name.as_ptr(),
+                flag,
+            );
+            self.handle_exception(val)
+        })
+    }
+
+    pub(crate) unsafe fn eval_raw_this<S: Into<Vec<u8>>>(
+        &self,
+        source: S,
+        this: &Object<'js>,
+        file_name: &CStr,
+        flag: i32,
+    ) -> Result<qjs::JSValue> {
+        let src = source.into();
+        let len = src.len();
+        let src = CString::new(src)?;
+        self.evaluate(|| {
+            let val = qjs::JS_EvalThis(
+                self.ctx.as_ptr(),
+                this.as_js_value(),
+                src.as_ptr(),
+                len as _,
+                file_name.as_ptr(),
+                flag,
+            );
+            self.handle_exception(val)
+        })
+    }
+
+    /// The time limit of evaluations set with
+    /// [`Runtime::set_execution_limit`](crate::Runtime::set_execution_limit).
+    pub(crate) fn execution_limit(&self) -> Option<Duration> {
+        unsafe { (*self.get_opaque()).execution_limit }
+    }
+
+    /// Run an evaluation with the execution limit of the runtime.
+    ///
+    /// Returns [`Error::Allocation`] instead of the `out of memory` error QuickJS throws when the
+    /// evaluation exceeds the memory limit of the runtime.
+    pub(crate) fn evaluate<R, F>(&self, f: F) -> Result<R>
+    where
+        F: FnOnce() -> Result<R>,
+    {
+        let rt = unsafe { qjs::JS_GetRuntime(self.ctx.as_ptr()) };
+        // Reset the flag so only allocations failing during this evaluation are reported.
+        unsafe { qjs::JS_TakeOutOfMemory(rt) };
+        let res = self.with_deadline(self.execution_limit(), f);
+        if let Err(Error::Exception) = res {
+            if unsafe { qjs::JS_TakeOutOfMemory(rt) } != 0 {
+                // Drop the error thrown by QuickJS, the context can't do anything useful with it.
+                let _ = self.catch();
+                return Err(Error::Allocation);
+            }
+        }
+        res
+    }
+
+    /// Run `f` with the deadline of the runtime moved to `limit` from now, unless the current
+    /// deadline is earlier.
+    ///
+    /// Returns [`Error::Timeout`] instead of the uncatchable exception raised once the deadline
+    /// set by this call passed.
+    pub(crate) fn with_deadline<R, F>(&self, limit: Option<Duration>, f: F) -> Result<R>
+    where
+        F: FnOnce() -> Result<R>,
+    {
+        let previous = unsafe { (*self.get_opaque()).deadline };
+        let deadline = match limit.and_then(|limit| Instant::now().checked_add(limit)) {
+            Some(deadline) if previous.map(|previous| deadline < previous).unwrap_or(true) => {
+                deadline
+            }
+            _ => return f(),
+        };
+        let guard = DeadlineGuard::set(self, deadline, previous);
+        let res = f();
+        drop(guard);
+        // SAFETY: `f` may have used the opaque, so it is only borrowed again afterwards.
+        let opaque = unsafe { &mut *self.get_opaque() };
+        if opaque.timed_out == Some(deadline) {
+            opaque.timed_out = None;
+            if let Err(Error::Exception) = res {
+                // Discard the `interrupted` error raised by the interrupt handler.
+                let _ = self.catch();
+                return Err(Error::Timeout);
+            }
+        }
+        res
     }
 
     /// Evaluate a script in global context.
@@ -150,6 +243,117 @@ impl<'js> Ctx<'js> {
         })
     }
 
+    /// Evaluate a script which is aborted when it runs longer than the timeout.
+    ///
+    /// Returns [`Error::Timeout`] when the script was aborted. The timeout also applies to
+    /// functions called by the script, an earlier deadline of an enclosing evaluation is kept.
+    pub fn eval_with_timeout<V: FromJs<'js>, S: Into<Vec<u8>>>(
+        &self,
+        source: S,
+        timeout: Duration,
+    ) -> Result<V> {
+        self.with_deadline(Some(timeout), || self.eval(source))
+    }
+
+    /// Evaluate a script and coerce the result to a boolean using the JavaScript truthiness rules.
+    ///
+    /// Unlike `eval::<bool, _>` this accepts any result, e.g. `"0"` and `[]` are `true`.
+    pub fn eval_coerced_bool<S: Into<Vec<u8>>>(&self, source: S) -> Result<bool> {
+        self.eval::<Coerced<bool>, _>(source).map(|res| res.0)
+    }
+
+    /// Evaluate a script and coerce the result to a number in the same way as `Number(result)`.
+    pub fn eval_coerced_number<S: Into<Vec<u8>>>(&self, source: S) -> Result<f64> {
+        self.eval::<Coerced<f64>, _>(source).map(|res| res.0)
+    }
+
+    /// Evaluate a script and coerce the result to a string in the same way as `String(result)`.
+    pub fn eval_coerced_string<S: Into<Vec<u8>>>(&self, source: S) -> Result<StdString> {
+        self.eval::<Coerced<StdString>, _>(source).map(|res| res.0)
+    }
+
+    /// Evaluate a script in global context with a custom `this` value.
+    pub fn eval_with_this<V: FromJs<'js>, S: Into<Vec<u8>>>(
+        &self,
+        source: S,
+        this: Object<'js>,
+    ) -> Result<V> {
+        let file_name = cstr!("eval_script");
+        let flag = EvalOptions::default().to_flag();
+
+        V::from_js(self, unsafe {
+            let val = self.eval_raw_this(source, &this, file_name, flag)?;
+            Value::from_js_value(self.clone(), val)
+        })
+    }
+
+    /// Evaluate a script using an object as its global scope.
+    ///
+    /// All free variables of the script, including builtins like `Array`, are looked up on and
+    /// assigned to `scope` instead of the context globals, and `this` refers to the scope. A
+    /// variable which is not defined on the scope evaluates to `undefined` instead of throwing.
+    ///
+    /// The script is evaluated in sloppy mode inside a `with` statement over a proxy of the scope.
+    /// The source is parsed on its own first, so it can't close the `with` block and sources which
+    /// aren't a valid script on their own are rejected with a syntax error.
+    /// `var` and function declarations still add bindings to the real globals, so use
+    /// `let` and `const` instead. This is a lightweight isolation primitive for cooperating
+    /// code, not a security boundary: values created by the script still use the intrinsics of
+    /// the context, for example `({}).constructor.constructor` is the real `Function`.
+    pub fn eval_sandboxed<V: FromJs<'js>, S: Into<Vec<u8>>>(
+        &self,
+        source: S,
+        scope: Object<'js>,
+    ) -> Result<V> {
+        // Built without the globals, which earlier scripts may have replaced.
+        let handler = Object::new(self.clone())?;
+        handler.set_prototype(None)?;
+        handler.define_value("has", Function::new(self.clone(), || true)?.into_value())?;
+        let get = Function::new(
+            self.clone(),
+            |ctx: Ctx<'js>, target: Object<'js>, key: Value<'js>| -> Result<Value<'js>> {
+                let key = Atom::from_value(ctx.clone(), &key)?;
+                // Without `Symbol.unscopables` every name is looked up on the scope.
+                if key == Atom::from_predefined(ctx.clone(), PredefinedAtom::SymbolUnscopables) {
+                    return Ok(Value::new_undefined(ctx));
+                }
+                target.get(key)
+            },
+        )?;
+        handler.define_value("get", get.into_value())?;
+        let proxy = unsafe {
+            let proxy = qjs::JS_NewProxy(self.as_ptr(), scope.as_js_value(), handler.as_js_value());
+            Object::from_js_value(self.clone(), self.handle_exception(proxy)?)
+        };
+
+        let source = source.into();
+        let file_name = cstr!("eval_script");
+        let flag = EvalOptions {
+            strict: false,
+            ..Default::default()
+        }
+        .to_flag();
+
+        // Only compile the source, wrapping it can't change how a complete script is parsed.
+        unsafe {
+            let compiled = self.eval_raw(
+                source.clone(),
+                file_name,
+                flag | qjs::JS_EVAL_FLAG_COMPILE_ONLY as i32,
+            )?;
+            qjs::JS_FreeValue(self.as_ptr(), compiled);
+        }
+
+        let mut wrapped = b"with (this) {\n".to_vec();
+        wrapped.extend(source);
+        wrapped.extend(b"\n}");
+
+        V::from_js(self, unsafe {
+            let val = self.eval_raw_this(wrapped, &proxy, file_name, flag)?;
+            Value::from_js_value(self.clone(), val)
+        })
+    }
+
     /// Evaluate a script directly from a file.
     pub fn eval_file<V: FromJs<'js>, P: AsRef<Path>>(&self, path: P) -> Result<V> {
         self.eval_file_with_options(path, Default::default())
@@ -225,6 +429,10 @@ impl<'js> Ctx<'js> {
     }
 
     /// Parse json into a JavaScript value.
+    ///
+    /// The input is only parsed as data, unlike evaluating `JSON.parse(...)` with the input
+    /// embedded in a script it can't execute code. Invalid json throws a `SyntaxError`, which is
+    /// returned as an [`Error::Exception`].
     pub fn json_parse<S>(&self, json: S) -> Result<Value<'js>>
     where
         S: Into<Vec<u8>>,
@@ -264,6 +472,9 @@ impl<'js> Ctx<'js> {
     }
 
     /// Stringify a JavaScript value into its JSON representation
+    ///
+    /// Returns `None` for values which have no JSON representation, like `undefined` or functions.
+    /// Cyclic values throw a `TypeError`.
     pub fn json_stringify<V>(&self, value: V) -> Result<Option<String<'js>>>
     where
         V: IntoJs<'js>,
@@ -345,7 +556,109 @@ impl<'js> Ctx<'js> {
         }
     }
 
-    // Creates promise and resolving functions.
+    /// Structurally copy a value into another context of the same runtime.
+    ///
+    /// Primitives are shared while plain objects and arrays are recursively copied into new
+    /// objects of the target context. Shared references and cycles are preserved in the copy.
+    ///
+    /// Values which can not be copied, like functions and class instances, result in an error
+    /// unless `skip_uncloneable` is set. Skipped values are left out of objects and replaced with
+    /// `undefined` in arrays. Other objects, like typed arrays, are copied the same way as by
+    /// [`Value::clone_into`].
+    ///
+    /// Requires the contexts to be used together with [`MultiWith`](crate::MultiWith).
+    #[cfg(feature = "multi-ctx")]
+    #[cfg_attr(feature = "doc-cfg", doc(cfg(feature = "multi-ctx")))]
+    pub fn clone_value_into(
+        &self,
+        value: &Value<'js>,
+        target: &Ctx<'js>,
+        skip_uncloneable: bool,
+    ) -> Result<Value<'js>> {
+        if unsafe { qjs::JS_GetRuntime(self.as_ptr()) != qjs::JS_GetRuntime(target.as_ptr()) } {
+            return Err(Error::UnrelatedRuntime);
+        }
+        ValueCloner::new(self, target, skip_uncloneable)?.clone(value)
+    }
+
+    /// Serialize a set of global variables into bytes.
+    ///
+    /// QuickJS can't snapshot a whole heap: builtin objects, functions, closures and class
+    /// instances can't be serialized. Instead this writes the values of the named globals with
+    /// `JS_WriteObject`, which supports primitives, plain objects, arrays, typed arrays and
+    /// array buffers. Shared references and cycles between the values are preserved.
+    ///
+    /// The format is specific to the QuickJS version and the endianness of the target. Use
+    /// [`Ctx::restore_globals`] to load the snapshot into another context.
+    pub fn snapshot_globals<I, S>(&self, names: I) -> Result<Vec<u8>>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let globals = self.globals();
+        let snapshot = Object::new(self.clone())?;
+        for name in names {
+            let name = name.as_ref();
+            snapshot.set(name, globals.get::<_, Value>(name)?)?;
+        }
+
+        let mut len = mem::MaybeUninit::uninit();
+        let buf = unsafe {
+            qjs::JS_WriteObject(
+                self.as_ptr(),
+                len.as_mut_ptr(),
+                snapshot.as_js_value(),
+                qjs::JS_WRITE_OBJ_REFERENCE as i32,
+            )
+        };
+        if buf.is_null() {
+            return Err(self.raise_exception());
+        }
+        let len = unsafe { len.assume_init() };
+        let bytes = unsafe { std::slice::from_raw_parts(buf, len as _) }.to_vec();
+        unsafe { qjs::js_free(self.as_ptr(), buf as _) };
+        Ok(bytes)
+    }
+
+    /// Define the globals serialized by [`Ctx::snapshot_globals`] in this context.
+    ///
+    /// Existing globals with the same names are overwritten.
+    ///
+    /// # Safety
+    /// QuickJS does not fully validate the serialized data, malformed bytes can corrupt memory.
+    /// User must ensure that the bytes were produced by [`Ctx::snapshot_globals`] with the same
+    /// version of QuickJS, e.g. by only reading snapshots from trusted sources.
+    pub unsafe fn restore_globals(&self, bytes: &[u8]) -> Result<()> {
+        let snapshot = unsafe {
+            let value = qjs::JS_ReadObject(
+                self.as_ptr(),
+                bytes.as_ptr(),
+                bytes.len() as _,
+                qjs::JS_READ_OBJ_REFERENCE as i32,
+            );
+            Value::from_js_value(self.clone(), self.handle_exception(value)?)
+        };
+        let snapshot = snapshot
+            .into_object()
+            .ok_or_else(|| Error::new_from_js("value", "globals snapshot"))?;
+        let globals = self.globals();
+        for prop in snapshot.props::<crate::Atom, Value>() {
+            let (name, value) = prop?;
+            globals.set(name, value)?;
+        }
+        Ok(())
+    }
+
+    /// Create a new pending promise together with its resolving functions.
+    ///
+    /// Returns the promise and the `resolve` and `reject` functions, calling either of them with
+    /// a value settles the promise. Only the first call has any effect, later calls are ignored as
+    /// required by the specification. Dropping both functions without calling them leaves the
+    /// promise pending forever.
+    ///
+    /// The reactions of the promise are run as jobs, so after settling it the pending jobs of the
+    /// runtime have to be executed, e.g. with [`Runtime::execute_pending_job`](crate::Runtime::execute_pending_job),
+    /// for the continuations of the script to run.
     pub fn promise(&self) -> Result<(Object<'js>, Function<'js>, Function<'js>)> {
         let mut funcs = mem::MaybeUninit::<(qjs::JSValue, qjs::JSValue)>::uninit();
 
@@ -363,6 +676,39 @@ impl<'js> Ctx<'js> {
         })
     }
 
+    /// Store a value in the registry of the runtime.
+    ///
+    /// The value is kept alive until it is removed with [`Ctx::remove`] or this context is
+    /// dropped and can be retrieved during later calls to [`Context::with`] with
+    /// [`Ctx::restore`].
+    pub fn store(&self, value: Value<'js>) -> RegisteryKey {
+        let key = RegisteryKey::new();
+        unsafe {
+            (*self.get_opaque())
+                .registery
+                .insert(key.id(), (self.ctx.as_ptr(), value.into_js_value()));
+        }
+        key
+    }
+
+    /// Retrieve a value from the registry.
+    ///
+    /// Returns `None` if the key was not stored in the registry of this runtime.
+    pub fn restore(&self, key: &RegisteryKey) -> Option<Value<'js>> {
+        unsafe {
+            let (_, value) = *(*self.get_opaque()).registery.get(&key.id())?;
+            Some(Value::from_js_value_const(self.clone(), value))
+        }
+    }
+
+    /// Remove a value from the registry, returning it if it was present.
+    pub fn remove(&self, key: RegisteryKey) -> Option<Value<'js>> {
+        unsafe {
+            let (_, value) = (*self.get_opaque()).registery.remove(&key.id())?;
+            Some(Value::from_js_value(self.clone(), value))
+        }
+    }
+
     pub(crate) unsafe fn get_opaque(&self) -> *mut Opaque<'js> {
         let rt = qjs::JS_GetRuntime(self.ctx.as_ptr());
         qjs::JS_GetRuntimeOpaque(rt).cast::<Opaque>()
@@ -422,6 +768,30 @@ impl<'js> Ctx<'js> {
     }
 }
 
+/// Restores the previous deadline of the runtime when dropped, also if the evaluation panics.
+struct DeadlineGuard<'a, 'js> {
+    ctx: &'a Ctx<'js>,
+    previous: Option<Instant>,
+}
+
+impl<'a, 'js> DeadlineGuard<'a, 'js> {
+    fn set(ctx: &'a Ctx<'js>, deadline: Instant, previous: Option<Instant>) -> Self {
+        unsafe { Self::update(ctx, Some(deadline)) };
+        DeadlineGuard { ctx, previous }
+    }
+
+    unsafe fn update(ctx: &Ctx<'js>, deadline: Option<Instant>) {
+        (*ctx.get_opaque()).deadline = deadline;
+        update_interrupt_handler(qjs::JS_GetRuntime(ctx.as_ptr()));
+    }
+}
+
+impl<'a, 'js> Drop for DeadlineGuard<'a, 'js> {
+    fn drop(&mut self) {
+        unsafe { Self::update(self.ctx, self.previous) };
+    }
+}
+
 #[cfg(test)]
 mod test {
 
@@ -441,6 +811,52 @@ mod test {
         });
     }
 
+    #[test]
+    fn eval_coerced() {
+        crate::test_with(|ctx| {
+            assert!(ctx.eval_coerced_bool("[]").unwrap());
+            assert!(ctx.eval_coerced_bool("'0'").unwrap());
+            assert!(!ctx.eval_coerced_bool("0").unwrap());
+            assert!(!ctx.eval_coerced_bool("undefined").unwrap());
+
+            assert_eq!(ctx.eval_coerced_number("'12.5'").unwrap(), 12.5);
+            assert_eq!(ctx.eval_coerced_number("[]").unwrap(), 0.0);
+            assert!(ctx.eval_coerced_number("'abc'").unwrap().is_nan());
+
+            assert_eq!(ctx.eval_coerced_string("[1, 2]").unwrap(), "1,2");
+            assert_eq!(ctx.eval_coerced_string("null").unwrap(), "null");
+
+            assert!(ctx.eval_coerced_bool("throw new Error('oops')").is_err());
+        })
+    }
+
+    #[test]
+    fn promise() {
+        use crate::{Context, Function, Object, Runtime};
+
+        let rt = Runtime::new().unwrap();
+        let ctx = Context::full(&rt).unwrap();
+        ctx.with(|ctx| {
+            let (promise, resolve, reject) = ctx.promise().unwrap();
+            let (pending, _, _) = ctx.promise().unwrap();
+            let track: Function = ctx
+                .eval("globalThis.results = []; (p) => p.then(v => results.push(v), e => results.push('error ' + e))")
+                .unwrap();
+            track.call::<_, Object>((promise,)).unwrap();
+            track.call::<_, Object>((pending,)).unwrap();
+
+            resolve.call::<_, ()>((42,)).unwrap();
+            resolve.call::<_, ()>((43,)).unwrap();
+            reject.call::<_, ()>(("oops",)).unwrap();
+        });
+
+        while rt.execute_pending_job().unwrap() {}
+        ctx.with(|ctx| {
+            let res: String = ctx.eval("results.join()").unwrap();
+            assert_eq!(res, "42");
+        });
+    }
+
     #[test]
     fn eval() {
         use crate::{Context, Runtime};
@@ -617,4 +1033,178 @@ mod test {
             assert_eq!(str, r#"{"a":{"b":1,"c":true},"d":[0,"foo"]}"#);
         })
     }
+
+    #[test]
+    fn json_errors() {
+        use crate::{Context, ErrorKind, Function, Object, Runtime, Undefined};
+
+        let runtime = Runtime::new().unwrap();
+        let ctx = Context::full(&runtime).unwrap();
+        ctx.with(|ctx| {
+            let err = ctx.json_parse("{ a: 1 }").unwrap_err();
+            assert!(err.is_exception());
+            let exception = ctx.catch().into_exception().unwrap();
+            assert_eq!(exception.kind(), Some(ErrorKind::Syntax));
+
+            let value = ctx.json_parse(r#""(() => { throw 1 })()""#).unwrap();
+            assert_eq!(
+                value.as_string().unwrap().to_string().unwrap(),
+                "(() => { throw 1 })()"
+            );
+
+            assert!(ctx.json_stringify(Undefined).unwrap().is_none());
+            let func: Function = ctx.eval("() => {}").unwrap();
+            assert!(ctx.json_stringify(func).unwrap().is_none());
+
+            let obj = Object::new(ctx.clone()).unwrap();
+            obj.set("self", obj.clone()).unwrap();
+            assert!(ctx.json_stringify(obj).unwrap_err().is_exception());
+            let exception = ctx.catch().into_exception().unwrap();
+            assert_eq!(exception.kind(), Some(ErrorKind::Type));
+        })
+    }
+
+    #[cfg(feature = "multi-ctx")]
+    #[test]
+    fn clone_value_into() {
+        use crate::{Context, MultiWith, Object, Runtime, Value};
+
+        let rt = Runtime::new().unwrap();
+        let a = Context::full(&rt).unwrap();
+        let b = Context::full(&rt).unwrap();
+        (&a, &b).with(|(a, b)| {
+            let value: Value = a
+                .eval(
+                    r#"
+                    let x = { n: 1, list: [1, 'two', { three: 3 }], bytes: new Uint8Array([4]), f() {} };
+                    x.self = x;
+                    x
+                    "#,
+                )
+                .unwrap();
+
+            assert!(a.clone_value_into(&value, &b, false).is_err());
+
+            let copy = a.clone_value_into(&value, &b, true).unwrap();
+            b.globals().set("copy", copy.clone()).unwrap();
+            let copy = copy.into_object().unwrap();
+            assert!(!copy.contains_key("f").unwrap());
+            assert_eq!(copy.get::<_, Object>("self").unwrap(), copy);
+
+            let res: bool = b
+                .eval(
+                    "copy.n === 1 && copy.list[1] === 'two' && copy.list[2].three === 3 \
+                     && copy.bytes[0] === 4 && Object.getPrototypeOf(copy) === Object.prototype",
+                )
+                .unwrap();
+            assert!(res);
+        })
+    }
+
+    #[test]
+    fn snapshot_globals() {
+        use crate::{CatchResultExt, Context, Runtime};
+
+        let snapshot = {
+            let rt = Runtime::new().unwrap();
+            let ctx = Context::full(&rt).unwrap();
+            ctx.with(|ctx| {
+                ctx.eval::<(), _>(
+                    r#"
+                    globalThis.config = { name: "worker", limits: [1, 2, 3] };
+                    config.self = config;
+                    globalThis.version = 3;
+                    globalThis.func = () => 1;
+                    "#,
+                )
+                .unwrap();
+                assert!(ctx.snapshot_globals(["func"]).is_err());
+                ctx.snapshot_globals(["config", "version"]).unwrap()
+            })
+        };
+
+        let rt = Runtime::new().unwrap();
+        let ctx = Context::full(&rt).unwrap();
+        ctx.with(|ctx| {
+            unsafe { ctx.restore_globals(&snapshot) }.unwrap();
+            let res: bool = ctx
+                .eval(
+                    r#"config.name === "worker" && config.limits[2] === 3
+                    && config.self === config && version === 3"#,
+                )
+                .catch(&ctx)
+                .unwrap();
+            assert!(res);
+
+            // An unknown version is rejected before any of the data is read.
+            assert!(unsafe { ctx.restore_globals(&[0xff, 0x00]) }.is_err());
+        });
+    }
+
+    #[test]
+    fn eval_with_this() {
+        use crate::{Context, Object, Runtime};
+
+        let runtime = Runtime::new().unwrap();
+        let ctx = Context::full(&runtime).unwrap();
+        ctx.with(|ctx| {
+            let this = Object::new(ctx.clone()).unwrap();
+            this.set("value", 5).unwrap();
+            let res: i32 = ctx.eval_with_this("this.value * 2", this).unwrap();
+            assert_eq!(res, 10);
+        })
+    }
+
+    #[test]
+    fn eval_sandboxed() {
+        use crate::{Context, Error, Object, Runtime};
+
+        let runtime = Runtime::new().unwrap();
+        let ctx = Context::full(&runtime).unwrap();
+        ctx.with(|ctx| {
+            ctx.globals().set("secret", "hidden").unwrap();
+            let scope = Object::new(ctx.clone()).unwrap();
+            scope.set("input", 20).unwrap();
+
+            let res: i32 = ctx
+                .eval_sandboxed(
+                    "output = input + 1; let local = 1; input + local",
+                    scope.clone(),
+                )
+                .unwrap();
+            assert_eq!(res, 21);
+            assert_eq!(scope.get::<_, i32>("output").unwrap(), 21);
+            assert!(!ctx.globals().contains_key("output").unwrap());
+
+            let res: Option<String> = ctx
+                .eval_sandboxed("typeof secret === 'undefined' ? undefined : secret", scope)
+                .unwrap();
+            assert_eq!(res, None);
+
+            let scope = Object::new(ctx.clone()).unwrap();
+            let err = ctx
+                .eval_sandboxed::<(), _>("} globalThis.escaped = 1; {", scope)
+                .unwrap_err();
+            assert!(matches!(err, Error::Exception));
+            let exception = ctx.catch().into_exception().unwrap();
+            assert_eq!(exception.get::<_, String>("name").unwrap(), "SyntaxError");
+            assert!(!ctx.globals().contains_key("escaped").unwrap());
+
+            // Replaced globals don't change the scope of later calls.
+            let _: () = ctx
+                .eval(
+                    r#"
+                    globalThis.Proxy = function (target) { return globalThis; };
+                    globalThis.Symbol = { unscopables: "secret" };
+                    Object.prototype.get = () => "hijacked";
+                    "#,
+                )
+                .unwrap();
+            let scope = Object::new(ctx.clone()).unwrap();
+            let res: Option<String> = ctx
+                .eval_sandboxed("typeof secret === 'undefined' ? undefined : secret", scope)
+                .unwrap();
+            assert_eq!(res, None);
+        })
+    }
 }
@@ -0,0 +1,164 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+
+use crate::{
+    function::{Opt, Rest},
+    Ctx, Error, Function, Mut, Ref, Result, Value,
+};
+
+/// The type of the function creating the futures which timers wait on.
+#[cfg(not(feature = "parallel"))]
+pub type SleepCallback = Box<dyn Fn(Duration) -> Pin<Box<dyn Future<Output = ()>>> + 'static>;
+/// The type of the function creating the futures which timers wait on.
+#[cfg(feature = "parallel")]
+pub type SleepCallback =
+    Box<dyn Fn(Duration) -> Pin<Box<dyn Future<Output = ()>>> + Send + Sync + 'static>;
+
+/// The largest delay in milliseconds, larger delays are clamped like in browsers.
+const MAX_DELAY: f64 = i32::MAX as f64;
+
+#[derive(Default)]
+struct Timers {
+    last_id: u32,
+    // { timer_id: waker_of_the_waiting_future }
+    active: HashMap<u32, Option<Waker>>,
+}
+
+impl Timers {
+    /// Add an active timer, returns its id.
+    ///
+    /// Ids start at 1 and wrap around, skipping the ids of timers which are still active.
+    fn add(&mut self) -> u32 {
+        loop {
+            self.last_id = self.last_id.wrapping_add(1).max(1);
+            if !self.active.contains_key(&self.last_id) {
+                self.active.insert(self.last_id, None);
+                return self.last_id;
+            }
+        }
+    }
+}
+
+/// Report an error of a timer callback as an unhandled promise rejection, so the rejection
+/// tracker of the runtime receives it.
+fn report<'js>(ctx: &Ctx<'js>, error: Error) {
+    error.throw(ctx);
+    let reason = ctx.catch();
+    if let Ok((_promise, _resolve, reject)) = ctx.promise() {
+        let _ = reject.call::<_, ()>((reason,));
+    }
+}
+
+/// A future which waits for the sleep of a timer unless the timer is cleared first.
+///
+/// Resolves to whether the timer is still active.
+struct Timer {
+    sleep: Pin<Box<dyn Future<Output = ()>>>,
+    id: u32,
+    timers: Ref<Mut<Timers>>,
+}
+
+impl Future for Timer {
+    type Output = bool;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<bool> {
+        match self.timers.lock().active.get_mut(&self.id) {
+            Some(waker) => *waker = Some(cx.waker().clone()),
+            None => return Poll::Ready(false),
+        }
+        self.sleep.as_mut().poll(cx).map(|_| true)
+    }
+}
+
+/// Define the `setTimeout`, `setInterval`, `clearTimeout` and `clearInterval` functions of the
+/// global object which spawn their timers on the runtime.
+pub(crate) fn install<'js>(ctx: &Ctx<'js>, sleep: SleepCallback) -> Result<()> {
+    let globals = ctx.globals();
+    let sleep = Ref::new(sleep);
+    let timers = Ref::new(Mut::new(Timers::default()));
+
+    for (name, repeat) in [("setTimeout", false), ("setInterval", true)] {
+        let sleep = sleep.clone();
+        let timers = timers.clone();
+        let func = Function::new(
+            ctx.clone(),
+            move |ctx: Ctx<'js>,
+                  callback: Function<'js>,
+                  delay: Opt<f64>,
+                  args: Rest<Value<'js>>|
+                  -> u32 {
+                let delay = match delay.0 {
+                    Some(delay) if delay.is_finite() && delay > 0.0 => delay.min(MAX_DELAY),
+                    _ => 0.0,
+                };
+                let delay = Duration::from_secs_f64(delay / 1000.0);
+                let id = timers.lock().add();
+
+                let sleep = sleep.clone();
+                let timers = timers.clone();
+                ctx.clone().spawn(async move {
+                    loop {
+                        let timer = Timer {
+                            sleep: sleep(delay),
+                            id,
+                            timers: timers.clone(),
+                        };
+                        if !timer.await {
+                            return;
+                        }
+                        if !repeat {
+                            timers.lock().active.remove(&id);
+                        }
+                        if let Err(error) = callback.call::<_, Value>((Rest(args.0.clone()),)) {
+                            report(&ctx, error);
+                        }
+                        if !repeat {
+                            return;
+                        }
+                    }
+                });
+                id
+            },
+        )?
+        .with_name(name)?;
+        globals.set(name, func)?;
+    }
+
+    for name in ["clearTimeout", "clearInterval"] {
+        let timers = timers.clone();
+        let func = Function::new(ctx.clone(), move |id: Opt<Value<'js>>| {
+            let Some(id) = id.0.and_then(|id| id.as_number()) else {
+                return;
+            };
+            let waker = timers.lock().active.remove(&(id as u32)).flatten();
+            // Wake the waiting future so it finishes without waiting for the sleep.
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        })?
+        .with_name(name)?;
+        globals.set(name, func)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::Timers;
+
+    #[test]
+    fn ids_skip_active_timers() {
+        let mut timers = Timers {
+            last_id: u32::MAX - 1,
+            ..Default::default()
+        };
+        assert_eq!(timers.add(), u32::MAX);
+        timers.active.insert(1, None);
+        assert_eq!(timers.add(), 2);
+    }
+}
@@ -0,0 +1,208 @@
+//! Conversion of Rust types implementing the `serde` traits.
+//!
+//! The [`Serializer`] builds JavaScript values directly and the [`Deserializer`] reads them
+//! directly, so no intermediate JSON string is created. The mapping follows the JSON
+//! representation of `serde`:
+//!
+//! - Numbers are converted to JavaScript numbers, 128 bit integers to `BigInt`s.
+//! - Sequences and tuples are converted to arrays, maps and structs to objects.
+//! - `None` and `()` are converted to `undefined`. Both `undefined` and `null` are accepted as
+//!   `None`.
+//! - Unit enum variants are converted to the name of the variant, other variants to an object with
+//!   the name of the variant as its only property.
+//!
+//! ```
+//! # use rquickjs::{Runtime, Context, Result, js_serde::{self, Serde}};
+//! # use std::collections::HashMap;
+//! # let rt = Runtime::new().unwrap();
+//! # let ctx = Context::full(&rt).unwrap();
+//! # ctx.with(|ctx| -> Result<()> {
+//! let scores = HashMap::from([("alice".to_string(), vec![1, 2])]);
+//! let value = js_serde::to_value(ctx.clone(), &scores)?;
+//! ctx.globals().set("scores", value)?;
+//! assert_eq!(ctx.eval::<i32, _>("scores.alice[1]")?, 2);
+//!
+//! let Serde(scores): Serde<HashMap<String, Vec<i32>>> = ctx.eval("({ bob: [3] })")?;
+//! assert_eq!(scores["bob"], [3]);
+//! # Ok(())
+//! # }).unwrap();
+//! ```
+
+use crate::{Ctx, Error, FromJs, IntoJs, Result, Value};
+use serde::{de::DeserializeOwned, Serialize};
+use std::fmt::Display;
+
+mod de;
+mod ser;
+
+pub use de::Deserializer;
+pub use ser::Serializer;
+
+/// Convert a Rust value to a JavaScript value
+pub fn to_value<'js, T>(ctx: Ctx<'js>, value: &T) -> Result<Value<'js>>
+where
+    T: Serialize + ?Sized,
+{
+    value.serialize(Serializer::new(ctx))
+}
+
+/// Convert a JavaScript value to a Rust value
+pub fn from_value<'js, T>(value: Value<'js>) -> Result<T>
+where
+    T: DeserializeOwned,
+{
+    T::deserialize(Deserializer::new(value))
+}
+
+/// The wrapper for values to convert them with `serde`
+///
+/// Implements [`FromJs`] for types implementing `Deserialize` and [`IntoJs`] for types implementing
+/// `Serialize`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct Serde<T>(pub T);
+
+impl<'js, T> FromJs<'js> for Serde<T>
+where
+    T: DeserializeOwned,
+{
+    fn from_js(_: &Ctx<'js>, value: Value<'js>) -> Result<Self> {
+        from_value(value).map(Serde)
+    }
+}
+
+impl<'js, T> IntoJs<'js> for Serde<T>
+where
+    T: Serialize,
+{
+    fn into_js(self, ctx: &Ctx<'js>) -> Result<Value<'js>> {
+        to_value(ctx.clone(), &self.0)
+    }
+}
+
+impl serde::ser::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error::new_into_js_message("Serialize", "value", msg.to_string())
+    }
+}
+
+impl serde::de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error::new_from_js_message("value", "Deserialize", msg.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::to_value;
+    use crate::{js_serde::Serde, test_with, Object, StdString, Value};
+    use serde::{Deserialize, Serialize};
+    use std::collections::BTreeMap;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    enum Shape {
+        Empty,
+        Circle(f64),
+        Rect { width: u32, height: u32 },
+        Line(i32, i32),
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Scene {
+        name: String,
+        shapes: Vec<Shape>,
+        tags: BTreeMap<u32, String>,
+        parent: Option<Box<Scene>>,
+        id: u64,
+        checksum: i128,
+        visible: bool,
+    }
+
+    #[test]
+    fn round_trip() {
+        test_with(|ctx| {
+            let scene = Scene {
+                name: "main".to_string(),
+                shapes: vec![
+                    Shape::Empty,
+                    Shape::Circle(1.5),
+                    Shape::Rect {
+                        width: 3_000_000_000,
+                        height: 2,
+                    },
+                    Shape::Line(-1, 1),
+                ],
+                tags: BTreeMap::from([(1, "a".to_string()), (20, "b".to_string())]),
+                parent: None,
+                id: 1 << 40,
+                checksum: -(1 << 100),
+                visible: true,
+            };
+            ctx.globals().set("scene", Serde(&scene)).unwrap();
+            let checks: bool = ctx
+                .eval(
+                    r#"
+                    scene.name === "main" && scene.shapes[0] === "Empty"
+                        && scene.shapes[1].Circle === 1.5 && scene.shapes[2].Rect.width === 3e9
+                        && scene.shapes[3].Line[0] === -1 && scene.tags[20] === "b"
+                        && scene.parent === undefined && scene.checksum === -(2n ** 100n)
+                "#,
+                )
+                .unwrap();
+            assert!(checks);
+            let Serde(back) = ctx.globals().get::<_, Serde<Scene>>("scene").unwrap();
+            assert_eq!(back, scene);
+        })
+    }
+
+    #[test]
+    fn from_script() {
+        test_with(|ctx| {
+            let Serde(shape): Serde<Shape> =
+                ctx.eval("({ Rect: { width: 2, height: 3.0 } })").unwrap();
+            assert_eq!(
+                shape,
+                Shape::Rect {
+                    width: 2,
+                    height: 3
+                }
+            );
+            let Serde(values): Serde<Vec<Option<f64>>> = ctx.eval("[1, null, 0.5]").unwrap();
+            assert_eq!(values, [Some(1.0), None, Some(0.5)]);
+
+            let err = ctx.eval::<Serde<Shape>, _>("'Triangle'").unwrap_err();
+            assert!(err.is_from_js());
+            assert!(ctx.eval::<Serde<u8>, _>("256").is_err());
+            assert!(ctx.eval::<Serde<u32>, _>("1.5").is_err());
+            assert!(ctx.eval::<Serde<String>, _>("(() => {})").is_err());
+
+            let value: Value = ctx.eval("({ a: [1, 'b'] })").unwrap();
+            let Serde(map): Serde<BTreeMap<String, (i32, String)>> = value.get().unwrap();
+            assert_eq!(map["a"], (1, "b".to_string()));
+
+            let err = ctx
+                .eval::<Serde<serde::de::IgnoredAny>, _>("const a = { b: [] }; a.b.push(a); a")
+                .unwrap_err();
+            assert!(err.to_string().contains("depth"));
+        })
+    }
+
+    #[test]
+    fn object_keys() {
+        test_with(|ctx| {
+            let map = BTreeMap::from([("__proto__".to_string(), 1)]);
+            let object: Object = to_value(ctx.clone(), &map).unwrap().into_object().unwrap();
+            assert!(object.has_own("__proto__").unwrap());
+            assert_eq!(object.get::<_, i32>("__proto__").unwrap(), 1);
+
+            let map = BTreeMap::from([(1, "a"), (2, "b")]);
+            let object: Object = to_value(ctx.clone(), &map).unwrap().into_object().unwrap();
+            assert_eq!(object.get::<_, StdString>("2").unwrap(), "b");
+
+            let map = BTreeMap::from([((1, 2), "a"), ((3, 4), "b")]);
+            assert!(to_value(ctx.clone(), &map).is_err());
+            let map = BTreeMap::from([((), "a")]);
+            assert!(to_value(ctx.clone(), &map).is_err());
+        })
+    }
+}
@@ -0,0 +1,357 @@
+use crate::{Array, Error, Filter, Object, Result, StdString, Type, Value};
+use serde::{
+    de::{self, value::StringDeserializer, DeserializeSeed, Visitor},
+    forward_to_deserialize_any,
+};
+
+/// The maximum nesting depth of arrays and objects, which also stops at cyclic values
+const MAX_DEPTH: usize = 256;
+
+/// A deserializer which reads Rust values from a JavaScript value
+///
+/// Arrays and objects nested deeper than 256 levels, including cyclic values, result in an error.
+pub struct Deserializer<'js> {
+    value: Value<'js>,
+    depth: usize,
+}
+
+impl<'js> Deserializer<'js> {
+    /// Create a deserializer reading the given value
+    pub fn new(value: Value<'js>) -> Self {
+        Self { value, depth: 0 }
+    }
+
+    fn unsupported(&self) -> Error {
+        Error::new_from_js(self.value.type_name(), "Deserialize")
+    }
+
+    /// Returns the depth of the values contained in the current value
+    fn nested_depth(&self) -> Result<usize> {
+        if self.depth >= MAX_DEPTH {
+            return Err(Error::new_from_js_message(
+                self.value.type_name(),
+                "Deserialize",
+                "Maximum nesting depth exceeded, the value may be cyclic",
+            ));
+        }
+        Ok(self.depth + 1)
+    }
+}
+
+impl<'de, 'js> de::Deserializer<'de> for Deserializer<'js> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.value.type_of() {
+            Type::Uninitialized | Type::Undefined | Type::Null => visitor.visit_unit(),
+            Type::Bool => visitor.visit_bool(self.value.as_bool().unwrap()),
+            Type::Int => visitor.visit_i32(self.value.as_int().unwrap()),
+            Type::Float => {
+                let value = self.value.as_float().unwrap();
+                // JavaScript doesn't distinguish integers and floats, so integral numbers are
+                // visited as integers to allow deserializing them into integer types.
+                if value.fract() == 0.0
+                    && value.abs() < i64::MAX as f64
+                    && !(value == 0.0 && value.is_sign_negative())
+                {
+                    visitor.visit_i64(value as i64)
+                } else {
+                    visitor.visit_f64(value)
+                }
+            }
+            Type::String => {
+                let string = self.value.into_string().unwrap().to_string()?;
+                visitor.visit_string(string)
+            }
+            Type::BigInt => {
+                let big_int = self.value.into_big_int().unwrap();
+                match big_int.clone().to_i64() {
+                    Ok(value) => visitor.visit_i64(value),
+                    Err(_) => match big_int.clone().to_i128() {
+                        Ok(value) => visitor.visit_i128(value),
+                        Err(_) => visitor.visit_u128(big_int.to_u128()?),
+                    },
+                }
+            }
+            Type::Array => {
+                let depth = self.nested_depth()?;
+                let array = self.value.into_array().unwrap();
+                visitor.visit_seq(SeqAccess::new(array, depth))
+            }
+            Type::Object | Type::Exception => {
+                let depth = self.nested_depth()?;
+                let object = self.value.into_object().unwrap();
+                visitor.visit_map(MapAccess::new(object, depth)?)
+            }
+            _ => Err(self.unsupported()),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if self.value.type_of().is_void() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        match self.value.type_of() {
+            Type::String => visitor.visit_enum(EnumAccess {
+                variant: self.value.into_string().unwrap().to_string()?,
+                value: None,
+                depth: self.depth,
+            }),
+            Type::Object => {
+                let depth = self.nested_depth()?;
+                let object = self.value.into_object().unwrap();
+                let mut props = object.own_props::<StdString, Value>(Filter::default());
+                match (props.next(), props.next()) {
+                    (Some(prop), None) => {
+                        let (variant, value) = prop?;
+                        visitor.visit_enum(EnumAccess {
+                            variant,
+                            value: Some(value),
+                            depth,
+                        })
+                    }
+                    _ => Err(Error::new_from_js_message(
+                        "object",
+                        "enum",
+                        "Expected an object with a single property",
+                    )),
+                }
+            }
+            _ => Err(Error::new_from_js(self.value.type_name(), "enum")),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct identifier
+        ignored_any
+    }
+}
+
+struct SeqAccess<'js> {
+    array: Array<'js>,
+    index: usize,
+    len: usize,
+    depth: usize,
+}
+
+impl<'js> SeqAccess<'js> {
+    fn new(array: Array<'js>, depth: usize) -> Self {
+        let len = array.len();
+        Self {
+            array,
+            index: 0,
+            len,
+            depth,
+        }
+    }
+}
+
+impl<'de, 'js> de::SeqAccess<'de> for SeqAccess<'js> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        if self.index >= self.len {
+            return Ok(None);
+        }
+        let value: Value = self.array.get(self.index)?;
+        self.index += 1;
+        let depth = self.depth;
+        seed.deserialize(Deserializer { value, depth }).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len - self.index)
+    }
+}
+
+/// Reads the own enumerable string keyed properties of an object
+struct MapAccess<'js> {
+    object: Object<'js>,
+    keys: std::vec::IntoIter<StdString>,
+    key: Option<StdString>,
+    depth: usize,
+}
+
+impl<'js> MapAccess<'js> {
+    fn new(object: Object<'js>, depth: usize) -> Result<Self> {
+        let keys = object.keys::<StdString>().collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            object,
+            keys: keys.into_iter(),
+            key: None,
+            depth,
+        })
+    }
+}
+
+impl<'de, 'js> de::MapAccess<'de> for MapAccess<'js> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        let Some(key) = self.keys.next() else {
+            return Ok(None);
+        };
+        let res = seed.deserialize(KeyDeserializer(key.clone())).map(Some);
+        self.key = Some(key);
+        res
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        let key = self
+            .key
+            .take()
+            .expect("next_value_seed should be called after next_key_seed");
+        let value: Value = self.object.get(key)?;
+        let depth = self.depth;
+        seed.deserialize(Deserializer { value, depth })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.keys.len())
+    }
+}
+
+/// Deserializes a property name, which can also be parsed as a number
+struct KeyDeserializer(StdString);
+
+macro_rules! deserialize_key_number {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+                match self.0.parse() {
+                    Ok(value) => visitor.$visit(value),
+                    Err(_) => visitor.visit_string(self.0),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for KeyDeserializer {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_string(self.0)
+    }
+
+    deserialize_key_number! {
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        StringDeserializer::<Error>::new(self.0).deserialize_enum(name, variants, visitor)
+    }
+
+    forward_to_deserialize_any! {
+        bool i128 u128 char str string bytes byte_buf option unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct EnumAccess<'js> {
+    variant: StdString,
+    value: Option<Value<'js>>,
+    depth: usize,
+}
+
+impl<'de, 'js> de::EnumAccess<'de> for EnumAccess<'js> {
+    type Error = Error;
+    type Variant = VariantAccess<'js>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self::Variant)> {
+        let variant = seed.deserialize(StringDeserializer::<Error>::new(self.variant))?;
+        let access = VariantAccess {
+            value: self.value,
+            depth: self.depth,
+        };
+        Ok((variant, access))
+    }
+}
+
+struct VariantAccess<'js> {
+    value: Option<Value<'js>>,
+    depth: usize,
+}
+
+impl<'js> VariantAccess<'js> {
+    /// Returns the deserializer for the fields of the variant
+    fn value(self, kind: &'static str) -> Result<Deserializer<'js>> {
+        let value = self
+            .value
+            .ok_or_else(|| Error::new_from_js("string", kind))?;
+        Ok(Deserializer {
+            value,
+            depth: self.depth,
+        })
+    }
+}
+
+impl<'de, 'js> de::VariantAccess<'de> for VariantAccess<'js> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        match self.value {
+            Some(value) if !value.type_of().is_void() => {
+                Err(Error::new_from_js(value.type_name(), "unit variant"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
+        seed.deserialize(self.value("newtype variant")?)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value> {
+        de::Deserializer::deserialize_seq(self.value("tuple variant")?, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        de::Deserializer::deserialize_map(self.value("struct variant")?, visitor)
+    }
+}
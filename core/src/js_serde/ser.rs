@@ -0,0 +1,370 @@
+use crate::{Array, Atom, BigInt, Ctx, Error, IntoJs, Object, Result, String, Type, Value};
+use serde::{ser, Serialize};
+
+/// A serializer which converts Rust values into JavaScript values
+pub struct Serializer<'js> {
+    ctx: Ctx<'js>,
+}
+
+impl<'js> Serializer<'js> {
+    /// Create a serializer producing values in the given context
+    pub fn new(ctx: Ctx<'js>) -> Self {
+        Self { ctx }
+    }
+
+    fn serialize<T: Serialize + ?Sized>(&self, value: &T) -> Result<Value<'js>> {
+        value.serialize(Serializer::new(self.ctx.clone()))
+    }
+
+    /// Wraps a value in an object with the variant name as the only property
+    fn variant(&self, variant: &'static str, value: Value<'js>) -> Result<Value<'js>> {
+        let object = Object::new(self.ctx.clone())?;
+        object.define_value(variant, value)?;
+        Ok(object.into_value())
+    }
+}
+
+impl<'js> ser::Serializer for Serializer<'js> {
+    type Ok = Value<'js>;
+    type Error = Error;
+
+    type SerializeSeq = SerializeArray<'js>;
+    type SerializeTuple = SerializeArray<'js>;
+    type SerializeTupleStruct = SerializeArray<'js>;
+    type SerializeTupleVariant = SerializeVariant<'js, SerializeArray<'js>>;
+    type SerializeMap = SerializeObject<'js>;
+    type SerializeStruct = SerializeObject<'js>;
+    type SerializeStructVariant = SerializeVariant<'js, SerializeObject<'js>>;
+
+    fn serialize_bool(self, v: bool) -> Result<Value<'js>> {
+        Ok(Value::new_bool(self.ctx, v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Value<'js>> {
+        v.into_js(&self.ctx)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Value<'js>> {
+        v.into_js(&self.ctx)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Value<'js>> {
+        v.into_js(&self.ctx)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Value<'js>> {
+        v.into_js(&self.ctx)
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Value<'js>> {
+        BigInt::from_i128(self.ctx, v).map(BigInt::into_value)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Value<'js>> {
+        v.into_js(&self.ctx)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Value<'js>> {
+        v.into_js(&self.ctx)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Value<'js>> {
+        v.into_js(&self.ctx)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Value<'js>> {
+        v.into_js(&self.ctx)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Value<'js>> {
+        BigInt::from_u128(self.ctx, v).map(BigInt::into_value)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Value<'js>> {
+        v.into_js(&self.ctx)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Value<'js>> {
+        v.into_js(&self.ctx)
+    }
+
+    fn serialize_char(self, v: char) -> Result<Value<'js>> {
+        self.serialize_str(v.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Value<'js>> {
+        String::from_str(self.ctx, v).map(String::into_value)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Value<'js>> {
+        Array::from_iter(self.ctx, v.iter().copied()).map(Array::into_value)
+    }
+
+    fn serialize_none(self) -> Result<Value<'js>> {
+        Ok(Value::new_undefined(self.ctx))
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Value<'js>> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Value<'js>> {
+        Ok(Value::new_undefined(self.ctx))
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Value<'js>> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Value<'js>> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Value<'js>> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Value<'js>> {
+        let value = self.serialize(value)?;
+        self.variant(variant, value)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<SerializeArray<'js>> {
+        SerializeArray::new(self)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<SerializeArray<'js>> {
+        SerializeArray::new(self)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<SerializeArray<'js>> {
+        SerializeArray::new(self)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<SerializeVariant<'js, SerializeArray<'js>>> {
+        let ser = Serializer::new(self.ctx.clone());
+        Ok(SerializeVariant {
+            ser: self,
+            variant,
+            inner: SerializeArray::new(ser)?,
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<SerializeObject<'js>> {
+        SerializeObject::new(self)
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<SerializeObject<'js>> {
+        SerializeObject::new(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<SerializeVariant<'js, SerializeObject<'js>>> {
+        let ser = Serializer::new(self.ctx.clone());
+        Ok(SerializeVariant {
+            ser: self,
+            variant,
+            inner: SerializeObject::new(ser)?,
+        })
+    }
+}
+
+/// Serializes sequences and tuples into an array
+pub struct SerializeArray<'js> {
+    ser: Serializer<'js>,
+    array: Array<'js>,
+    len: usize,
+}
+
+impl<'js> SerializeArray<'js> {
+    fn new(ser: Serializer<'js>) -> Result<Self> {
+        let array = Array::new(ser.ctx.clone())?;
+        Ok(Self { ser, array, len: 0 })
+    }
+
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let value = self.ser.serialize(value)?;
+        self.array.set(self.len, value)?;
+        self.len += 1;
+        Ok(())
+    }
+}
+
+impl<'js> ser::SerializeSeq for SerializeArray<'js> {
+    type Ok = Value<'js>;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value<'js>> {
+        Ok(self.array.into_value())
+    }
+}
+
+impl<'js> ser::SerializeTuple for SerializeArray<'js> {
+    type Ok = Value<'js>;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value<'js>> {
+        Ok(self.array.into_value())
+    }
+}
+
+impl<'js> ser::SerializeTupleStruct for SerializeArray<'js> {
+    type Ok = Value<'js>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value<'js>> {
+        Ok(self.array.into_value())
+    }
+}
+
+/// Serializes maps and structs into an object
+pub struct SerializeObject<'js> {
+    ser: Serializer<'js>,
+    object: Object<'js>,
+    key: Option<Atom<'js>>,
+}
+
+impl<'js> SerializeObject<'js> {
+    fn new(ser: Serializer<'js>) -> Result<Self> {
+        let object = Object::new(ser.ctx.clone())?;
+        Ok(Self {
+            ser,
+            object,
+            key: None,
+        })
+    }
+}
+
+impl<'js> ser::SerializeMap for SerializeObject<'js> {
+    type Ok = Value<'js>;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        let key = self.ser.serialize(key)?;
+        // Like with JSON other keys would be converted to strings which could collide.
+        if !matches!(
+            key.type_of(),
+            Type::String | Type::Int | Type::Float | Type::BigInt
+        ) {
+            return Err(Error::new_into_js_message(
+                key.type_name(),
+                "property key",
+                "Map keys must be strings or numbers",
+            ));
+        }
+        self.key = Some(Atom::from_value(self.ser.ctx.clone(), &key)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let key = self
+            .key
+            .take()
+            .expect("serialize_value should be called after serialize_key");
+        let value = self.ser.serialize(value)?;
+        self.object.define_value(key, value)
+    }
+
+    fn end(self) -> Result<Value<'js>> {
+        Ok(self.object.into_value())
+    }
+}
+
+impl<'js> ser::SerializeStruct for SerializeObject<'js> {
+    type Ok = Value<'js>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        let value = self.ser.serialize(value)?;
+        self.object.define_value(key, value)
+    }
+
+    fn end(self) -> Result<Value<'js>> {
+        Ok(self.object.into_value())
+    }
+}
+
+/// Serializes the fields of an enum variant and wraps them in an object with the variant name
+pub struct SerializeVariant<'js, S> {
+    ser: Serializer<'js>,
+    variant: &'static str,
+    inner: S,
+}
+
+impl<'js> ser::SerializeTupleVariant for SerializeVariant<'js, SerializeArray<'js>> {
+    type Ok = Value<'js>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.inner.push(value)
+    }
+
+    fn end(self) -> Result<Value<'js>> {
+        let value = ser::SerializeSeq::end(self.inner)?;
+        self.ser.variant(self.variant, value)
+    }
+}
+
+impl<'js> ser::SerializeStructVariant for SerializeVariant<'js, SerializeObject<'js>> {
+    type Ok = Value<'js>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        ser::SerializeStruct::serialize_field(&mut self.inner, key, value)
+    }
+
+    fn end(self) -> Result<Value<'js>> {
+        let value = ser::SerializeStruct::end(self.inner)?;
+        self.ser.variant(self.variant, value)
+    }
+}
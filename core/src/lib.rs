@@ -42,7 +42,9 @@ macro_rules! cstr {
 
 pub mod markers;
 mod result;
-pub use result::{CatchResultExt, CaughtError, CaughtResult, Error, Result, ThrowResultExt};
+pub use result::{
+    CatchResultExt, CaughtError, CaughtResult, Error, ErrorKind, Result, ThrowResultExt,
+};
 mod safe_ref;
 pub(crate) use safe_ref::*;
 pub mod runtime;
@@ -58,12 +60,15 @@ pub use context::AsyncContext;
 pub use context::MultiWith;
 pub use context::{Context, Ctx};
 mod persistent;
+mod registery_key;
 mod value;
 pub use persistent::{Outlive, Persistent};
+pub use registery_key::RegisteryKey;
 pub use value::{
-    array, atom, convert, function, module, object, Array, Atom, BigInt, Coerced, Exception,
-    Filter, FromAtom, FromIteratorJs, FromJs, Function, IntoAtom, IntoJs, IteratorJs, Module, Null,
-    Object, String, Symbol, Type, Undefined, Value,
+    array, atom, convert, date, function, map, module, object, regexp, set, Array, Atom, BigInt,
+    Coerced, Date, Exception, Filter, FromAtom, FromIteratorJs, FromJs, Function, IntoAtom, IntoJs,
+    IteratorJs, Map, Module, Null, Object, PromiseObject, PromiseState, RegExp, Set, StrRef,
+    String, Symbol, Type, Undefined, Value,
 };
 
 pub mod class;
@@ -71,7 +76,7 @@ pub use class::Class;
 
 #[cfg(feature = "array-buffer")]
 #[cfg_attr(feature = "doc-cfg", doc(cfg(feature = "array-buffer")))]
-pub use value::{ArrayBuffer, TypedArray};
+pub use value::{ArrayBuffer, SharedMemory, TypedArray};
 
 pub(crate) use std::{result::Result as StdResult, string::String as StdString};
 
@@ -87,12 +92,19 @@ pub mod allocator;
 #[cfg_attr(feature = "doc-cfg", doc(cfg(feature = "loader")))]
 pub mod loader;
 
+#[cfg(feature = "serde")]
+#[cfg_attr(feature = "doc-cfg", doc(cfg(feature = "serde")))]
+pub mod js_serde;
+
 pub mod prelude {
     //! A group of often used types.
     #[cfg(feature = "multi-ctx")]
     pub use crate::context::MultiWith;
     pub use crate::{
-        convert::{Coerced, FromAtom, FromIteratorJs, FromJs, IntoAtom, IntoJs, IteratorJs, List},
+        convert::{
+            Coerced, FromAtom, FromIteratorJs, FromJs, IgnorePoison, IntoAtom, IntoJs, IteratorJs,
+            List, Saturated,
+        },
         function::{
             Exhaustive, Flat, Func, FuncArg, IntoArg, IntoArgs, MutFn, OnceFn, Opt, Rest, This,
         },
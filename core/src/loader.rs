@@ -2,7 +2,7 @@
 
 use std::{ffi::CStr, ptr};
 
-use crate::{module::ModuleData, qjs, Ctx, Module, Result};
+use crate::{module::ModuleData, qjs, Ctx, Module, Object, Result};
 
 mod builtin_resolver;
 pub use builtin_resolver::BuiltinResolver;
@@ -10,9 +10,24 @@ pub use builtin_resolver::BuiltinResolver;
 mod file_resolver;
 pub use file_resolver::FileResolver;
 
+mod node_resolver;
+pub use node_resolver::NodeResolver;
+
+mod map_resolver;
+pub use map_resolver::MapResolver;
+
 mod script_loader;
 pub use script_loader::ScriptLoader;
 
+mod json_loader;
+pub use json_loader::JsonLoader;
+
+mod text_loader;
+pub use text_loader::TextLoader;
+
+mod transform_loader;
+pub use transform_loader::TransformLoader;
+
 mod builtin_loader;
 pub use builtin_loader::BuiltinLoader;
 
@@ -22,12 +37,30 @@ pub use module_loader::ModuleLoader;
 mod compile;
 pub use compile::Compile;
 
+mod reload;
+pub use reload::Reload;
+
+mod cache;
+pub use cache::Cache;
+
 #[cfg(feature = "dyn-load")]
 mod native_loader;
 #[cfg(feature = "dyn-load")]
 #[cfg_attr(feature = "doc-cfg", doc(cfg(feature = "dyn-load")))]
 pub use native_loader::NativeLoader;
 
+#[cfg(feature = "http")]
+mod http_resolver;
+#[cfg(feature = "http")]
+#[cfg_attr(feature = "doc-cfg", doc(cfg(feature = "http")))]
+pub use http_resolver::HttpResolver;
+
+#[cfg(feature = "http")]
+mod http_loader;
+#[cfg(feature = "http")]
+#[cfg_attr(feature = "doc-cfg", doc(cfg(feature = "http")))]
+pub use http_loader::{HttpLoader, Transport};
+
 pub mod bundle;
 
 #[cfg(feature = "phf")]
@@ -41,6 +74,11 @@ pub type Bundle = bundle::Bundle<bundle::ScaBundleData<&'static [u8]>>;
 mod util;
 
 /// Module resolver interface
+///
+/// Tuples of up to 8 resolvers are resolvers too. They try each resolver in order and return the
+/// first name which was resolved, so e.g. `(BuiltinResolver, FileResolver)` falls back to the
+/// filesystem for modules which aren't builtin. A resolver which returns any error other than
+/// [`Error::Resolving`](crate::Error::Resolving) ends the chain.
 #[cfg_attr(feature = "doc-cfg", doc(cfg(feature = "loader")))]
 pub trait Resolver {
     /// Normalize module name
@@ -66,10 +104,22 @@ pub trait Resolver {
 }
 
 /// Module loader interface
+///
+/// Tuples of up to 8 loaders are [`RawLoader`]s which try each loader in order and return the
+/// first module which was loaded. A loader which returns any error other than
+/// [`Error::Loading`](crate::Error::Loading) ends the chain.
 #[cfg_attr(feature = "doc-cfg", doc(cfg(feature = "loader")))]
 pub trait Loader {
     /// Load module by name
     fn load<'js>(&mut self, ctx: &Ctx<'js>, name: &str) -> Result<ModuleData>;
+
+    /// Populate the `import.meta` object of a module loaded by this loader
+    ///
+    /// Called after the module was declared and before it is evaluated, so properties like
+    /// `import.meta.url` are available to the module code. Does nothing by default.
+    fn import_meta<'js>(&mut self, _ctx: &Ctx<'js>, _name: &str, _meta: Object<'js>) -> Result<()> {
+        Ok(())
+    }
 }
 
 /// The Raw Module loader interface.
@@ -93,6 +143,7 @@ pub unsafe trait RawLoader {
 unsafe impl<T: Loader> RawLoader for T {
     unsafe fn raw_load<'js>(&mut self, ctx: &Ctx<'js>, name: &str) -> Result<Module<'js>> {
         let res = self.load(ctx, name)?.unsafe_declare(ctx.clone())?;
+        self.import_meta(ctx, name, res.meta()?)?;
         Ok(res)
     }
 }
@@ -270,9 +321,9 @@ loader_impls!(A B C D E F G H);
 
 #[cfg(test)]
 mod test {
-    use crate::{module::ModuleData, Context, Ctx, Error, Result, Runtime};
+    use crate::{module::ModuleData, CatchResultExt, Context, Ctx, Error, Object, Result, Runtime};
 
-    use super::{Loader, Resolver};
+    use super::{BuiltinLoader, BuiltinResolver, Loader, NodeResolver, Resolver, ScriptLoader};
 
     struct TestResolver;
 
@@ -300,12 +351,24 @@ mod test {
                     r#"
                       export const n = 123;
                       export const s = "abc";
+                      export const url = import.meta.url;
+                      export const main = import.meta.main;
                     "#,
                 ))
             } else {
                 Err(Error::new_loading_message(name, "unable to load"))
             }
         }
+
+        fn import_meta<'js>(
+            &mut self,
+            _ctx: &Ctx<'js>,
+            name: &str,
+            meta: Object<'js>,
+        ) -> Result<()> {
+            meta.set("url", format!("test://{name}"))?;
+            meta.set("main", false)
+        }
     }
 
     #[test]
@@ -351,4 +414,102 @@ mod test {
                 .expect("Unable to resolve");
         })
     }
+
+    #[test]
+    fn dynamic_import() {
+        let rt = Runtime::new().unwrap();
+        let ctx = Context::full(&rt).unwrap();
+        rt.set_loader(TestResolver, TestLoader);
+        ctx.with(|ctx| {
+            let _module = ctx
+                .clone()
+                .compile(
+                    "loader",
+                    r#"
+                      import("test").then((m) => { globalThis.n = m.n; });
+                      import("test_").catch((e) => { globalThis.error = e.message; });
+                    "#,
+                )
+                .unwrap();
+        });
+        rt.execute_pending_jobs().unwrap();
+        ctx.with(|ctx| {
+            let n: i32 = ctx.globals().get("n").unwrap();
+            assert_eq!(n, 123);
+            let error: String = ctx.globals().get("error").unwrap();
+            assert!(error.contains("test_"), "{error}");
+        })
+    }
+
+    #[test]
+    fn import_meta() {
+        let rt = Runtime::new().unwrap();
+        let ctx = Context::full(&rt).unwrap();
+        rt.set_loader(TestResolver, TestLoader);
+        ctx.with(|ctx| {
+            let _module = ctx
+                .clone()
+                .compile(
+                    "loader",
+                    r#"
+                      import { url, main } from "test";
+                      globalThis.url = url;
+                      globalThis.main = main;
+                    "#,
+                )
+                .unwrap();
+            let url: String = ctx.globals().get("url").unwrap();
+            let main: bool = ctx.globals().get("main").unwrap();
+            assert_eq!(url, "test://test");
+            assert!(!main);
+        })
+    }
+
+    #[test]
+    fn chained_loaders() {
+        let dir = std::env::temp_dir().join(format!("rquickjs-chain-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("file.js"), "export default 'file';").unwrap();
+
+        let rt = Runtime::new().unwrap();
+        let ctx = Context::full(&rt).unwrap();
+        rt.set_loader(
+            (
+                BuiltinResolver::default().with_module("virtual.js"),
+                NodeResolver::default(),
+            ),
+            (
+                ScriptLoader::default(),
+                BuiltinLoader::default().with_module("virtual.js", "export default 'builtin';"),
+            ),
+        );
+        ctx.with(|ctx| {
+            let main = dir.join("main.js");
+            let main = main.to_str().unwrap();
+            let _module = ctx
+                .clone()
+                .compile(
+                    main,
+                    r#"
+                      import file from "./file.js";
+                      import builtin from "virtual.js";
+                      globalThis.result = [file, builtin].join();
+                    "#,
+                )
+                .catch(&ctx)
+                .unwrap();
+            let result: String = ctx.globals().get("result").unwrap();
+            assert_eq!(result, "file,builtin");
+
+            let err = ctx
+                .clone()
+                .compile(main, "import missing from 'missing';")
+                .map(|_| ())
+                .catch(&ctx)
+                .unwrap_err();
+            assert!(err.to_string().contains("'missing'"), "{err}");
+        });
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
 }
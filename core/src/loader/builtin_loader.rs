@@ -3,6 +3,12 @@ use std::collections::HashMap;
 
 /// The builtin script module loader
 ///
+/// Loads modules from sources registered in memory, so tests and embedding applications don't
+/// need to touch the filesystem. The sources are kept after loading, so every context of the
+/// runtime can import the modules. Use it together with a
+/// [`BuiltinResolver`](crate::loader::BuiltinResolver) or use a
+/// [`MapResolver`](crate::loader::MapResolver) which resolves and loads from one map.
+///
 /// This loader can be used as the nested backing loader in user-defined loaders.
 #[derive(Debug, Default)]
 pub struct BuiltinLoader {
@@ -30,9 +36,35 @@ impl BuiltinLoader {
 
 impl Loader for BuiltinLoader {
     fn load<'js>(&mut self, _ctx: &Ctx<'js>, path: &str) -> Result<ModuleData> {
-        match self.modules.remove(path) {
-            Some(source) => Ok(ModuleData::source(path, source)),
+        match self.modules.get(path) {
+            Some(source) => Ok(ModuleData::source(path, source.clone())),
             _ => Err(Error::new_loading(path)),
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::BuiltinLoader;
+    use crate::{loader::BuiltinResolver, Context, Runtime};
+
+    #[test]
+    fn load_in_each_context() {
+        let rt = Runtime::new().unwrap();
+        rt.set_loader(
+            BuiltinResolver::default().with_module("math"),
+            BuiltinLoader::default().with_module("math", "export const two = 2;"),
+        );
+        for _ in 0..2 {
+            let ctx = Context::full(&rt).unwrap();
+            ctx.with(|ctx| {
+                let _module = ctx
+                    .clone()
+                    .compile("main", "import { two } from 'math'; globalThis.two = two;")
+                    .unwrap();
+                let two: i32 = ctx.globals().get("two").unwrap();
+                assert_eq!(two, 2);
+            });
+        }
+    }
+}
@@ -0,0 +1,220 @@
+use crate::{
+    loader::{Loader, RawLoader, Resolver},
+    module::ModuleDataKind,
+    Ctx, Module, Mut, Ref, Result,
+};
+use std::{
+    collections::HashMap,
+    ops::{Deref, DerefMut},
+};
+
+/// Module caching support
+///
+/// QuickJS only caches modules per context, so every new context resolves and compiles all of
+/// the modules it imports again. The wrapped resolver memoizes resolved names by the base and the
+/// imported name and the wrapped loader keeps the bytecode of every compiled module, so later
+/// contexts of the runtime declare modules from the bytecode instead of loading and compiling
+/// their sources again. Native modules are not cached.
+///
+/// Cached entries are kept until they are invalidated with [`Cache::invalidate`] or
+/// [`Cache::clear`].
+#[derive(Default, Clone)]
+pub struct Cache<T = ()> {
+    data: Ref<Mut<CacheData>>,
+    inner: T,
+}
+
+#[derive(Default)]
+struct CacheData {
+    // { (base, name): module_path }
+    resolved: HashMap<(String, String), String>,
+    // { module_path: module_bytecode }
+    bytecodes: HashMap<String, Vec<u8>>,
+}
+
+impl<T> Deref for Cache<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<T> DerefMut for Cache<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl Cache {
+    /// Create new caching scope
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create caching resolver by wrapping other resolver
+    pub fn resolver<R: Resolver>(&self, resolver: R) -> Cache<R> {
+        Cache {
+            data: self.data.clone(),
+            inner: resolver,
+        }
+    }
+
+    /// Create caching loader by wrapping other loader
+    pub fn loader<L: Loader>(&self, loader: L) -> Cache<L> {
+        Cache {
+            data: self.data.clone(),
+            inner: loader,
+        }
+    }
+}
+
+impl<T> Cache<T> {
+    /// Remove the module with the given resolved path from the cache
+    ///
+    /// The next context which imports the module will resolve and load it again. Contexts which
+    /// already imported the module keep using their instance.
+    pub fn invalidate<P: AsRef<str>>(&self, path: P) {
+        let path = path.as_ref();
+        let mut data = self.data.lock();
+        data.bytecodes.remove(path);
+        data.resolved.retain(|_, resolved| resolved != path);
+    }
+
+    /// Remove all modules from the cache
+    pub fn clear(&self) {
+        let mut data = self.data.lock();
+        data.bytecodes.clear();
+        data.resolved.clear();
+    }
+
+    /// Returns whether the bytecode of the module with the given resolved path is cached
+    pub fn contains<P: AsRef<str>>(&self, path: P) -> bool {
+        self.data.lock().bytecodes.contains_key(path.as_ref())
+    }
+}
+
+impl<R> Resolver for Cache<R>
+where
+    R: Resolver,
+{
+    fn resolve<'js>(&mut self, ctx: &Ctx<'js>, base: &str, name: &str) -> Result<String> {
+        let key = (base.to_string(), name.to_string());
+        if let Some(path) = self.data.lock().resolved.get(&key) {
+            return Ok(path.clone());
+        }
+        let path = self.inner.resolve(ctx, base, name)?;
+        self.data.lock().resolved.insert(key, path.clone());
+        Ok(path)
+    }
+}
+
+unsafe impl<L> RawLoader for Cache<L>
+where
+    L: Loader,
+{
+    unsafe fn raw_load<'js>(&mut self, ctx: &Ctx<'js>, path: &str) -> Result<Module<'js>> {
+        let cached = self.data.lock().bytecodes.get(path).cloned();
+        let module = if let Some(bytecode) = cached {
+            Module::unsafe_declare_read_object(ctx.clone(), &bytecode)?
+        } else {
+            let data = self.inner.load(ctx, path)?;
+            let is_native = !matches!(
+                data.kind(),
+                ModuleDataKind::Source(_) | ModuleDataKind::ByteCode(_)
+            );
+            let module = data.unsafe_declare(ctx.clone())?;
+            if !is_native {
+                let bytecode = module.write_object(false)?;
+                self.data.lock().bytecodes.insert(path.into(), bytecode);
+            }
+            module
+        };
+        self.inner.import_meta(ctx, path, module.meta()?)?;
+        Ok(module)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Cache;
+    use crate::{
+        loader::{Loader, Resolver},
+        module::ModuleData,
+        CatchResultExt, Context, Ctx, Error, Result, Runtime,
+    };
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    struct CountingResolver(Arc<AtomicUsize>);
+
+    impl Resolver for CountingResolver {
+        fn resolve<'js>(&mut self, _ctx: &Ctx<'js>, base: &str, name: &str) -> Result<String> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            if name == "dep" {
+                Ok(name.into())
+            } else {
+                Err(Error::new_resolving(base, name))
+            }
+        }
+    }
+
+    struct CountingLoader(Arc<AtomicUsize>);
+
+    impl Loader for CountingLoader {
+        fn load<'js>(&mut self, _ctx: &Ctx<'js>, name: &str) -> Result<ModuleData> {
+            let count = self.0.fetch_add(1, Ordering::SeqCst) + 1;
+            if name == "dep" {
+                Ok(ModuleData::source(
+                    name,
+                    format!("export const value = {count};"),
+                ))
+            } else {
+                Err(Error::new_loading(name))
+            }
+        }
+    }
+
+    #[test]
+    fn cache_modules() {
+        let resolves = Arc::new(AtomicUsize::new(0));
+        let loads = Arc::new(AtomicUsize::new(0));
+        let cache = Cache::new();
+        let rt = Runtime::new().unwrap();
+        rt.set_loader(
+            cache.resolver(CountingResolver(resolves.clone())),
+            cache.loader(CountingLoader(loads.clone())),
+        );
+
+        let import = || -> i32 {
+            let ctx = Context::full(&rt).unwrap();
+            ctx.with(|ctx| {
+                let _module = ctx
+                    .clone()
+                    .compile(
+                        "main",
+                        "import { value } from 'dep'; globalThis.value = value;",
+                    )
+                    .catch(&ctx)
+                    .unwrap();
+                ctx.globals().get("value").unwrap()
+            })
+        };
+
+        assert_eq!(import(), 1);
+        assert_eq!(import(), 1);
+        assert_eq!(resolves.load(Ordering::SeqCst), 1);
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+        assert!(cache.contains("dep"));
+
+        cache.invalidate("dep");
+        assert!(!cache.contains("dep"));
+        assert_eq!(import(), 2);
+        assert_eq!(resolves.load(Ordering::SeqCst), 2);
+
+        cache.clear();
+        assert_eq!(import(), 3);
+    }
+}
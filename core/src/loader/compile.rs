@@ -194,6 +194,7 @@ where
             std::any::type_name::<L>()
         );
         let module = data.unsafe_declare(ctx.clone())?;
+        self.inner.import_meta(ctx, path, module.meta()?)?;
         let data = module.write_object(false)?;
         self.data.lock().bytecodes.push((path.into(), data));
         Ok(module)
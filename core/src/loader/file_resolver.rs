@@ -56,6 +56,20 @@ impl FileResolver {
         self
     }
 
+    /// Add allowed module file extension
+    ///
+    /// This is a shorthand for adding the pattern `{}.<extension>`.
+    pub fn add_extension<X: AsRef<str>>(&mut self, extension: X) -> &mut Self {
+        self.add_pattern(format!("{{}}.{}", extension.as_ref()))
+    }
+
+    /// Add allowed module file extension
+    #[must_use]
+    pub fn with_extension<X: AsRef<str>>(mut self, extension: X) -> Self {
+        self.add_extension(extension);
+        self
+    }
+
     /// Add support for native modules
     pub fn add_native(&mut self) -> &mut Self {
         #[cfg(target_family = "windows")]
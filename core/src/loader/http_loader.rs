@@ -0,0 +1,137 @@
+use crate::{
+    loader::{http_resolver::is_url, Loader},
+    module::ModuleData,
+    Ctx, Error, Object, Result,
+};
+use std::collections::HashMap;
+
+/// The transport which fetches remote modules for a [`HttpLoader`]
+///
+/// This library doesn't include an HTTP client, so the transport is provided by the application,
+/// which can also add authentication or a persistent cache. It is implemented for closures.
+pub trait Transport {
+    /// Fetch the source of a module from the URL
+    fn fetch(&mut self, url: &str) -> Result<Vec<u8>>;
+}
+
+impl<F> Transport for F
+where
+    F: FnMut(&str) -> Result<Vec<u8>>,
+{
+    fn fetch(&mut self, url: &str) -> Result<Vec<u8>> {
+        self(url)
+    }
+}
+
+/// The HTTP(S) module loader
+///
+/// Loads modules resolved by a [`HttpResolver`](crate::loader::HttpResolver) through a
+/// [`Transport`]. The fetched sources are cached, so every URL is only fetched once for all
+/// contexts of the runtime, until it is removed with [`HttpLoader::invalidate`]. The
+/// `import.meta.url` of the loaded modules is set to their URL.
+pub struct HttpLoader<T> {
+    transport: T,
+    cache: HashMap<String, Vec<u8>>,
+}
+
+impl<T: Transport> HttpLoader<T> {
+    /// Create a loader which fetches modules with the transport
+    pub fn new(transport: T) -> Self {
+        Self {
+            transport,
+            cache: HashMap::new(),
+        }
+    }
+
+    /// Remove the cached source of the module with the URL
+    pub fn invalidate<U: AsRef<str>>(&mut self, url: U) {
+        self.cache.remove(url.as_ref());
+    }
+}
+
+impl<T: Transport> Loader for HttpLoader<T> {
+    fn load<'js>(&mut self, _ctx: &Ctx<'js>, url: &str) -> Result<ModuleData> {
+        if !is_url(url) {
+            return Err(Error::new_loading(url));
+        }
+
+        let source = match self.cache.get(url) {
+            Some(source) => source.clone(),
+            None => {
+                let source = self.transport.fetch(url)?;
+                self.cache.insert(url.into(), source.clone());
+                source
+            }
+        };
+        Ok(ModuleData::source(url, source))
+    }
+
+    fn import_meta<'js>(&mut self, _ctx: &Ctx<'js>, url: &str, meta: Object<'js>) -> Result<()> {
+        meta.set("url", url)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::HttpLoader;
+    use crate::{loader::HttpResolver, CatchResultExt, Context, Error, Result, Runtime};
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    #[test]
+    fn import_url() {
+        let fetches = Arc::new(AtomicUsize::new(0));
+        let transport = {
+            let fetches = fetches.clone();
+            move |url: &str| -> Result<Vec<u8>> {
+                fetches.fetch_add(1, Ordering::SeqCst);
+                match url {
+                    "https://example.com/lib/mod.js" => Ok(
+                        b"export { two } from './two.js'; export const url = import.meta.url;"
+                            .to_vec(),
+                    ),
+                    "https://example.com/lib/two.js" => Ok(b"export const two = 2;".to_vec()),
+                    _ => Err(Error::new_loading_message(url, "404 Not Found")),
+                }
+            }
+        };
+
+        let rt = Runtime::new().unwrap();
+        rt.set_loader(
+            HttpResolver::default().with_allowed("https://example.com/"),
+            HttpLoader::new(transport),
+        );
+        for _ in 0..2 {
+            let ctx = Context::full(&rt).unwrap();
+            ctx.with(|ctx| {
+                let _module = ctx
+                    .clone()
+                    .compile(
+                        "main",
+                        r#"
+                        import { two, url } from "https://example.com/lib/mod.js";
+                        globalThis.two = two;
+                        globalThis.url = url;
+                        "#,
+                    )
+                    .catch(&ctx)
+                    .unwrap();
+                let two: i32 = ctx.globals().get("two").unwrap();
+                assert_eq!(two, 2);
+                let url: String = ctx.globals().get("url").unwrap();
+                assert_eq!(url, "https://example.com/lib/mod.js");
+
+                let err = ctx
+                    .clone()
+                    .compile("main", "import 'https://evil.com/mod.js';")
+                    .map(|_| ())
+                    .catch(&ctx)
+                    .unwrap_err();
+                assert!(err.to_string().contains("is not allowed"), "{err}");
+            });
+        }
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,265 @@
+use crate::{loader::Resolver, Ctx, Error, Result};
+use std::fmt;
+
+/// The HTTP(S) module resolver
+///
+/// Resolves `https://` and `http://` URLs and paths which are relative to the URL of the
+/// importing module, for example `./util.js` or `/std/mod.js`. Only URLs which start with one of
+/// the allowed prefixes are resolved, so no remote module is resolved by default.
+///
+/// The resolved URLs can be loaded with a [`HttpLoader`](crate::loader::HttpLoader).
+#[derive(Debug, Default)]
+pub struct HttpResolver {
+    allowed: Vec<String>,
+}
+
+impl HttpResolver {
+    /// Allow resolving URLs which start with the given prefix
+    ///
+    /// The prefix should at least contain the origin of the allowed URLs, like
+    /// `https://example.com/`. URLs are normalized before they are checked: the scheme, host and
+    /// port must match exactly and the path of the prefix only matches whole segments, so
+    /// `https://example.com/lib` allows `https://example.com/lib/mod.js` but neither
+    /// `https://example.com/library.js` nor `https://example.com/lib/../admin.js`.
+    pub fn add_allowed<P: Into<String>>(&mut self, prefix: P) -> &mut Self {
+        self.allowed.push(prefix.into());
+        self
+    }
+
+    /// Allow resolving URLs which start with the given prefix
+    #[must_use]
+    pub fn with_allowed<P: Into<String>>(mut self, prefix: P) -> Self {
+        self.add_allowed(prefix);
+        self
+    }
+
+    fn is_allowed(&self, url: &Url) -> bool {
+        self.allowed
+            .iter()
+            .filter_map(|prefix| Url::parse(prefix))
+            .any(|prefix| url.starts_with(&prefix))
+    }
+}
+
+impl Resolver for HttpResolver {
+    fn resolve<'js>(&mut self, _ctx: &Ctx<'js>, base: &str, name: &str) -> Result<String> {
+        let url = if is_url(name) {
+            Url::parse(name)
+        } else if is_url(base) && (name.starts_with('.') || name.starts_with('/')) {
+            Url::parse(base).map(|base| base.join(name))
+        } else {
+            None
+        };
+        let url = url.ok_or_else(|| Error::new_resolving(base, name))?;
+
+        if self.is_allowed(&url) {
+            Ok(url.to_string())
+        } else {
+            Err(Error::new_resolving_message(
+                base,
+                name,
+                format!("'{url}' is not allowed"),
+            ))
+        }
+    }
+}
+
+/// Returns whether the name is an HTTP(S) URL
+pub(crate) fn is_url(name: &str) -> bool {
+    ["https://", "http://"].iter().any(|scheme| {
+        name.get(..scheme.len())
+            .map(|start| start.eq_ignore_ascii_case(scheme))
+            .unwrap_or(false)
+    })
+}
+
+/// A normalized HTTP(S) URL
+///
+/// The scheme and host are lowercased, default ports are removed and the dot segments of the
+/// path are resolved, so URLs can be compared part by part.
+#[derive(Debug, PartialEq, Eq)]
+struct Url {
+    scheme: String,
+    authority: String,
+    path: String,
+    query: String,
+}
+
+impl Url {
+    fn parse(url: &str) -> Option<Self> {
+        let (scheme, rest) = url.split_once("://")?;
+        let scheme = scheme.to_ascii_lowercase();
+        let default_port = match scheme.as_str() {
+            "https" => ":443",
+            "http" => ":80",
+            _ => return None,
+        };
+        let (authority, rest) = rest.split_at(rest.find(['/', '?', '#']).unwrap_or(rest.len()));
+        let mut authority = authority.to_ascii_lowercase();
+        if authority.is_empty() {
+            return None;
+        }
+        if authority.ends_with(default_port) {
+            authority.truncate(authority.len() - default_port.len());
+        }
+        let (path, query) = split_query(rest);
+        Some(Url {
+            scheme,
+            authority,
+            path: normalize_path(path),
+            query: query.into(),
+        })
+    }
+
+    /// Resolve a relative path against this URL
+    fn join(&self, name: &str) -> Self {
+        let (path, query) = split_query(name);
+        let path = if path.starts_with('/') {
+            normalize_path(path)
+        } else {
+            let dir = &self.path[..self.path.rfind('/').map_or(0, |idx| idx + 1)];
+            normalize_path(&format!("{dir}{path}"))
+        };
+        Url {
+            scheme: self.scheme.clone(),
+            authority: self.authority.clone(),
+            path,
+            query: query.into(),
+        }
+    }
+
+    /// Returns whether the URL has the same origin as the prefix and its path is below the path
+    /// of the prefix, only ever matching whole path segments
+    fn starts_with(&self, prefix: &Url) -> bool {
+        if self.scheme != prefix.scheme || self.authority != prefix.authority {
+            return false;
+        }
+        match self.path.strip_prefix(&prefix.path) {
+            Some(rest) => prefix.path.ends_with('/') || rest.is_empty() || rest.starts_with('/'),
+            None => false,
+        }
+    }
+}
+
+impl fmt::Display for Url {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}://{}{}{}",
+            self.scheme, self.authority, self.path, self.query
+        )
+    }
+}
+
+/// Split the path from the query, dropping the fragment
+fn split_query(url: &str) -> (&str, &str) {
+    let url = url.split('#').next().unwrap_or_default();
+    url.split_at(url.find('?').unwrap_or(url.len()))
+}
+
+/// Resolve the `.` and `..` segments of an absolute path, `..` never goes above the root
+fn normalize_path(path: &str) -> String {
+    let mut segments = Vec::new();
+    let mut dir = false;
+    for segment in path.split('/') {
+        dir = true;
+        match segment.to_ascii_lowercase().as_str() {
+            "" | "." | "%2e" => {}
+            ".." | ".%2e" | "%2e." | "%2e%2e" => {
+                segments.pop();
+            }
+            _ => {
+                segments.push(segment);
+                dir = false;
+            }
+        }
+    }
+    let mut path = format!("/{}", segments.join("/"));
+    if dir && !segments.is_empty() {
+        path.push('/');
+    }
+    path
+}
+
+#[cfg(test)]
+mod test {
+    use super::{HttpResolver, Url};
+    use crate::{loader::Resolver, test_with};
+
+    fn join(base: &str, name: &str) -> String {
+        Url::parse(base).unwrap().join(name).to_string()
+    }
+
+    #[test]
+    fn join_relative() {
+        let base = "https://example.com/lib/mod.js?v=1";
+        assert_eq!(join(base, "./util.js"), "https://example.com/lib/util.js");
+        assert_eq!(join(base, "../std/fs.js"), "https://example.com/std/fs.js");
+        assert_eq!(join(base, "/a/../b.js"), "https://example.com/b.js");
+        assert_eq!(join(base, "../../../x.js"), "https://example.com/x.js");
+        assert_eq!(
+            join("http://example.com", "./a.js"),
+            "http://example.com/a.js"
+        );
+    }
+
+    #[test]
+    fn normalize() {
+        assert_eq!(
+            Url::parse("HTTPS://Example.COM:443/a/./b/../c.js#x")
+                .unwrap()
+                .to_string(),
+            "https://example.com/a/c.js"
+        );
+        assert_eq!(
+            Url::parse("https://example.com/lib/%2e%2e/admin/x.js")
+                .unwrap()
+                .to_string(),
+            "https://example.com/admin/x.js"
+        );
+    }
+
+    #[test]
+    fn allowed() {
+        test_with(|ctx| {
+            let mut resolver = HttpResolver::default()
+                .with_allowed("https://cdn.example.com/lib/")
+                .with_allowed("https://example.com");
+            let base = "https://cdn.example.com/lib/mod.js";
+
+            assert_eq!(
+                resolver.resolve(&ctx, base, "./util.js").unwrap(),
+                "https://cdn.example.com/lib/util.js"
+            );
+            assert_eq!(
+                resolver
+                    .resolve(&ctx, "", "HTTPS://CDN.example.com/lib/a.js")
+                    .unwrap(),
+                "https://cdn.example.com/lib/a.js"
+            );
+            assert_eq!(
+                resolver
+                    .resolve(&ctx, "", "https://example.com/x.js")
+                    .unwrap(),
+                "https://example.com/x.js"
+            );
+
+            // Dot segments leaving the allowed path
+            assert!(resolver.resolve(&ctx, base, "../secret.js").is_err());
+            assert!(resolver.resolve(&ctx, base, "../../lib/../x.js").is_err());
+            assert!(resolver
+                .resolve(&ctx, "", "https://cdn.example.com/lib/../admin/x.js")
+                .is_err());
+            // Prefixes only match whole hosts and path segments
+            assert!(resolver
+                .resolve(&ctx, "", "https://example.com.evil.net/x.js")
+                .is_err());
+            assert!(resolver
+                .resolve(&ctx, "", "https://example.com@evil.net/x.js")
+                .is_err());
+            assert!(resolver
+                .resolve(&ctx, "", "https://cdn.example.com/library/x.js")
+                .is_err());
+        })
+    }
+}
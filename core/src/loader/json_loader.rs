@@ -0,0 +1,108 @@
+use crate::{
+    loader::{
+        util::{check_extensions, string_literal},
+        Loader,
+    },
+    module::ModuleData,
+    Ctx, Error, Result,
+};
+
+/// The JSON module loader
+///
+/// Loads JSON files as modules which export the parsed document as default export, so scripts
+/// can use `import data from "./data.json"`. The JSON is parsed with `JSON.parse` when the module
+/// is evaluated and is never executed as code.
+///
+/// Note that the [`FileResolver`](crate::loader::FileResolver) only resolves files with the
+/// `json` extension when it was added with
+/// [`FileResolver::with_extension`](crate::loader::FileResolver::with_extension).
+#[derive(Debug)]
+pub struct JsonLoader {
+    extensions: Vec<String>,
+}
+
+impl JsonLoader {
+    /// Add JSON file extension
+    pub fn add_extension<X: Into<String>>(&mut self, extension: X) -> &mut Self {
+        self.extensions.push(extension.into());
+        self
+    }
+
+    /// Add JSON file extension
+    #[must_use]
+    pub fn with_extension<X: Into<String>>(mut self, extension: X) -> Self {
+        self.add_extension(extension);
+        self
+    }
+}
+
+impl Default for JsonLoader {
+    fn default() -> Self {
+        Self {
+            extensions: vec!["json".into()],
+        }
+    }
+}
+
+impl Loader for JsonLoader {
+    fn load<'js>(&mut self, _ctx: &Ctx<'js>, path: &str) -> Result<ModuleData> {
+        if !check_extensions(path, &self.extensions) {
+            return Err(Error::new_loading(path));
+        }
+
+        let json = std::fs::read(path)
+            .map_err(|error| Error::new_loading_message(path, error.to_string()))?;
+        let json = String::from_utf8(json)?;
+        let source = format!("export default JSON.parse({});", string_literal(&json));
+        Ok(ModuleData::source(path, source))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::JsonLoader;
+    use crate::{loader::Resolver, CatchResultExt, Context, Ctx, Result, Runtime};
+
+    struct PathResolver;
+
+    impl Resolver for PathResolver {
+        fn resolve<'js>(&mut self, _ctx: &Ctx<'js>, _base: &str, name: &str) -> Result<String> {
+            Ok(name.into())
+        }
+    }
+
+    #[test]
+    fn import_json() {
+        let path = std::env::temp_dir().join(format!("rquickjs-json-{}.json", std::process::id()));
+        std::fs::write(
+            &path,
+            "{\n  \"name\": \"quote \\\" and \\\\ slash\",\n  \"list\": [1, 2, 3]\r\n}",
+        )
+        .unwrap();
+
+        let rt = Runtime::new().unwrap();
+        let ctx = Context::full(&rt).unwrap();
+        rt.set_loader(PathResolver, JsonLoader::default());
+        ctx.with(|ctx| {
+            let source = format!(
+                "import data from {:?}; globalThis.name = data.name; globalThis.sum = data.list.reduce((a, b) => a + b);",
+                path.to_str().unwrap()
+            );
+            let _module = ctx.clone().compile("test", source).catch(&ctx).unwrap();
+            let name: String = ctx.globals().get("name").unwrap();
+            assert_eq!(name, "quote \" and \\ slash");
+            let sum: i32 = ctx.globals().get("sum").unwrap();
+            assert_eq!(sum, 6);
+
+            let err = ctx
+                .clone()
+                .compile("test", "import data from 'missing.js';")
+                .map(|_| ())
+                .catch(&ctx)
+                .unwrap_err();
+            assert!(err.to_string().contains("Error loading module 'missing.js'"));
+        });
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
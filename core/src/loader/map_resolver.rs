@@ -0,0 +1,102 @@
+use crate::{
+    loader::{util::resolve_simple, Loader, Resolver},
+    module::ModuleData,
+    Ctx, Error, Result,
+};
+use std::collections::HashMap;
+
+/// The in-memory module resolver
+///
+/// Resolves module names against a fixed map of module names to sources.
+/// It also implements [`Loader`] so the same map can be used to load the resolved modules
+/// without touching the filesystem.
+#[derive(Debug, Default, Clone)]
+pub struct MapResolver {
+    modules: HashMap<String, String>,
+}
+
+impl MapResolver {
+    /// Create a resolver from a map of module names to sources
+    pub fn new(modules: HashMap<String, String>) -> Self {
+        Self { modules }
+    }
+
+    /// Add module source
+    pub fn add_module<N: Into<String>, S: Into<String>>(
+        &mut self,
+        name: N,
+        source: S,
+    ) -> &mut Self {
+        self.modules.insert(name.into(), source.into());
+        self
+    }
+
+    /// Add module source
+    #[must_use]
+    pub fn with_module<N: Into<String>, S: Into<String>>(mut self, name: N, source: S) -> Self {
+        self.add_module(name, source);
+        self
+    }
+}
+
+impl From<HashMap<String, String>> for MapResolver {
+    fn from(modules: HashMap<String, String>) -> Self {
+        Self::new(modules)
+    }
+}
+
+impl Resolver for MapResolver {
+    fn resolve<'js>(&mut self, _ctx: &Ctx<'js>, base: &str, name: &str) -> Result<String> {
+        let full = resolve_simple(base, name);
+
+        if self.modules.contains_key(&full) {
+            Ok(full)
+        } else {
+            Err(Error::new_resolving(base, name))
+        }
+    }
+}
+
+impl Loader for MapResolver {
+    fn load<'js>(&mut self, _ctx: &Ctx<'js>, path: &str) -> Result<ModuleData> {
+        match self.modules.get(path) {
+            Some(source) => Ok(ModuleData::source(path, source.as_bytes())),
+            _ => Err(Error::new_loading(path)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::MapResolver;
+    use crate::{loader::ScriptLoader, CatchResultExt, Context, Runtime};
+
+    #[test]
+    fn resolve_from_map() {
+        let modules = MapResolver::default()
+            .with_module("lib/math", "export const two = 2;")
+            .with_module("main", "export { two } from './lib/math';");
+
+        let rt = Runtime::new().unwrap();
+        let ctx = Context::full(&rt).unwrap();
+        rt.set_loader(modules.clone(), (modules, ScriptLoader::default()));
+        ctx.with(|ctx| {
+            let _module = ctx
+                .clone()
+                .compile("test", "import { two } from 'main'; globalThis.two = two;")
+                .unwrap();
+            let two: i32 = ctx.globals().get("two").unwrap();
+            assert_eq!(two, 2);
+
+            let err = ctx
+                .clone()
+                .compile("test", "import { two } from 'missing';")
+                .map(|_| ())
+                .catch(&ctx)
+                .unwrap_err();
+            assert!(err
+                .to_string()
+                .contains("Error resolving module 'missing' from 'test'"));
+        })
+    }
+}
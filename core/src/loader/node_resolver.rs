@@ -0,0 +1,407 @@
+use crate::{
+    loader::{util::normalize_path, Resolver},
+    Ctx, Error, Object, Result, Value,
+};
+use std::path::{Component, Path, PathBuf};
+
+/// The Node.js style module resolver
+///
+/// Resolves relative and absolute paths against the directory of the importing module and bare
+/// specifiers like `lodash-es` or `@scope/pkg/sub` by searching the `node_modules` directories of
+/// the importing module and all of its ancestors.
+///
+/// Packages are resolved through the `exports` field of their `package.json` when present,
+/// including subpath patterns and the conditions added with [`NodeResolver::add_condition`].
+/// Otherwise the `main` field is used, falling back to an `index` file. Paths without a matching
+/// file are tried with each of the known extensions. Export targets which lead outside of their
+/// package are ignored.
+///
+/// Resolved paths are normalized, so every import of a file results in the same module name.
+#[derive(Debug)]
+pub struct NodeResolver {
+    extensions: Vec<String>,
+    conditions: Vec<String>,
+}
+
+impl NodeResolver {
+    /// Add a file extension which is tried when a path doesn't exist
+    pub fn add_extension<X: Into<String>>(&mut self, extension: X) -> &mut Self {
+        self.extensions.push(extension.into());
+        self
+    }
+
+    /// Add a file extension which is tried when a path doesn't exist
+    #[must_use]
+    pub fn with_extension<X: Into<String>>(mut self, extension: X) -> Self {
+        self.add_extension(extension);
+        self
+    }
+
+    /// Add a condition matched against the conditional `exports` of packages
+    ///
+    /// The `import` and `default` conditions are always enabled.
+    pub fn add_condition<C: Into<String>>(&mut self, condition: C) -> &mut Self {
+        self.conditions.push(condition.into());
+        self
+    }
+
+    /// Add a condition matched against the conditional `exports` of packages
+    #[must_use]
+    pub fn with_condition<C: Into<String>>(mut self, condition: C) -> Self {
+        self.add_condition(condition);
+        self
+    }
+
+    fn resolve_file(&self, path: &Path) -> Option<PathBuf> {
+        if path.is_file() {
+            return Some(path.into());
+        }
+        let file_name = path.file_name()?.to_str()?;
+        self.extensions.iter().find_map(|extension| {
+            let file = path.with_file_name(format!("{file_name}.{extension}"));
+            file.is_file().then_some(file)
+        })
+    }
+
+    fn resolve_index(&self, path: &Path) -> Option<PathBuf> {
+        self.extensions.iter().find_map(|extension| {
+            let file = path.join(format!("index.{extension}"));
+            file.is_file().then_some(file)
+        })
+    }
+
+    fn resolve_directory<'js>(&self, ctx: &Ctx<'js>, path: &Path) -> Result<Option<PathBuf>> {
+        if let Some(package) = read_package(ctx, path)? {
+            if let Some(main) = package.get::<_, Option<String>>("main")? {
+                let main = path.join(main);
+                if let Some(file) = self
+                    .resolve_file(&main)
+                    .or_else(|| self.resolve_index(&main))
+                {
+                    return Ok(Some(file));
+                }
+            }
+        }
+        Ok(self.resolve_index(path))
+    }
+
+    fn resolve_path<'js>(&self, ctx: &Ctx<'js>, path: &Path) -> Result<Option<PathBuf>> {
+        if let Some(file) = self.resolve_file(path) {
+            return Ok(Some(file));
+        }
+        if path.is_dir() {
+            return self.resolve_directory(ctx, path);
+        }
+        Ok(None)
+    }
+
+    fn resolve_package<'js>(
+        &self,
+        ctx: &Ctx<'js>,
+        base: &str,
+        name: &str,
+        dir: &Path,
+    ) -> Result<Option<PathBuf>> {
+        let (package_name, subpath) = split_package_name(name);
+        for dir in dir.ancestors() {
+            let package_dir = dir.join("node_modules").join(package_name);
+            if !package_dir.is_dir() {
+                continue;
+            }
+            if let Some(package) = read_package(ctx, &package_dir)? {
+                if let Some(exports) = package.get::<_, Option<Value>>("exports")? {
+                    let subpath = format!(".{subpath}");
+                    let path = self.resolve_exports(&package_dir, &subpath, exports)?;
+                    if path.is_none() {
+                        let message =
+                            format!("subpath '{subpath}' is not exported by '{package_name}'");
+                        return Err(Error::new_resolving_message(base, name, message));
+                    }
+                    return Ok(path);
+                }
+            }
+            let path = package_dir.join(subpath.trim_start_matches('/'));
+            return self.resolve_path(ctx, &path);
+        }
+        Ok(None)
+    }
+
+    fn resolve_exports<'js>(
+        &self,
+        package_dir: &Path,
+        subpath: &str,
+        exports: Value<'js>,
+    ) -> Result<Option<PathBuf>> {
+        let map = match exports.as_object() {
+            Some(object) if !exports.is_array() => {
+                let keys = object.keys::<String>().collect::<Result<Vec<_>>>()?;
+                if keys.iter().any(|key| key.starts_with('.')) {
+                    Some((object.clone(), keys))
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        };
+        let Some((map, keys)) = map else {
+            // The exports are the target of the `.` subpath
+            return if subpath == "." {
+                self.resolve_target(package_dir, exports, None)
+            } else {
+                Ok(None)
+            };
+        };
+
+        if !subpath.contains('*') && keys.iter().any(|key| key == subpath) {
+            return self.resolve_target(package_dir, map.get(subpath)?, None);
+        }
+        // Find the pattern with the longest prefix which matches the subpath
+        let mut best: Option<(&str, &str)> = None;
+        for key in &keys {
+            let Some((prefix, suffix)) = key.split_once('*') else {
+                continue;
+            };
+            if subpath.len() >= key.len() - 1
+                && subpath.starts_with(prefix)
+                && subpath.ends_with(suffix)
+                && best
+                    .map(|(best, _)| prefix.len() > best.len())
+                    .unwrap_or(true)
+            {
+                best = Some((prefix, key));
+            }
+        }
+        match best {
+            Some((prefix, key)) => {
+                let suffix_len = key.len() - prefix.len() - 1;
+                let matched = &subpath[prefix.len()..subpath.len() - suffix_len];
+                self.resolve_target(package_dir, map.get(key)?, Some(matched))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn resolve_target<'js>(
+        &self,
+        package_dir: &Path,
+        target: Value<'js>,
+        matched: Option<&str>,
+    ) -> Result<Option<PathBuf>> {
+        if let Some(target) = target.as_string() {
+            let target = target.to_string()?;
+            if !target.starts_with("./") {
+                return Ok(None);
+            }
+            let target = match matched {
+                Some(matched) => target.replace('*', matched),
+                None => target,
+            };
+            let target = normalize_path(Path::new(&target[2..]));
+            // `..` and root components would lead outside of the package
+            if !target
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)))
+            {
+                return Ok(None);
+            }
+            return Ok(Some(package_dir.join(target)));
+        }
+        if let Some(array) = target.as_array() {
+            for target in array.iter::<Value>() {
+                if let Some(path) = self.resolve_target(package_dir, target?, matched)? {
+                    return Ok(Some(path));
+                }
+            }
+            return Ok(None);
+        }
+        if let Some(object) = target.as_object() {
+            for condition in object.keys::<String>() {
+                let condition = condition?;
+                if self.is_condition(&condition) {
+                    return self.resolve_target(package_dir, object.get(condition)?, matched);
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn is_condition(&self, condition: &str) -> bool {
+        condition == "import"
+            || condition == "default"
+            || self.conditions.iter().any(|known| known == condition)
+    }
+}
+
+impl Default for NodeResolver {
+    fn default() -> Self {
+        Self {
+            extensions: vec!["js".into(), "mjs".into()],
+            conditions: Vec::new(),
+        }
+    }
+}
+
+impl Resolver for NodeResolver {
+    fn resolve<'js>(&mut self, ctx: &Ctx<'js>, base: &str, name: &str) -> Result<String> {
+        let dir = match Path::new(base).parent() {
+            Some(dir) if dir != Path::new("") => dir,
+            _ => Path::new("."),
+        };
+        let is_path = name.starts_with("./")
+            || name.starts_with("../")
+            || name == "."
+            || name == ".."
+            || Path::new(name).is_absolute();
+
+        let path = if is_path {
+            self.resolve_path(ctx, &normalize_path(&dir.join(name)))?
+        } else {
+            self.resolve_package(ctx, base, name, dir)?
+        }
+        .ok_or_else(|| Error::new_resolving(base, name))?;
+
+        normalize_path(&path)
+            .to_str()
+            .map(|path| path.to_owned())
+            .ok_or_else(|| Error::new_resolving_message(base, name, "path is not valid UTF-8"))
+    }
+}
+
+/// Split a bare specifier into the package name and the subpath, including the leading `/`
+fn split_package_name(name: &str) -> (&str, &str) {
+    let mut separators = name.match_indices('/').map(|(index, _)| index);
+    let end = if name.starts_with('@') {
+        separators.nth(1)
+    } else {
+        separators.next()
+    };
+    match end {
+        Some(end) => name.split_at(end),
+        None => (name, ""),
+    }
+}
+
+fn read_package<'js>(ctx: &Ctx<'js>, dir: &Path) -> Result<Option<Object<'js>>> {
+    let path = dir.join("package.json");
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let json = std::fs::read(&path)?;
+    ctx.json_parse(json)?.get()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{split_package_name, NodeResolver};
+    use crate::{loader::ScriptLoader, CatchResultExt, Context, Runtime};
+    use std::fs;
+
+    #[test]
+    fn package_name() {
+        assert_eq!(split_package_name("pkg"), ("pkg", ""));
+        assert_eq!(split_package_name("pkg/a/b"), ("pkg", "/a/b"));
+        assert_eq!(split_package_name("@scope/pkg"), ("@scope/pkg", ""));
+        assert_eq!(split_package_name("@scope/pkg/a"), ("@scope/pkg", "/a"));
+    }
+
+    #[test]
+    fn resolve_node_modules() {
+        let root = std::env::temp_dir().join(format!("rquickjs-node-{}", std::process::id()));
+        let files = [
+            ("src/main.js", ""),
+            ("src/util/index.js", "export default 'util';"),
+            ("src/helper.mjs", "export default 'helper';"),
+            (
+                "src/counter.js",
+                "globalThis.count = (globalThis.count || 0) + 1; export default 1;",
+            ),
+            (
+                "node_modules/main-pkg/package.json",
+                r#"{ "main": "lib/entry" }"#,
+            ),
+            (
+                "node_modules/main-pkg/lib/entry.js",
+                "export { default as sub } from 'main-pkg/sub'; export default 'main';",
+            ),
+            (
+                "node_modules/main-pkg/sub/index.js",
+                "export default 'sub';",
+            ),
+            (
+                "node_modules/@scope/exports-pkg/package.json",
+                r#"{
+                    "main": "wrong.js",
+                    "exports": {
+                        ".": { "require": "./wrong.js", "import": "./esm/index.js" },
+                        "./features/*": "./esm/features/*.js",
+                        "./private/*": null,
+                        "./escape": "./../../../src/helper.mjs"
+                    }
+                }"#,
+            ),
+            (
+                "node_modules/@scope/exports-pkg/esm/index.js",
+                "export default 'exports';",
+            ),
+            (
+                "node_modules/@scope/exports-pkg/esm/features/a.js",
+                "export default 'feature';",
+            ),
+        ];
+        for (path, source) in files {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, source).unwrap();
+        }
+
+        let rt = Runtime::new().unwrap();
+        let ctx = Context::full(&rt).unwrap();
+        rt.set_loader(
+            NodeResolver::default(),
+            ScriptLoader::default().with_extension("mjs"),
+        );
+        ctx.with(|ctx| {
+            let main = root.join("src/main.js");
+            let main = main.to_str().unwrap();
+            let _module = ctx
+                .clone()
+                .compile(
+                    main,
+                    r#"
+                    import util from "./util";
+                    import helper from "./helper";
+                    import pkg, { sub } from "main-pkg";
+                    import exports from "@scope/exports-pkg";
+                    import feature from "@scope/exports-pkg/features/a";
+                    import "./counter.js";
+                    import "../src/./counter.js";
+                    globalThis.values = [util, helper, pkg, sub, exports, feature].join();
+                    "#,
+                )
+                .catch(&ctx)
+                .unwrap();
+            let values: String = ctx.globals().get("values").unwrap();
+            assert_eq!(values, "util,helper,main,sub,exports,feature");
+            let count: i32 = ctx.globals().get("count").unwrap();
+            assert_eq!(count, 1);
+
+            let err = ctx
+                .clone()
+                .compile(main, "import '@scope/exports-pkg/private/x';")
+                .map(|_| ())
+                .catch(&ctx)
+                .unwrap_err();
+            assert!(err.to_string().contains("is not exported"), "{err}");
+
+            let err = ctx
+                .clone()
+                .compile(main, "import '@scope/exports-pkg/escape';")
+                .map(|_| ())
+                .catch(&ctx)
+                .unwrap_err();
+            assert!(err.to_string().contains("is not exported"), "{err}");
+        });
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
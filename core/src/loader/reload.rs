@@ -0,0 +1,188 @@
+use crate::{
+    loader::{Loader, Resolver},
+    module::ModuleData,
+    Ctx, Mut, Object, Ref, Result,
+};
+use std::{
+    collections::HashMap,
+    ops::{Deref, DerefMut},
+};
+
+/// The separator between a module name and its version in a reloaded module name.
+const VERSION_SEPARATOR: &str = "?v=";
+
+/// Module reloading support
+///
+/// QuickJS caches every evaluated module by its resolved name for the lifetime of the context,
+/// which includes modules declared directly with [`Module::declare`](crate::Module::declare).
+/// Any later import of the same name reuses the cached module.
+///
+/// The wrapped resolver resolves an invalidated module to a new versioned name (`name?v=1`) so
+/// the next import loads and evaluates it again through the wrapped loader, which receives the
+/// original name. Modules which already imported the old instance keep using it, and the old
+/// instance is only freed together with the context.
+#[derive(Default, Clone)]
+pub struct Reload<T = ()> {
+    data: Ref<Mut<ReloadData>>,
+    inner: T,
+}
+
+#[derive(Default)]
+struct ReloadData {
+    versions: HashMap<String, u32>,
+}
+
+impl<T> Deref for Reload<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<T> DerefMut for Reload<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl Reload {
+    /// Create new reloading scope
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create reloading resolver by wrapping other resolver
+    pub fn resolver<R: Resolver>(&self, resolver: R) -> Reload<R> {
+        Reload {
+            data: self.data.clone(),
+            inner: resolver,
+        }
+    }
+
+    /// Create reloading loader by wrapping other loader
+    pub fn loader<L: Loader>(&self, loader: L) -> Reload<L> {
+        Reload {
+            data: self.data.clone(),
+            inner: loader,
+        }
+    }
+}
+
+impl<T> Reload<T> {
+    /// Invalidate the cached module with the given resolved name
+    ///
+    /// The next import of the module will load it again.
+    pub fn invalidate<N: AsRef<str>>(&self, name: N) {
+        *self
+            .data
+            .lock()
+            .versions
+            .entry(name.as_ref().to_string())
+            .or_default() += 1;
+    }
+
+    /// Invalidate all modules which were resolved so far
+    pub fn invalidate_all(&self) {
+        self.data
+            .lock()
+            .versions
+            .values_mut()
+            .for_each(|version| *version += 1);
+    }
+}
+
+/// Strip the version added by the reloading resolver from a module name.
+fn original_name(name: &str) -> &str {
+    match name.rsplit_once(VERSION_SEPARATOR) {
+        Some((original, version)) if version.parse::<u32>().is_ok() => original,
+        _ => name,
+    }
+}
+
+impl<R> Resolver for Reload<R>
+where
+    R: Resolver,
+{
+    fn resolve<'js>(&mut self, ctx: &Ctx<'js>, base: &str, name: &str) -> Result<String> {
+        let path = self.inner.resolve(ctx, original_name(base), name)?;
+        let version = *self.data.lock().versions.entry(path.clone()).or_default();
+        Ok(if version == 0 {
+            path
+        } else {
+            format!("{path}{VERSION_SEPARATOR}{version}")
+        })
+    }
+}
+
+impl<L> Loader for Reload<L>
+where
+    L: Loader,
+{
+    fn load<'js>(&mut self, ctx: &Ctx<'js>, name: &str) -> Result<ModuleData> {
+        let data = self.inner.load(ctx, original_name(name))?;
+        Ok(data.with_name(name))
+    }
+
+    fn import_meta<'js>(&mut self, ctx: &Ctx<'js>, name: &str, meta: Object<'js>) -> Result<()> {
+        self.inner.import_meta(ctx, original_name(name), meta)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Reload;
+    use crate::{
+        loader::{BuiltinResolver, Loader},
+        module::ModuleData,
+        CatchResultExt, Context, Ctx, Error, Result, Runtime,
+    };
+    use std::sync::{Arc, Mutex};
+
+    struct SharedLoader(Arc<Mutex<String>>);
+
+    impl Loader for SharedLoader {
+        fn load<'js>(&mut self, _ctx: &Ctx<'js>, name: &str) -> Result<ModuleData> {
+            if name == "dep" {
+                Ok(ModuleData::source(name, self.0.lock().unwrap().clone()))
+            } else {
+                Err(Error::new_loading(name))
+            }
+        }
+    }
+
+    #[test]
+    fn reload_module() {
+        let source = Arc::new(Mutex::new("export const value = 1;".to_string()));
+        let reload = Reload::new();
+        let rt = Runtime::new().unwrap();
+        let ctx = Context::full(&rt).unwrap();
+        rt.set_loader(
+            reload.resolver(BuiltinResolver::default().with_module("dep")),
+            reload.loader(SharedLoader(source.clone())),
+        );
+
+        let import = |name: &str| -> i32 {
+            ctx.with(|ctx| {
+                ctx.clone()
+                    .compile(name, "import { value } from 'dep'; export default value;")
+                    .catch(&ctx)
+                    .unwrap()
+                    .get("default")
+                    .unwrap()
+            })
+        };
+
+        assert_eq!(import("a"), 1);
+
+        *source.lock().unwrap() = "export const value = 2;".to_string();
+        assert_eq!(import("b"), 1);
+
+        reload.invalidate("dep");
+        assert_eq!(import("c"), 2);
+
+        *source.lock().unwrap() = "export const value = 3;".to_string();
+        reload.invalidate_all();
+        assert_eq!(import("d"), 3);
+    }
+}
@@ -1,12 +1,19 @@
 use crate::{
-    loader::{util::check_extensions, Loader},
+    loader::{
+        util::{check_extensions, file_url},
+        Loader,
+    },
     module::ModuleData,
-    Ctx, Error, Result,
+    Ctx, Error, Object, Result,
 };
+use std::path::Path;
 
 /// The script module loader
 ///
 /// This loader can be used as the nested backing loader in user-defined loaders.
+///
+/// The `import.meta.url` of loaded modules is set to the `file://` URL of the script, with the
+/// path made absolute, normalized and percent-encoded.
 #[derive(Debug)]
 pub struct ScriptLoader {
     extensions: Vec<String>,
@@ -41,7 +48,13 @@ impl Loader for ScriptLoader {
             return Err(Error::new_loading(path));
         }
 
-        let source: Vec<_> = std::fs::read(path)?;
+        // A missing file is a loading error, so the next loader of a chain is tried
+        let source = std::fs::read(path)
+            .map_err(|error| Error::new_loading_message(path, error.to_string()))?;
         Ok(ModuleData::source(path, source))
     }
+
+    fn import_meta<'js>(&mut self, _ctx: &Ctx<'js>, path: &str, meta: Object<'js>) -> Result<()> {
+        meta.set("url", file_url(Path::new(path))?)
+    }
 }
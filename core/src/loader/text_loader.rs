@@ -0,0 +1,113 @@
+use crate::{
+    loader::{
+        util::{check_extensions, string_literal},
+        Loader,
+    },
+    module::ModuleData,
+    Ctx, Error, Result,
+};
+
+/// The text module loader
+///
+/// Loads text files as modules which export the content of the file as a string default export,
+/// so scripts can use `import text from "./readme.txt"` like with common bundlers. Other assets
+/// can be imported as text by adding their extensions.
+///
+/// Note that the [`FileResolver`](crate::loader::FileResolver) only resolves files with the
+/// `txt` extension when it was added with
+/// [`FileResolver::with_extension`](crate::loader::FileResolver::with_extension).
+#[derive(Debug)]
+pub struct TextLoader {
+    extensions: Vec<String>,
+}
+
+impl TextLoader {
+    /// Add text file extension
+    pub fn add_extension<X: Into<String>>(&mut self, extension: X) -> &mut Self {
+        self.extensions.push(extension.into());
+        self
+    }
+
+    /// Add text file extension
+    #[must_use]
+    pub fn with_extension<X: Into<String>>(mut self, extension: X) -> Self {
+        self.add_extension(extension);
+        self
+    }
+}
+
+impl Default for TextLoader {
+    fn default() -> Self {
+        Self {
+            extensions: vec!["txt".into()],
+        }
+    }
+}
+
+impl Loader for TextLoader {
+    fn load<'js>(&mut self, _ctx: &Ctx<'js>, path: &str) -> Result<ModuleData> {
+        if !check_extensions(path, &self.extensions) {
+            return Err(Error::new_loading(path));
+        }
+
+        let text = std::fs::read(path)
+            .map_err(|error| Error::new_loading_message(path, error.to_string()))?;
+        let text = String::from_utf8(text)?;
+        let source = format!("export default {};", string_literal(&text));
+        Ok(ModuleData::source(path, source))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TextLoader;
+    use crate::{
+        loader::{JsonLoader, Resolver},
+        CatchResultExt, Context, Ctx, Result, Runtime,
+    };
+
+    struct PathResolver;
+
+    impl Resolver for PathResolver {
+        fn resolve<'js>(&mut self, _ctx: &Ctx<'js>, _base: &str, name: &str) -> Result<String> {
+            Ok(name.into())
+        }
+    }
+
+    #[test]
+    fn import_text() {
+        let dir = std::env::temp_dir().join(format!("rquickjs-text-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let text = "line \"one\"\r\nline \\two\u{2028}\0";
+        std::fs::write(dir.join("file.txt"), text).unwrap();
+        std::fs::write(dir.join("file.svg"), "<svg/>").unwrap();
+        std::fs::write(dir.join("file.json"), "[1, 2]").unwrap();
+
+        let rt = Runtime::new().unwrap();
+        let ctx = Context::full(&rt).unwrap();
+        rt.set_loader(
+            PathResolver,
+            (
+                JsonLoader::default(),
+                TextLoader::default().with_extension("svg"),
+            ),
+        );
+        ctx.with(|ctx| {
+            let source = format!(
+                "import text from {:?}; import svg from {:?}; import json from {:?}; globalThis.text = text; globalThis.svg = svg; globalThis.json = json;",
+                dir.join("file.txt").to_str().unwrap(),
+                dir.join("file.svg").to_str().unwrap(),
+                dir.join("file.json").to_str().unwrap(),
+            );
+            let _module = ctx.clone().compile("test", source).catch(&ctx).unwrap();
+            let text_value: String = ctx.globals().get("text").unwrap();
+            assert_eq!(text_value, text);
+            let svg: String = ctx.globals().get("svg").unwrap();
+            assert_eq!(svg, "<svg/>");
+            let json: Vec<i32> = ctx.globals().get("json").unwrap();
+            assert_eq!(json, [1, 2]);
+        });
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
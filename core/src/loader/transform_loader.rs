@@ -0,0 +1,115 @@
+use crate::{loader::Loader, module::ModuleData, Ctx, Object, Result};
+
+/// The source transforming loader
+///
+/// Wraps another loader and passes the source of every module it loads from source through a
+/// transform before the module is compiled, for example to strip TypeScript types with an
+/// external transpiler. Modules loaded from bytecode or native modules are not transformed.
+///
+/// ```no_run
+/// # use rquickjs::{loader::{FileResolver, ScriptLoader, TransformLoader}, Runtime, Result};
+/// # fn strip_types(source: &[u8]) -> Result<Vec<u8>> { Ok(source.to_vec()) }
+/// let rt = Runtime::new().unwrap();
+/// rt.set_loader(
+///     FileResolver::default().with_extension("ts"),
+///     TransformLoader::new(
+///         ScriptLoader::default().with_extension("ts"),
+///         |name: &str, source: Vec<u8>| {
+///             if name.ends_with(".ts") {
+///                 strip_types(&source)
+///             } else {
+///                 Ok(source)
+///             }
+///         },
+///     ),
+/// );
+/// ```
+pub struct TransformLoader<L, F> {
+    inner: L,
+    transform: F,
+}
+
+impl<L, F> TransformLoader<L, F>
+where
+    L: Loader,
+    F: FnMut(&str, Vec<u8>) -> Result<Vec<u8>>,
+{
+    /// Create a loader which transforms the sources loaded by the inner loader
+    ///
+    /// The transform is called with the module name and the source.
+    pub fn new(inner: L, transform: F) -> Self {
+        Self { inner, transform }
+    }
+}
+
+impl<L, F> Loader for TransformLoader<L, F>
+where
+    L: Loader,
+    F: FnMut(&str, Vec<u8>) -> Result<Vec<u8>>,
+{
+    fn load<'js>(&mut self, ctx: &Ctx<'js>, name: &str) -> Result<ModuleData> {
+        let transform = &mut self.transform;
+        self.inner
+            .load(ctx, name)?
+            .map_source(|source| transform(name, source))
+    }
+
+    fn import_meta<'js>(&mut self, ctx: &Ctx<'js>, name: &str, meta: Object<'js>) -> Result<()> {
+        self.inner.import_meta(ctx, name, meta)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TransformLoader;
+    use crate::{
+        loader::{BuiltinLoader, BuiltinResolver},
+        CatchResultExt, Context, Error, Runtime,
+    };
+
+    #[test]
+    fn transform_source() {
+        let rt = Runtime::new().unwrap();
+        let ctx = Context::full(&rt).unwrap();
+        rt.set_loader(
+            BuiltinResolver::default()
+                .with_module("util.ts")
+                .with_module("broken.ts"),
+            TransformLoader::new(
+                BuiltinLoader::default()
+                    .with_module(
+                        "util.ts",
+                        "export const double = (x: number): number => x * 2;",
+                    )
+                    .with_module("broken.ts", ""),
+                |name: &str, source: Vec<u8>| {
+                    if name == "broken.ts" {
+                        return Err(Error::new_loading_message(name, "unable to transpile"));
+                    }
+                    let source = String::from_utf8(source)?;
+                    Ok(source.replace(": number", "").into_bytes())
+                },
+            ),
+        );
+        ctx.with(|ctx| {
+            let _module = ctx
+                .clone()
+                .compile(
+                    "main",
+                    "import { double } from 'util.ts'; globalThis.four = double(2);",
+                )
+                .catch(&ctx)
+                .unwrap();
+            let four: i32 = ctx.globals().get("four").unwrap();
+            assert_eq!(four, 4);
+
+            let err = ctx
+                .clone()
+                .compile("main", "import 'broken.ts';")
+                .map(|_| ())
+                .catch(&ctx)
+                .unwrap_err();
+            assert!(err.to_string().contains("unable to transpile"), "{err}");
+        });
+    }
+}
@@ -1,4 +1,5 @@
 use relative_path::RelativePath;
+use std::path::{Component, Path, PathBuf};
 
 pub fn resolve_simple(base: &str, name: &str) -> String {
     if name.starts_with('.') {
@@ -20,3 +21,122 @@ pub fn check_extensions(name: &str, extensions: &[String]) -> bool {
         })
         .unwrap_or(false)
 }
+
+/// Quote a text as a JS string literal
+pub fn string_literal(text: &str) -> String {
+    let mut literal = String::with_capacity(text.len() + 2);
+    literal.push('"');
+    for c in text.chars() {
+        match c {
+            '\\' => literal.push_str("\\\\"),
+            '"' => literal.push_str("\\\""),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\0' => literal.push_str("\\u0000"),
+            '\u{2028}' => literal.push_str("\\u2028"),
+            '\u{2029}' => literal.push_str("\\u2029"),
+            c => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+/// Returns the path with `.` and `..` components removed lexically
+pub fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push(component);
+                }
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// Convert a path, relative to the current directory, into a `file://` URL
+///
+/// The path is normalized and every segment is percent-encoded.
+pub fn file_url(path: &Path) -> std::io::Result<String> {
+    let path = normalize_path(&std::env::current_dir()?.join(path));
+    let mut url = String::from("file://");
+    for component in path.components() {
+        match component {
+            // Windows drive and UNC prefixes
+            Component::Prefix(prefix) => {
+                let prefix = prefix.as_os_str().to_string_lossy();
+                let prefix = prefix.trim_start_matches('\\');
+                if prefix.contains('\\') {
+                    // `\\server\share` becomes the authority and first segment
+                    url.push_str(&prefix.replace('\\', "/"));
+                } else {
+                    url.push('/');
+                    url.push_str(prefix);
+                }
+            }
+            Component::Normal(segment) => {
+                url.push('/');
+                percent_encode(&segment.to_string_lossy(), &mut url);
+            }
+            _ => {}
+        }
+    }
+    if url.len() == "file://".len() {
+        url.push('/');
+    }
+    Ok(url)
+}
+
+fn percent_encode(segment: &str, out: &mut String) {
+    for byte in segment.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                out.push(byte as char)
+            }
+            b'!' | b'$' | b'&' | b'\'' | b'(' | b')' | b'*' | b'+' | b',' | b';' | b'=' | b':'
+            | b'@' => out.push(byte as char),
+            _ => {
+                const HEX: &[u8; 16] = b"0123456789ABCDEF";
+                out.push('%');
+                out.push(HEX[(byte >> 4) as usize] as char);
+                out.push(HEX[(byte & 0xf) as usize] as char);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{file_url, normalize_path};
+    use std::path::Path;
+
+    #[test]
+    fn normalize() {
+        assert_eq!(
+            normalize_path(Path::new("a/./b/../c.js")),
+            Path::new("a/c.js")
+        );
+        assert_eq!(normalize_path(Path::new("../a/../b")), Path::new("../b"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn file_urls() {
+        assert_eq!(
+            file_url(Path::new("/a b/./c/../%#?.js")).unwrap(),
+            "file:///a%20b/%25%23%3F.js"
+        );
+        assert_eq!(file_url(Path::new("/ä.js")).unwrap(), "file:///%C3%A4.js");
+        assert_eq!(file_url(Path::new("/")).unwrap(), "file:///");
+        let cwd = std::env::current_dir().unwrap();
+        assert_eq!(
+            file_url(Path::new("x.js")).unwrap(),
+            file_url(&cwd.join("x.js")).unwrap()
+        );
+    }
+}
@@ -2,7 +2,8 @@ use crate::{
     atom::{self, Atom},
     qjs,
     value::Constructor,
-    Array, BigInt, Ctx, Error, FromJs, Function, IntoJs, Object, Result, String, Symbol, Value,
+    Array, BigInt, Ctx, Date, Error, FromJs, Function, IntoJs, Map, Object, RegExp, Result, Set,
+    String, Symbol, Value,
 };
 use std::{
     fmt,
@@ -60,6 +61,10 @@ outlive_impls! {
     Function,
     Constructor,
     Atom,
+    Map,
+    Set,
+    Date,
+    RegExp,
 }
 
 macro_rules! impl_outlive{
@@ -151,6 +156,10 @@ impl_outlive!(
 /// assert_eq!(res, 1);
 /// ```
 ///
+/// As a `Persistent` has no `'js` lifetime it can be stored in Rust structs, e.g. in a registry
+/// of callbacks or a cache of values, and restored in any context of the same runtime. Clone it
+/// to restore it more than once.
+///
 /// It is an error (`Error::UnrelatedRuntime`) to restore the `Persistent` in a
 /// context who isn't part of the original `Runtime`.
 ///
@@ -342,4 +351,45 @@ mod test {
             assert!(eq.as_bool().unwrap());
         });
     }
+
+    #[test]
+    fn persistent_registry() {
+        use std::collections::HashMap;
+
+        struct Registry {
+            callbacks: HashMap<StdString, Persistent<Function<'static>>>,
+        }
+
+        let rt = Runtime::new().unwrap();
+        let ctx = Context::full(&rt).unwrap();
+
+        let registry = ctx.with(|ctx| {
+            let handlers: Object = ctx
+                .eval(r#"({ add: (a) => a + 1, greet: (name) => "hi " + name })"#)
+                .unwrap();
+            let callbacks = handlers
+                .props::<StdString, Persistent<Function<'static>>>()
+                .collect::<Result<_>>()
+                .unwrap();
+            Registry { callbacks }
+        });
+
+        let (sum, greeting) = ctx.with(|ctx| {
+            let add = registry.callbacks["add"].clone().restore(&ctx).unwrap();
+            let greet = registry.callbacks["greet"].clone().restore(&ctx).unwrap();
+            (
+                add.call::<_, i32>((1,)).unwrap(),
+                greet.call::<_, StdString>(("js",)).unwrap(),
+            )
+        });
+        assert_eq!(sum, 2);
+        assert_eq!(greeting, "hi js");
+
+        // The callbacks can be restored in other contexts of the runtime as well.
+        let ctx2 = Context::full(&rt).unwrap();
+        ctx2.with(|ctx| {
+            let add = registry.callbacks["add"].clone().restore(&ctx).unwrap();
+            assert_eq!(add.call::<_, i32>((2,)).unwrap(), 3);
+        });
+    }
 }
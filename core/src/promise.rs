@@ -9,10 +9,14 @@ use std::{
 
 use crate::{
     atom::PredefinedAtom, function::This, qjs, safe_ref::Ref, CatchResultExt, CaughtError,
-    CaughtResult, Ctx, Exception, FromJs, Function, IntoJs, Object, Result, ThrowResultExt, Value,
+    CaughtResult, Ctx, Error, Exception, FromJs, Function, IntoJs, Object, Result, ThrowResultExt,
+    Value,
 };
 
 /// Future-aware promise
+///
+/// Can be created from native promises as well as from any other thenable, see
+/// [`Value::is_thenable`].
 #[cfg_attr(feature = "doc-cfg", doc(cfg(feature = "futures")))]
 pub struct Promise<'js, T> {
     state: Ref<State<'js, T>>,
@@ -50,6 +54,9 @@ where
     T: FromJs<'js> + 'js,
 {
     fn from_js(ctx: &Ctx<'js>, value: Value<'js>) -> Result<Self> {
+        if !value.is_thenable()? {
+            return Err(Error::new_from_js(value.type_name(), "promise"));
+        }
         let promise = Object::from_js(ctx, value)?;
         let state = Ref::new(State {
             waker: Cell::new(None),
@@ -139,6 +146,17 @@ where
     }
 }
 
+/// Create a promise which is rejected with the error
+///
+/// Used by async functions, so errors which occur before the future was created reject the
+/// returned promise like errors of the future do.
+pub(crate) fn rejected<'js>(ctx: &Ctx<'js>, error: Error) -> Result<Value<'js>> {
+    let (promise, _, reject) = ctx.promise()?;
+    error.throw(ctx);
+    reject.call::<_, ()>((ctx.catch(),))?;
+    Ok(promise.into_value())
+}
+
 #[cfg(test)]
 mod test {
     use std::time::Duration;
@@ -206,6 +224,26 @@ mod test {
         .await
     }
 
+    #[tokio::test]
+    async fn thenable() {
+        let rt = AsyncRuntime::new().unwrap();
+        let ctx = AsyncContext::full(&rt).await.unwrap();
+
+        async_with!(ctx => |ctx| {
+            let thenable: Value = ctx
+                .eval("({ then(resolve, reject) { resolve(42) } })")
+                .catch(&ctx)
+                .unwrap();
+            assert!(thenable.is_thenable().unwrap());
+            let promise: Promise<i32> = thenable.get().unwrap();
+            assert_eq!(promise.await.catch(&ctx).unwrap(), 42);
+
+            let object: Value = ctx.eval("({ then: 42 })").catch(&ctx).unwrap();
+            assert!(object.get::<Promise<i32>>().is_err());
+        })
+        .await
+    }
+
     #[tokio::test]
     async fn promised() {
         let rt = AsyncRuntime::new().unwrap();
@@ -255,4 +293,45 @@ mod test {
         })
         .await
     }
+
+    async fn double(x: i32) -> i32 {
+        x * 2
+    }
+
+    async fn fail<'js>(ctx: Ctx<'js>) -> Result<()> {
+        Err(Exception::throw_message(&ctx, "failed"))
+    }
+
+    #[tokio::test]
+    async fn async_function_errors() {
+        let rt = AsyncRuntime::new().unwrap();
+        let ctx = AsyncContext::full(&rt).await.unwrap();
+
+        async_with!(ctx => |ctx| {
+            ctx.globals().set("double", Func::from(Async(double))).unwrap();
+            ctx.globals().set("fail", Func::from(Async(fail))).unwrap();
+
+            let promise: Promise<String> = ctx
+                .eval(
+                    r#"
+                    (async () => {
+                        const results = [await double(2)];
+                        let promise;
+                        try {
+                            promise = double("a");
+                        } catch (e) {
+                            results.push("thrown");
+                        }
+                        await promise.catch(() => results.push("rejected"));
+                        await fail().catch((e) => results.push(e.message));
+                        return results.join();
+                    })()
+                    "#,
+                )
+                .catch(&ctx)
+                .unwrap();
+            assert_eq!(promise.await.catch(&ctx).unwrap(), "4,rejected,failed");
+        })
+        .await
+    }
 }
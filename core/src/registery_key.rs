@@ -0,0 +1,82 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static NEXT_KEY: AtomicU64 = AtomicU64::new(0);
+
+/// Key for a value stored in the registry of a runtime.
+///
+/// Values stored with [`Ctx::store`](crate::Ctx::store) or
+/// [`Context::store`](crate::Context::store) are kept alive until the key is
+/// removed with [`Ctx::remove`](crate::Ctx::remove) or the context which stored them is dropped.
+/// The value can be retrieved in any later [`Context::with`](crate::Context::with) call of a
+/// context in the same runtime with [`Ctx::restore`](crate::Ctx::restore).
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct RegisteryKey(u64);
+
+impl RegisteryKey {
+    pub(crate) fn new() -> Self {
+        RegisteryKey(NEXT_KEY.fetch_add(1, Ordering::Relaxed))
+    }
+
+    pub(crate) fn id(&self) -> u64 {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn store_restore() {
+        let rt = Runtime::new().unwrap();
+        let ctx = Context::full(&rt).unwrap();
+
+        let key = ctx.with(|ctx| {
+            let func: Value = ctx.eval("(a, b) => a + b").unwrap();
+            ctx.store(func)
+        });
+        rt.run_gc();
+
+        let res: i32 = ctx.with(|ctx| {
+            let func: Function = ctx.restore(&key).unwrap().into_function().unwrap();
+            func.call((1, 2)).unwrap()
+        });
+        assert_eq!(res, 3);
+
+        ctx.remove(key);
+        let key = ctx.with(|ctx| ctx.store(Value::new_int(ctx.clone(), 1)));
+        ctx.with(|ctx| {
+            assert!(ctx.remove(key).is_some());
+        });
+    }
+
+    #[test]
+    fn released_with_context() {
+        let rt = Runtime::new().unwrap();
+        let ctx = Context::full(&rt).unwrap();
+        let other = Context::full(&rt).unwrap();
+
+        let key = ctx.with(|ctx| ctx.store(Object::new(ctx.clone()).unwrap().into_value()));
+        other.with(|ctx| assert!(ctx.restore(&key).is_some()));
+        drop(ctx);
+        other.with(|ctx| assert!(ctx.restore(&key).is_none()));
+
+        // Stored through another context, the value is held by the storing one.
+        let ctx = Context::full(&rt).unwrap();
+        let key = other.with(|other_ctx| ctx.store(&Object::new(other_ctx).unwrap().into_value()));
+        other.with(|ctx| assert!(ctx.restore(&key).is_some()));
+        drop(ctx);
+        other.with(|ctx| assert!(ctx.restore(&key).is_none()));
+    }
+
+    #[test]
+    fn restore_removed() {
+        test_with(|ctx| {
+            let key = ctx.store(Value::new_int(ctx.clone(), 1));
+            let copy = super::RegisteryKey(key.id());
+            assert!(ctx.remove(key).is_some());
+            assert!(ctx.restore(&copy).is_none());
+            assert!(ctx.remove(copy).is_none());
+        })
+    }
+}
@@ -7,6 +7,7 @@ use std::{
     panic::UnwindSafe,
     str::{FromStr, Utf8Error},
     string::FromUtf8Error,
+    sync::Arc,
 };
 
 #[cfg(feature = "futures")]
@@ -54,6 +55,40 @@ impl fmt::Display for BorrowError {
 
 impl std::error::Error for BorrowError {}
 
+/// The builtin JavaScript error class used by [`Error::Throw`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    /// `TypeError`
+    Type,
+    /// `RangeError`
+    Range,
+    /// `SyntaxError`
+    Syntax,
+    /// `ReferenceError`
+    Reference,
+    /// `InternalError`
+    Internal,
+}
+
+impl ErrorKind {
+    /// The name of the JavaScript error class.
+    pub fn name(self) -> &'static str {
+        match self {
+            ErrorKind::Type => "TypeError",
+            ErrorKind::Range => "RangeError",
+            ErrorKind::Syntax => "SyntaxError",
+            ErrorKind::Reference => "ReferenceError",
+            ErrorKind::Internal => "InternalError",
+        }
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.name().fmt(f)
+    }
+}
+
 /// Error type of the library.
 #[derive(Debug)]
 #[non_exhaustive]
@@ -83,6 +118,14 @@ pub enum Error {
     /// When returned from a callback the JavaScript will continue to unwind with the current
     /// error.
     Exception,
+    /// An error which is thrown as an instance of a builtin JavaScript error class.
+    ///
+    /// When returned from a callback the script receives for example a `RangeError` with the
+    /// given message.
+    Throw {
+        kind: ErrorKind,
+        message: StdString,
+    },
     /// Error converting from JavaScript to a Rust type.
     FromJs {
         from: &'static str,
@@ -122,6 +165,18 @@ pub enum Error {
     AsSlice(AsSliceError),
     /// Error when restoring a Persistent in a runtime other than the original runtime.
     UnrelatedRuntime,
+    /// An evaluation was aborted as it ran longer than its time limit.
+    ///
+    /// See [`Ctx::eval_with_timeout`] and
+    /// [`Runtime::set_execution_limit`](crate::Runtime::set_execution_limit).
+    Timeout,
+    /// An error of other Rust code, for example returned from a callback.
+    ///
+    /// It is thrown as a JavaScript `Error` with the message of the error. The exception keeps
+    /// the error, so when it propagates back to Rust [`CaughtError::from_error`] returns this
+    /// variant with the original error again, which can be inspected with
+    /// [`Error::downcast_custom`].
+    Custom(Arc<dyn StdError + Send + Sync + 'static>),
     /// An error from QuickJS from which the specifics are unknown.
     /// Should eventually be removed as development progresses.
     Unknown,
@@ -195,6 +250,75 @@ impl Error {
         matches!(self, Error::Exception)
     }
 
+    /// Returns whether the error is an execution timeout.
+    pub fn is_timeout(&self) -> bool {
+        matches!(self, Error::Timeout)
+    }
+
+    /// Create an error which is thrown as an instance of the given JavaScript error class
+    pub fn new_throw<M>(kind: ErrorKind, msg: M) -> Self
+    where
+        StdString: From<M>,
+    {
+        Error::Throw {
+            kind,
+            message: msg.into(),
+        }
+    }
+
+    /// Create an error which is thrown as a `TypeError`
+    pub fn new_type_error<M>(msg: M) -> Self
+    where
+        StdString: From<M>,
+    {
+        Self::new_throw(ErrorKind::Type, msg)
+    }
+
+    /// Create an error which is thrown as a `RangeError`
+    pub fn new_range_error<M>(msg: M) -> Self
+    where
+        StdString: From<M>,
+    {
+        Self::new_throw(ErrorKind::Range, msg)
+    }
+
+    /// Create an error which is thrown as a `SyntaxError`
+    pub fn new_syntax_error<M>(msg: M) -> Self
+    where
+        StdString: From<M>,
+    {
+        Self::new_throw(ErrorKind::Syntax, msg)
+    }
+
+    /// Create an error which is thrown as a `ReferenceError`
+    pub fn new_reference_error<M>(msg: M) -> Self
+    where
+        StdString: From<M>,
+    {
+        Self::new_throw(ErrorKind::Reference, msg)
+    }
+
+    /// Create an error from any other Rust error
+    pub fn new_custom<E>(error: E) -> Self
+    where
+        E: StdError + Send + Sync + 'static,
+    {
+        Error::Custom(Arc::new(error))
+    }
+
+    /// Returns a reference to the error of an [`Error::Custom`] if it is of the given type
+    pub fn downcast_custom<E: StdError + 'static>(&self) -> Option<&E> {
+        match self {
+            Error::Custom(error) => error.downcast_ref(),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the error is thrown as a builtin JavaScript error
+    pub fn is_throw(&self) -> bool {
+        matches!(self, Error::Throw { .. })
+    }
+
     /// Create from JS conversion error
     pub fn new_from_js(from: &'static str, to: &'static str) -> Self {
         Error::FromJs {
@@ -274,6 +398,19 @@ impl Error {
         match self {
             Exception => qjs::JS_EXCEPTION,
             Allocation => unsafe { qjs::JS_ThrowOutOfMemory(ctx.as_ptr()) },
+            Throw { kind, message } => {
+                // The message is passed to a format string, so it has to end at the first NUL.
+                let message = message.split('\0').next().unwrap_or_default();
+                let message = CString::new(message).expect("message should not contain NUL");
+                let throw = match kind {
+                    ErrorKind::Type => qjs::JS_ThrowTypeError,
+                    ErrorKind::Range => qjs::JS_ThrowRangeError,
+                    ErrorKind::Syntax => qjs::JS_ThrowSyntaxError,
+                    ErrorKind::Reference => qjs::JS_ThrowReferenceError,
+                    ErrorKind::Internal => qjs::JS_ThrowInternalError,
+                };
+                unsafe { throw(ctx.as_ptr(), ERROR_FORMAT_STR.as_ptr(), message.as_ptr()) }
+            }
             InvalidString(_)
             | Utf8(_)
             | FromJs { .. }
@@ -311,7 +448,8 @@ impl Error {
                     )
                 }
             }
-            Unknown => {
+            Custom(error) => crate::value::exception::throw_custom(ctx, error),
+            Unknown | Timeout => {
                 let message = self.to_cstring();
                 unsafe {
                     qjs::JS_ThrowInternalError(
@@ -370,6 +508,11 @@ impl Display for Error {
             }
             Unknown => "QuickJS library created a unknown error".fmt(f)?,
             Exception => "Exception generated by QuickJS".fmt(f)?,
+            Throw { kind, message } => {
+                kind.fmt(f)?;
+                ": ".fmt(f)?;
+                message.fmt(f)?;
+            }
             FromJs { from, to, message } => {
                 "Error converting from js '".fmt(f)?;
                 from.fmt(f)?;
@@ -459,6 +602,8 @@ impl Display for Error {
                 x.fmt(f)?;
             }
             UnrelatedRuntime => "Restoring Persistent in an unrelated runtime".fmt(f)?,
+            Timeout => "Execution time limit exceeded".fmt(f)?,
+            Custom(error) => error.fmt(f)?,
         }
         Ok(())
     }
@@ -483,6 +628,12 @@ from_impls! {
     IoError => Io,
 }
 
+impl From<Box<dyn StdError + Send + Sync + 'static>> for Error {
+    fn from(error: Box<dyn StdError + Send + Sync + 'static>) -> Self {
+        Error::Custom(error.into())
+    }
+}
+
 impl From<FromUtf8Error> for Error {
     fn from(error: FromUtf8Error) -> Self {
         Error::Utf8(error.utf8_error())
@@ -524,6 +675,8 @@ impl<'js> StdError for CaughtError<'js> {}
 impl<'js> CaughtError<'js> {
     /// Create a `CaughtError` from an [`Error`], retrieving the error value from `Ctx` if there
     /// was one.
+    ///
+    /// Exceptions which were thrown for an [`Error::Custom`] return the custom error again.
     pub fn from_error(ctx: &Ctx<'js>, error: Error) -> Self {
         if let Error::Exception = error {
             let value = ctx.catch();
@@ -531,6 +684,9 @@ impl<'js> CaughtError<'js> {
                 .as_object()
                 .and_then(|x| Exception::from_object(x.clone()))
             {
+                if let Some(error) = ex.custom_error() {
+                    return CaughtError::Error(Error::Custom(error));
+                }
                 CaughtError::Exception(ex)
             } else {
                 CaughtError::Value(value)
@@ -629,6 +785,28 @@ impl Display for JobException {
     }
 }
 
+/// A error raised from running pending jobs with
+/// [`Runtime::execute_pending_jobs`](crate::Runtime::execute_pending_jobs)
+///
+/// Contains the number of jobs executed before the error was raised.
+#[derive(Clone, Debug)]
+pub struct PendingJobsException {
+    /// The number of jobs executed successfully before the error
+    pub executed: usize,
+    /// The exception raised by the failing job
+    pub exception: JobException,
+}
+
+impl Display for PendingJobsException {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "{} after {} executed jobs",
+            self.exception, self.executed
+        )
+    }
+}
+
 /// A error raised from running a pending job
 /// Contains the context from which the error was raised.
 ///
@@ -676,13 +854,17 @@ impl<'js> Ctx<'js> {
     ///
     /// # Safety
     /// Assumes to have ownership of the [`JSValue`]
+    ///
+    /// Also continues a panic of a callback which couldn't raise an exception, like the promise
+    /// rejection tracker, when the operation returned without an exception.
     pub(crate) unsafe fn handle_exception(&self, js_val: qjs::JSValue) -> Result<qjs::JSValue> {
+        if let Some(x) = (*self.get_opaque()).panic.take() {
+            qjs::JS_FreeValue(self.as_ptr(), js_val);
+            panic::resume_unwind(x)
+        }
         if qjs::JS_VALUE_GET_NORM_TAG(js_val) != qjs::JS_TAG_EXCEPTION {
             Ok(js_val)
         } else {
-            if let Some(x) = (*self.get_opaque()).panic.take() {
-                panic::resume_unwind(x)
-            }
             Err(Error::Exception)
         }
     }
@@ -699,3 +881,150 @@ impl<'js> Ctx<'js> {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        test_with, CatchResultExt, CaughtError, Error, ErrorKind, Exception, Function, Result,
+        StdResult,
+    };
+    use std::{error::Error as StdError, fmt};
+
+    #[test]
+    fn throw_error_kinds() {
+        test_with(|ctx| {
+            let check: Function = ctx
+                .eval(
+                    r#"
+                    (f, kind) => {
+                        try {
+                            f();
+                        } catch (e) {
+                            return e instanceof globalThis[kind] && e.message === "bad value";
+                        }
+                        return false;
+                    }
+                    "#,
+                )
+                .unwrap();
+
+            for kind in [
+                ErrorKind::Type,
+                ErrorKind::Range,
+                ErrorKind::Syntax,
+                ErrorKind::Reference,
+                ErrorKind::Internal,
+            ] {
+                let f = Function::new(ctx.clone(), move || -> Result<()> {
+                    Err(Error::new_throw(kind, "bad value"))
+                })
+                .unwrap();
+                let caught: bool = check.call((f, kind.name())).unwrap();
+                assert!(caught, "expected a {kind}");
+            }
+
+            let err = Error::new_throw(ErrorKind::Range, "out of range");
+            assert!(err.is_throw());
+            assert_eq!(err.to_string(), "RangeError: out of range");
+        })
+    }
+
+    #[test]
+    fn caught_error_kinds() {
+        test_with(|ctx| {
+            let catch = |source: &str| match ctx.eval::<(), _>(source).catch(&ctx) {
+                Err(CaughtError::Exception(ex)) => ex,
+                other => panic!("expected an exception, got {other:?}"),
+            };
+
+            let ex = catch("null.prop");
+            assert_eq!(ex.kind(), Some(ErrorKind::Type));
+            assert_eq!(ex.name().unwrap(), "TypeError");
+            assert_eq!(catch("new Array(-1)").kind(), Some(ErrorKind::Range));
+            assert_eq!(catch("missing").kind(), Some(ErrorKind::Reference));
+
+            let ex = catch(
+                "class ValidationError extends Error { name = 'ValidationError' }
+                throw new ValidationError('invalid')",
+            );
+            assert_eq!(ex.kind(), None);
+            assert_eq!(ex.name().unwrap(), "ValidationError");
+            assert_eq!(ex.message().unwrap(), "invalid");
+
+            let ex = Exception::from_kind(ctx.clone(), ErrorKind::Syntax, "bad token").unwrap();
+            assert_eq!(ex.kind(), Some(ErrorKind::Syntax));
+            assert_eq!(ex.message().unwrap(), "bad token");
+
+            let err = Error::new_type_error("not a function");
+            assert_eq!(err.to_string(), "TypeError: not a function");
+        })
+    }
+
+    #[derive(Debug)]
+    struct QuotaError {
+        used: u32,
+    }
+
+    impl fmt::Display for QuotaError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "quota exceeded after {} calls", self.used)
+        }
+    }
+
+    impl StdError for QuotaError {}
+
+    #[test]
+    fn custom_errors() {
+        test_with(|ctx| {
+            let limited = Function::new(ctx.clone(), || -> Result<()> {
+                Err(Error::new_custom(QuotaError { used: 3 }))
+            })
+            .unwrap();
+            let boxed = Function::new(
+                ctx.clone(),
+                || -> StdResult<(), Box<dyn StdError + Send + Sync>> { Err("boxed".into()) },
+            )
+            .unwrap();
+            ctx.globals().set("limited", limited).unwrap();
+            ctx.globals().set("boxed", boxed).unwrap();
+
+            let message: String = ctx
+                .eval("try { limited() } catch (e) { e instanceof Error && e.message }")
+                .unwrap();
+            assert_eq!(message, "quota exceeded after 3 calls");
+
+            // The error is recovered when the exception propagates back to Rust
+            let err = ctx
+                .eval::<(), _>("try { limited() } catch (e) { throw e }")
+                .catch(&ctx)
+                .unwrap_err();
+            let CaughtError::Error(err) = err else {
+                panic!("expected the custom error, got {err:?}");
+            };
+            assert_eq!(err.downcast_custom::<QuotaError>().unwrap().used, 3);
+
+            // Scripts can neither see the error nor attach it to other exceptions
+            let hidden: bool = ctx
+                .eval(
+                    r#"try { limited() } catch (e) {
+                        Object.getOwnPropertySymbols(e).length === 0 &&
+                        Reflect.ownKeys(e).every(key => typeof key === "string")
+                    }"#,
+                )
+                .unwrap();
+            assert!(hidden);
+            let err = ctx
+                .eval::<(), _>(
+                    r#"let forged = new Error("forged");
+                    try { limited() } catch (e) { Object.assign(forged, e) }
+                    throw forged"#,
+                )
+                .catch(&ctx)
+                .unwrap_err();
+            assert!(!matches!(err, CaughtError::Error(_)));
+
+            let err = ctx.eval::<(), _>("boxed()").catch(&ctx).unwrap_err();
+            assert_eq!(err.to_string(), "boxed");
+        })
+    }
+}
@@ -3,6 +3,7 @@
 pub(crate) mod raw;
 
 mod base;
+pub use crate::result::{JobException, PendingJobsException};
 pub use base::{Runtime, WeakRuntime};
 
 /// The type of the interrupt handler.
@@ -12,6 +13,29 @@ pub type InterruptHandler = Box<dyn FnMut() -> bool + 'static>;
 #[cfg(feature = "parallel")]
 pub type InterruptHandler = Box<dyn FnMut() -> bool + Send + 'static>;
 
+/// The type of the exception formatter.
+#[cfg(not(feature = "parallel"))]
+pub type ExceptionFormatter =
+    Box<dyn for<'js> Fn(&crate::Exception<'js>) -> std::string::String + 'static>;
+/// The type of the exception formatter.
+#[cfg(feature = "parallel")]
+pub type ExceptionFormatter =
+    Box<dyn for<'js> Fn(&crate::Exception<'js>) -> std::string::String + Send + 'static>;
+
+/// The type of the host promise rejection tracker.
+///
+/// Called with the promise, the rejection reason and whether the rejection is handled.
+#[cfg(not(feature = "parallel"))]
+pub type RejectionTracker =
+    Box<dyn for<'js> Fn(crate::Ctx<'js>, crate::Value<'js>, crate::Value<'js>, bool) + 'static>;
+/// The type of the host promise rejection tracker.
+///
+/// Called with the promise, the rejection reason and whether the rejection is handled.
+#[cfg(feature = "parallel")]
+pub type RejectionTracker = Box<
+    dyn for<'js> Fn(crate::Ctx<'js>, crate::Value<'js>, crate::Value<'js>, bool) + Send + 'static,
+>;
+
 #[cfg(feature = "futures")]
 mod r#async;
 #[cfg(feature = "futures")]
@@ -22,4 +46,9 @@ pub use r#async::{AsyncRuntime, AsyncWeakRuntime};
 mod spawner;
 
 /// A struct with information about the runtimes memory usage.
+///
+/// Computed by [`Runtime::memory_usage`]. Besides the totals of the allocator, `malloc_size`,
+/// `malloc_count` and `malloc_limit`, it counts the live atoms, strings, objects, properties,
+/// shapes, functions and arrays of the runtime together with the memory they use, e.g.
+/// `obj_count` and `obj_size`.
 pub type MemoryUsage = crate::qjs::JSMemoryUsage;
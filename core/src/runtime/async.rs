@@ -3,6 +3,7 @@ use std::{
     ptr::NonNull,
     result::Result as StdResult,
     sync::{Arc, Weak},
+    time::Duration,
 };
 
 #[cfg(feature = "parallel")]
@@ -14,14 +15,14 @@ use async_lock::Mutex;
 use crate::allocator::Allocator;
 #[cfg(feature = "loader")]
 use crate::loader::{RawLoader, Resolver};
-#[cfg(feature = "parallel")]
-use crate::qjs;
-use crate::{context::AsyncContext, result::AsyncJobException, Ctx, Error, Exception, Result};
+use crate::{
+    context::AsyncContext, qjs, result::AsyncJobException, Ctx, Error, ErrorKind, Exception, Result,
+};
 
 use super::{
     raw::{Opaque, RawRuntime},
     spawner::DriveFuture,
-    InterruptHandler, MemoryUsage,
+    ExceptionFormatter, InterruptHandler, MemoryUsage, RejectionTracker,
 };
 
 #[derive(Debug)]
@@ -35,7 +36,7 @@ impl InnerRuntime {
     pub fn drop_pending(&self) {
         #[cfg(feature = "parallel")]
         while let Ok(x) = self.drop_recv.try_recv() {
-            unsafe { qjs::JS_FreeContext(x.as_ptr()) }
+            unsafe { super::raw::free_context(x.as_ptr()) }
         }
     }
 }
@@ -71,6 +72,28 @@ impl AsyncWeakRuntime {
 }
 
 /// Asynchronous QuickJS runtime, entry point of the library.
+///
+/// The runtime doesn't depend on a specific executor. Futures spawned by async functions and
+/// promises are driven while the runtime is used, by [`AsyncRuntime::idle`] which runs all jobs
+/// and futures to completion, and by the future returned from [`AsyncRuntime::drive`] which can
+/// be spawned on any executor to keep driving them in the background, e.g. with
+/// `tokio::task::spawn_local` or `async_std::task::spawn_local`. With the `parallel` feature the
+/// runtime is `Send` and the future can be spawned with `tokio::spawn` or
+/// `async_std::task::spawn` as well.
+///
+/// ```
+/// # use rquickjs::{async_with, AsyncRuntime, AsyncContext};
+/// # async fn run() {
+/// let rt = AsyncRuntime::new().unwrap();
+/// let ctx = AsyncContext::full(&rt).await.unwrap();
+/// tokio::task::spawn_local(rt.drive());
+///
+/// async_with!(ctx => |ctx| {
+///     ctx.eval::<(), _>("Promise.resolve().then(() => globalThis.done = true)").unwrap();
+/// })
+/// .await;
+/// # }
+/// ```
 #[cfg_attr(feature = "doc-cfg", doc(cfg(feature = "futures")))]
 #[derive(Clone)]
 pub struct AsyncRuntime {
@@ -174,6 +197,44 @@ impl AsyncRuntime {
         }
     }
 
+    /// Set a time limit for each evaluation of a script or a module.
+    ///
+    /// See [`Runtime::set_execution_limit`](crate::Runtime::set_execution_limit).
+    #[inline]
+    pub async fn set_execution_limit(&self, limit: Option<Duration>) {
+        unsafe {
+            self.inner.lock().await.runtime.set_execution_limit(limit);
+        }
+    }
+
+    /// Set a closure which tracks promise rejections.
+    ///
+    /// See [`Runtime::set_host_promise_rejection_tracker`](crate::Runtime::set_host_promise_rejection_tracker).
+    #[inline]
+    pub async fn set_host_promise_rejection_tracker(&self, tracker: Option<RejectionTracker>) {
+        unsafe {
+            self.inner
+                .lock()
+                .await
+                .runtime
+                .set_host_promise_rejection_tracker(tracker);
+        }
+    }
+
+    /// Set a closure which is used to format exceptions.
+    ///
+    /// See [`Runtime::set_exception_formatter`](crate::Runtime::set_exception_formatter).
+    #[inline]
+    pub async fn set_exception_formatter(&self, formatter: Option<ExceptionFormatter>) {
+        unsafe {
+            self.inner
+                .lock()
+                .await
+                .runtime
+                .set_exception_formatter(formatter);
+        }
+    }
+
     /// Set the module loader
     #[cfg(feature = "loader")]
     #[cfg_attr(feature = "doc-cfg", doc(cfg(feature = "loader")))]
@@ -200,8 +261,7 @@ impl AsyncRuntime {
     ///
     /// Setting the limit to 0 is equivalent to unlimited memory.
     ///
-    /// Note that is a Noop when a custom allocator is being used,
-    /// as is the case for the "rust-alloc" or "allocator" features.
+    /// See [`Runtime::set_memory_limit`](crate::Runtime::set_memory_limit).
     pub async fn set_memory_limit(&self, limit: usize) {
         unsafe {
             self.inner.lock().await.runtime.set_memory_limit(limit);
@@ -218,6 +278,11 @@ impl AsyncRuntime {
     }
 
     /// Set a memory threshold for garbage collection.
+    ///
+    /// The garbage collector runs automatically when the allocated memory exceeds the threshold.
+    /// After every automatic collection QuickJS resets the threshold to one and a half times the
+    /// memory still in use. Setting it to `usize::MAX` disables automatic collection entirely,
+    /// cyclic garbage is then only collected by [`run_gc`](Self::run_gc).
     pub async fn set_gc_threshold(&self, threshold: usize) {
         unsafe {
             self.inner.lock().await.runtime.set_gc_threshold(threshold);
@@ -254,6 +319,14 @@ impl AsyncRuntime {
             || !unsafe { lock.runtime.get_opaque_mut().spawner() }.is_empty()
     }
 
+    /// Returns the number of queued jobs, not counting spawned futures
+    ///
+    /// See [`Runtime::pending_job_count`](crate::Runtime::pending_job_count).
+    #[inline]
+    pub async fn pending_job_count(&self) -> usize {
+        self.inner.lock().await.runtime.pending_job_count()
+    }
+
     /// Execute first pending job
     ///
     /// Returns true when job was executed or false when queue is empty or error when exception thrown under execution.
@@ -263,12 +336,11 @@ impl AsyncRuntime {
         lock.runtime.update_stack_top();
         lock.drop_pending();
 
-        let job_res = lock.runtime.execute_pending_job().map_err(|e| {
-            let ptr =
-                NonNull::new(e).expect("executing pending job returned a null context on error");
-            AsyncJobException(unsafe { AsyncContext::from_raw(ptr, self.clone()) })
-        })?;
-        if job_res {
+        let job_res = lock
+            .runtime
+            .execute_pending_job()
+            .map_err(|ctx| AsyncJobException(unsafe { self.job_context(ctx) }))?;
+        if job_res.is_some() {
             return Ok(true);
         }
 
@@ -278,19 +350,88 @@ impl AsyncRuntime {
             .await)
     }
 
+    /// Set a limit on the number of jobs executed by one call to
+    /// [`execute_pending_jobs`](Self::execute_pending_jobs).
+    ///
+    /// The limit also applies to [`idle`](Self::idle) and to the future returned by
+    /// [`drive`](Self::drive), which stops executing jobs after `limit` jobs per poll and yields to
+    /// the executor until it is polled again.
+    ///
+    /// See [`Runtime::set_max_pending_jobs`](crate::Runtime::set_max_pending_jobs).
+    pub async fn set_max_pending_jobs(&self, limit: usize) {
+        self.inner.lock().await.runtime.max_pending_jobs = limit;
+    }
+
+    /// Execute pending jobs and drive spawned futures until both are finished
+    ///
+    /// Unlike [`idle`](Self::idle), which only reports exceptions thrown by jobs, this stops at
+    /// the first exception and returns it. Returns the number of executed jobs otherwise, not
+    /// counting the polled futures.
+    ///
+    /// Also returns an error when more jobs are pending than the limit set with
+    /// [`set_max_pending_jobs`](Self::set_max_pending_jobs).
+    pub async fn execute_pending_jobs(&self) -> StdResult<usize, AsyncJobException> {
+        let mut lock = self.inner.lock().await;
+        lock.runtime.update_stack_top();
+        lock.drop_pending();
+
+        let limit = lock.runtime.max_pending_jobs;
+        let mut count = 0;
+        loop {
+            match lock.runtime.execute_pending_job() {
+                Ok(Some(ctx)) => {
+                    count += 1;
+                    if limit != 0 && count >= limit && lock.runtime.is_job_pending() {
+                        // SAFETY: Runtime is already locked so creating a context is safe.
+                        let job_ctx = unsafe { Ctx::from_ptr(ctx.as_ptr()) };
+                        let error = Error::new_throw(
+                            ErrorKind::Internal,
+                            format!("more than {limit} pending jobs"),
+                        );
+                        error.throw(&job_ctx);
+                        return Err(AsyncJobException(unsafe { self.job_context(ctx) }));
+                    }
+                    continue;
+                }
+                Ok(None) => {}
+                Err(ctx) => return Err(AsyncJobException(unsafe { self.job_context(ctx) })),
+            }
+
+            if !unsafe { lock.runtime.get_opaque_mut() }
+                .spawner()
+                .drive()
+                .await
+            {
+                return Ok(count);
+            }
+        }
+    }
+
+    /// Wrap the context of a job returned by QuickJS, which is not owned by the caller.
+    unsafe fn job_context(&self, ctx: NonNull<qjs::JSContext>) -> AsyncContext {
+        let ctx = NonNull::new_unchecked(qjs::JS_DupContext(ctx.as_ptr()));
+        AsyncContext::from_raw(ctx, self.clone())
+    }
+
     /// Run all futures and jobs in the runtime until all are finished.
+    ///
+    /// If the job queue does not drain within the limit set with
+    /// [`set_max_pending_jobs`](Self::set_max_pending_jobs) the flood is reported like an exception
+    /// thrown by a job and `idle` returns, the remaining jobs stay queued.
     #[inline]
     pub async fn idle(&self) {
         let mut lock = self.inner.lock().await;
         lock.runtime.update_stack_top();
         lock.drop_pending();
 
+        let limit = lock.runtime.max_pending_jobs;
+        let mut count = 0;
         loop {
-            match lock.runtime.execute_pending_job().map_err(|e| {
-                let ptr = NonNull::new(e)
-                    .expect("executing pending job returned a null context on error");
-                AsyncJobException(unsafe { AsyncContext::from_raw(ptr, self.clone()) })
-            }) {
+            match lock
+                .runtime
+                .execute_pending_job()
+                .map_err(|ctx| AsyncJobException(unsafe { self.job_context(ctx) }))
+            {
                 Err(e) => {
                     // SAFETY: Runtime is already locked so creating a context is safe.
                     let ctx = unsafe { Ctx::from_ptr(e.0 .0.ctx.as_ptr()) };
@@ -302,8 +443,15 @@ impl AsyncRuntime {
                         println!("error executing job: {:?}", err);
                     }
                 }
-                Ok(true) => continue,
-                Ok(false) => {}
+                Ok(Some(_)) => {
+                    count += 1;
+                    if limit != 0 && count >= limit && lock.runtime.is_job_pending() {
+                        println!("error executing job: more than {limit} pending jobs");
+                        return;
+                    }
+                    continue;
+                }
+                Ok(None) => count = 0,
             }
 
             if unsafe { lock.runtime.get_opaque_mut() }
@@ -489,4 +637,47 @@ mod test {
         }).await;
 
     });
+
+    async_test_case!(execute_pending_jobs => (rt,ctx){
+        use std::sync::{Arc, atomic::{Ordering,AtomicUsize}};
+
+        let number = Arc::new(AtomicUsize::new(0));
+        let number_clone = number.clone();
+
+        async_with!(&ctx => |ctx|{
+            let _: () = ctx
+                .eval("for (let i = 0; i < 3; i++) { Promise.resolve(i).then(() => {}) }")
+                .unwrap();
+            ctx.spawn(async move {
+                tokio::task::yield_now().await;
+                number_clone.store(1,Ordering::SeqCst);
+            });
+        }).await;
+        assert_eq!(rt.execute_pending_jobs().await.unwrap(), 3);
+        assert_eq!(number.load(Ordering::SeqCst),1);
+        assert!(!rt.is_job_pending().await);
+
+        rt.set_max_pending_jobs(10).await;
+        async_with!(&ctx => |ctx|{
+            let _: () = ctx
+                .eval("const flood = () => Promise.resolve().then(flood); flood();")
+                .unwrap();
+        }).await;
+        let err = rt.execute_pending_jobs().await.unwrap_err();
+        async_with!(err.0 => |ctx|{
+            let err = ctx.catch().into_object().and_then(Exception::from_object);
+            assert_eq!(err.unwrap().message().unwrap(), "more than 10 pending jobs");
+        }).await;
+    });
+
+    async_test_case!(idle_max_pending_jobs => (rt,ctx){
+        rt.set_max_pending_jobs(10).await;
+        async_with!(&ctx => |ctx|{
+            let _: () = ctx
+                .eval("const flood = () => Promise.resolve().then(flood); flood();")
+                .unwrap();
+        }).await;
+        rt.idle().await;
+        assert!(rt.is_job_pending().await);
+    });
 }
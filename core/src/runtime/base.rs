@@ -2,15 +2,19 @@
 
 #[cfg(feature = "loader")]
 use crate::loader::{RawLoader, Resolver};
-use crate::{result::JobException, Context, Error, Mut, Ref, Result, Weak};
-use std::{ffi::CString, ptr::NonNull, result::Result as StdResult};
+use crate::{
+    qjs,
+    result::{JobException, PendingJobsException},
+    Context, Error, ErrorKind, Mut, Ref, Result, Weak,
+};
+use std::{ffi::CString, ptr::NonNull, result::Result as StdResult, time::Duration};
 
 #[cfg(feature = "allocator")]
 use crate::allocator::Allocator;
 
 use super::{
     raw::{Opaque, RawRuntime},
-    InterruptHandler, MemoryUsage,
+    ExceptionFormatter, InterruptHandler, MemoryUsage, RejectionTracker,
 };
 
 /// A weak handle to the runtime.
@@ -73,6 +77,12 @@ impl Runtime {
     /// Set a closure which is regularly called by the engine when it is executing code.
     /// If the provided closure returns `true` the interpreter will raise and uncatchable
     /// exception and return control flow to the caller.
+    ///
+    /// This can be used to abort runaway scripts, e.g. once a deadline passed or a cancellation
+    /// token was triggered. The raised exception is an `InternalError` with the message
+    /// `interrupted` which JS `try`/`catch` blocks can't catch, so the interrupted call returns
+    /// [`Error::Exception`] and the exception can be retrieved with
+    /// [`Ctx::catch`](crate::Ctx::catch). The context stays usable afterwards.
     #[inline]
     pub fn set_interrupt_handler(&self, handler: Option<InterruptHandler>) {
         unsafe {
@@ -80,6 +90,47 @@ impl Runtime {
         }
     }
 
+    /// Set a time limit for each evaluation of a script or a module.
+    ///
+    /// Evaluations which run longer than the limit, including the functions they call, are
+    /// aborted and return [`Error::Timeout`]. Functions called from Rust and pending jobs are not
+    /// limited. Setting the limit to `None` removes it, which is the default.
+    ///
+    /// See also [`Ctx::eval_with_timeout`](crate::Ctx::eval_with_timeout).
+    #[inline]
+    pub fn set_execution_limit(&self, limit: Option<Duration>) {
+        unsafe {
+            self.inner.lock().set_execution_limit(limit);
+        }
+    }
+
+    /// Set a closure which is used to format exceptions.
+    ///
+    /// The closure replaces the default `Display` implementation of [`Exception`](crate::Exception)
+    /// and thereby also the rendering of [`CaughtError`](crate::CaughtError).
+    /// The closure should not itself format the exception with `Display` as that would recurse.
+    #[inline]
+    pub fn set_exception_formatter(&self, formatter: Option<ExceptionFormatter>) {
+        unsafe {
+            self.inner.lock().set_exception_formatter(formatter);
+        }
+    }
+
+    /// Set a closure which tracks promise rejections.
+    ///
+    /// QuickJS silently drops the reasons of rejected promises which have no handler. The
+    /// tracker is called with the promise, the reason and `false` when a promise is rejected
+    /// without a handler, and again with `true` when a handler is added to such a promise later
+    /// on. The stack of an `Error` reason can be read through [`Exception`](crate::Exception).
+    #[inline]
+    pub fn set_host_promise_rejection_tracker(&self, tracker: Option<RejectionTracker>) {
+        unsafe {
+            self.inner
+                .lock()
+                .set_host_promise_rejection_tracker(tracker);
+        }
+    }
+
     /// Set the module loader
     #[cfg(feature = "loader")]
     #[cfg_attr(feature = "doc-cfg", doc(cfg(feature = "loader")))]
@@ -106,8 +157,8 @@ impl Runtime {
     ///
     /// Setting the limit to 0 is equivalent to unlimited memory.
     ///
-    /// Note that is a Noop when a custom allocator is being used,
-    /// as is the case for the "rust-alloc" or "allocator" features.
+    /// Allocations exceeding the limit fail, also with a custom allocator. Evaluations of scripts
+    /// and modules which run out of memory return [`Error::Allocation`], the context stays usable.
     pub fn set_memory_limit(&self, limit: usize) {
         unsafe {
             self.inner.lock().set_memory_limit(limit);
@@ -117,6 +168,11 @@ impl Runtime {
     /// Set a limit on the max size of stack the runtime will use.
     ///
     /// The default values is 256x1024 bytes.
+    ///
+    /// The limit is measured on the native stack of the thread which runs the scripts, so it must be
+    /// smaller than the stack that thread has available. When the limit is reached QuickJS throws
+    /// a catchable `InternalError` which is returned as [`Error::Exception`]. A limit larger than
+    /// the actual native stack will instead crash the process on deep recursion.
     pub fn set_max_stack_size(&self, limit: usize) {
         unsafe {
             self.inner.lock().set_max_stack_size(limit);
@@ -124,6 +180,11 @@ impl Runtime {
     }
 
     /// Set a memory threshold for garbage collection.
+    ///
+    /// The garbage collector runs automatically when the allocated memory exceeds the threshold.
+    /// After every automatic collection QuickJS resets the threshold to one and a half times the
+    /// memory still in use. Setting it to `usize::MAX` disables automatic collection entirely,
+    /// cyclic garbage is then only collected by [`run_gc`](Self::run_gc).
     pub fn set_gc_threshold(&self, threshold: usize) {
         unsafe {
             self.inner.lock().set_gc_threshold(threshold);
@@ -143,6 +204,9 @@ impl Runtime {
     }
 
     /// Get memory usage stats
+    ///
+    /// The statistics are computed by walking all allocations of the runtime, which makes this
+    /// call too expensive for hot paths but fine for periodic monitoring.
     pub fn memory_usage(&self) -> MemoryUsage {
         unsafe { self.inner.lock().memory_usage() }
     }
@@ -155,19 +219,90 @@ impl Runtime {
         self.inner.lock().is_job_pending()
     }
 
+    /// Returns the number of queued jobs
+    ///
+    /// QuickJS itself only reports whether jobs are pending, the count is taken from its job
+    /// queue by a patch of the engine. Jobs enqueued by the counted jobs while they execute are
+    /// not included, so [`execute_pending_jobs`](Self::execute_pending_jobs) may execute more.
+    #[inline]
+    pub fn pending_job_count(&self) -> usize {
+        self.inner.lock().pending_job_count()
+    }
+
     /// Execute first pending job
     ///
     /// Returns true when job was executed or false when queue is empty or error when exception thrown under execution.
     #[inline]
     pub fn execute_pending_job(&self) -> StdResult<bool, JobException> {
-        self.inner.lock().execute_pending_job().map_err(|e| {
-            JobException(unsafe {
-                Context::from_raw(
-                    NonNull::new(e).expect("QuickJS returned null ptr for job error"),
-                    self.clone(),
-                )
-            })
-        })
+        self.inner
+            .lock()
+            .execute_pending_job()
+            .map(|ctx| ctx.is_some())
+            .map_err(|ctx| JobException(unsafe { self.job_context(ctx) }))
+    }
+
+    /// Set a limit on the number of jobs executed by one call to
+    /// [`execute_pending_jobs`](Self::execute_pending_jobs).
+    ///
+    /// A script can keep enqueueing jobs from its jobs, e.g. with a promise reaction which
+    /// resolves another promise, and thereby never let the job queue drain. With a limit set,
+    /// `execute_pending_jobs` stops after executing `limit` jobs if more are still pending and
+    /// returns a [`PendingJobsException`] with an `InternalError` thrown in the context of the last
+    /// job.
+    /// The remaining jobs stay queued.
+    ///
+    /// Setting the limit to 0 is equivalent to no limit, which is the default.
+    pub fn set_max_pending_jobs(&self, limit: usize) {
+        self.inner.lock().max_pending_jobs = limit;
+    }
+
+    /// Execute pending jobs until the queue is empty
+    ///
+    /// Returns the number of executed jobs or an error when an exception was thrown by a job. The
+    /// count includes jobs which were enqueued by the executed jobs themselves. The error contains
+    /// the number of jobs executed before the exception, the jobs after it stay queued.
+    ///
+    /// Also returns an error when more jobs are pending than the limit set with
+    /// [`set_max_pending_jobs`](Self::set_max_pending_jobs).
+    pub fn execute_pending_jobs(&self) -> StdResult<usize, PendingJobsException> {
+        let limit = self.inner.lock().max_pending_jobs;
+        let mut count = 0;
+        loop {
+            // The lock must be released before the context of a job can be used.
+            let res = self.inner.lock().execute_pending_job();
+            match res {
+                Ok(None) => return Ok(count),
+                Ok(Some(ctx)) => {
+                    count += 1;
+                    if limit != 0 && count >= limit && self.is_job_pending() {
+                        let context = unsafe { self.job_context(ctx) };
+                        context.with(|ctx| {
+                            let error = Error::new_throw(
+                                ErrorKind::Internal,
+                                format!("more than {limit} pending jobs"),
+                            );
+                            error.throw(&ctx);
+                        });
+                        return Err(PendingJobsException {
+                            executed: count,
+                            exception: JobException(context),
+                        });
+                    }
+                }
+                Err(ctx) => {
+                    return Err(PendingJobsException {
+                        executed: count,
+                        exception: JobException(unsafe { self.job_context(ctx) }),
+                    })
+                }
+            }
+        }
+    }
+
+    /// Wrap the context of a job returned by QuickJS, which is not owned by the caller.
+    unsafe fn job_context(&self, ctx: NonNull<qjs::JSContext>) -> Context {
+        let ctx = NonNull::new_unchecked(qjs::JS_DupContext(ctx.as_ptr()));
+        Context::from_raw(ctx, self.clone())
     }
 }
 
@@ -197,4 +332,346 @@ mod test {
         rt.set_gc_threshold(0xFF);
         rt.run_gc();
     }
+
+    #[test]
+    fn memory_usage() {
+        use crate::Context;
+
+        let rt = Runtime::new().unwrap();
+        let ctx = Context::full(&rt).unwrap();
+        let before = rt.memory_usage();
+        ctx.with(|ctx| {
+            let _: () = ctx
+                .eval(
+                    r#"
+                    globalThis.objects = Array.from({ length: 100 }, (_, i) => ({ i }));
+                    globalThis.strings = objects.map(({ i }) => "string " + i);
+                    globalThis.func = () => {};
+                    "#,
+                )
+                .unwrap();
+        });
+        let after = rt.memory_usage();
+        assert!(after.malloc_size > before.malloc_size);
+        assert!(after.malloc_count > before.malloc_count);
+        assert!(after.obj_count >= before.obj_count + 100);
+        assert!(after.str_count >= before.str_count + 100);
+        assert!(after.js_func_count > before.js_func_count);
+        assert!(after.array_count >= before.array_count + 2);
+
+        ctx.with(|ctx| {
+            let _: () = ctx
+                .eval("delete globalThis.objects; delete globalThis.strings")
+                .unwrap();
+        });
+        rt.run_gc();
+        assert!(rt.memory_usage().obj_count < after.obj_count - 50);
+    }
+
+    #[test]
+    fn memory_limit() {
+        use crate::Context;
+
+        let rt = Runtime::new().unwrap();
+        let ctx = Context::full(&rt).unwrap();
+        rt.set_memory_limit(rt.memory_usage().malloc_size as usize + 0x100000);
+        ctx.with(|ctx| {
+            let err = ctx
+                .eval::<(), _>("globalThis.big = 'x'.repeat(1 << 24)")
+                .unwrap_err();
+            assert!(matches!(err, Error::Allocation), "{err}");
+            // Exceptions which are not caused by the limit are kept.
+            let err = ctx.eval::<(), _>("throw new Error('other')").unwrap_err();
+            assert!(err.is_exception());
+            let _ = ctx.catch();
+            let res: i32 = ctx.eval("1 + 1").unwrap();
+            assert_eq!(res, 2);
+        });
+    }
+
+    #[test]
+    fn gc_threshold() {
+        use crate::Context;
+
+        const CYCLES: i32 = 10_000;
+        let create_cycles = format!(
+            "for (let i = 0; i < {CYCLES}; i++) {{ const a = {{}}; const b = {{ a }}; a.b = b; }}"
+        );
+
+        let rt = Runtime::new().unwrap();
+        let ctx = Context::full(&rt).unwrap();
+
+        rt.set_gc_threshold(usize::MAX);
+        let before = rt.memory_usage().obj_count;
+        ctx.with(|ctx| ctx.eval::<(), _>(create_cycles.as_str()).unwrap());
+        assert!(rt.memory_usage().obj_count >= before + 2 * CYCLES as i64);
+        rt.run_gc();
+        assert!(rt.memory_usage().obj_count < before + CYCLES as i64);
+
+        rt.set_gc_threshold(64 * 1024);
+        let before = rt.memory_usage().obj_count;
+        ctx.with(|ctx| ctx.eval::<(), _>(create_cycles.as_str()).unwrap());
+        assert!(rt.memory_usage().obj_count < before + CYCLES as i64);
+    }
+
+    #[test]
+    fn stack_overflow() {
+        use crate::{CatchResultExt, Context};
+
+        let rt = Runtime::new().unwrap();
+        rt.set_max_stack_size(64 * 1024);
+        let ctx = Context::full(&rt).unwrap();
+        ctx.with(|ctx| {
+            let err = ctx
+                .eval::<(), _>("function f() { return f() + 1 } f()")
+                .catch(&ctx)
+                .unwrap_err();
+            assert!(err.to_string().contains("stack overflow"));
+
+            // The context is still usable after the overflow.
+            let res: i32 = ctx.eval("1 + 1").unwrap();
+            assert_eq!(res, 2);
+        });
+    }
+
+    #[test]
+    fn pending_job_count() {
+        use crate::Context;
+
+        let rt = Runtime::new().unwrap();
+        let ctx = Context::full(&rt).unwrap();
+        assert_eq!(rt.pending_job_count(), 0);
+        ctx.with(|ctx| {
+            let _: () = ctx
+                .eval("for (let i = 0; i < 3; i++) { Promise.resolve(i).then(() => {}) }")
+                .unwrap();
+        });
+        assert!(rt.is_job_pending());
+        assert_eq!(rt.pending_job_count(), 3);
+        assert_eq!(rt.execute_pending_jobs().unwrap(), 3);
+        assert_eq!(rt.pending_job_count(), 0);
+        assert!(!rt.is_job_pending());
+        assert_eq!(rt.execute_pending_jobs().unwrap(), 0);
+    }
+
+    #[test]
+    fn max_pending_jobs() {
+        use crate::Context;
+
+        let rt = Runtime::new().unwrap();
+        rt.set_max_pending_jobs(100);
+        let ctx = Context::full(&rt).unwrap();
+        ctx.with(|ctx| {
+            let _: () = ctx
+                .eval(
+                    r#"
+                    globalThis.runs = 0;
+                    globalThis.stop = false;
+                    const flood = () => { runs++; if (!stop) Promise.resolve().then(flood) };
+                    flood();
+                    "#,
+                )
+                .unwrap();
+        });
+
+        let err = rt.execute_pending_jobs().unwrap_err();
+        assert_eq!(err.executed, 100);
+        err.exception.0.with(|ctx| {
+            let err = ctx
+                .catch()
+                .into_object()
+                .and_then(crate::Exception::from_object);
+            assert_eq!(
+                err.unwrap().message().unwrap(),
+                "more than 100 pending jobs"
+            );
+            let runs: i32 = ctx.globals().get("runs").unwrap();
+            assert_eq!(runs, 101);
+            ctx.globals().set("stop", true).unwrap();
+        });
+        assert!(rt.is_job_pending());
+
+        // The remaining jobs can be executed after the flood stopped.
+        assert_eq!(rt.execute_pending_jobs().unwrap(), 1);
+        assert!(!rt.is_job_pending());
+    }
+
+    #[test]
+    fn interrupt_handler() {
+        use crate::{CatchResultExt, Context, Function};
+        use std::sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        };
+
+        let interrupt = Arc::new(AtomicBool::new(false));
+        let rt = Runtime::new().unwrap();
+        rt.set_interrupt_handler(Some(Box::new({
+            let interrupt = interrupt.clone();
+            move || interrupt.load(Ordering::SeqCst)
+        })));
+        let ctx = Context::full(&rt).unwrap();
+        ctx.with(|ctx| {
+            let stop = Function::new(ctx.clone(), {
+                let interrupt = interrupt.clone();
+                move || interrupt.store(true, Ordering::SeqCst)
+            })
+            .unwrap();
+            ctx.globals().set("stop", stop).unwrap();
+            let err = ctx
+                .eval::<(), _>("for (;;) { try { stop(); for (;;) {} } catch (e) {} }")
+                .catch(&ctx)
+                .unwrap_err();
+            assert!(err.to_string().contains("interrupted"), "{err}");
+        });
+
+        interrupt.store(false, Ordering::SeqCst);
+        ctx.with(|ctx| {
+            let res: i32 = ctx.eval("1 + 1").unwrap();
+            assert_eq!(res, 2);
+        });
+    }
+
+    #[test]
+    fn execution_limit() {
+        use crate::{CatchResultExt, Context};
+
+        let rt = Runtime::new().unwrap();
+        let ctx = Context::full(&rt).unwrap();
+        ctx.with(|ctx| {
+            let err = ctx
+                .eval_with_timeout::<(), _>(
+                    "for (;;) { try { for (;;) {} } catch (e) {} }",
+                    Duration::from_millis(20),
+                )
+                .unwrap_err();
+            assert!(err.is_timeout(), "{err}");
+            let res: i32 = ctx
+                .eval_with_timeout("1 + 1", Duration::from_millis(20))
+                .unwrap();
+            assert_eq!(res, 2);
+        });
+
+        rt.set_execution_limit(Some(Duration::from_millis(20)));
+        ctx.with(|ctx| {
+            let err = ctx.eval::<(), _>("for (;;) {}").catch(&ctx).unwrap_err();
+            assert!(
+                matches!(err, crate::CaughtError::Error(Error::Timeout)),
+                "{err}"
+            );
+            // Each evaluation gets the whole limit.
+            let res: i32 = ctx.eval("1 + 1").unwrap();
+            assert_eq!(res, 2);
+        });
+    }
+
+    #[test]
+    fn deadline_restored_after_panic() {
+        use crate::{Context, Function};
+        use std::panic::{self, AssertUnwindSafe};
+
+        let rt = Runtime::new().unwrap();
+        let ctx = Context::full(&rt).unwrap();
+        ctx.with(|ctx| {
+            let fail = Function::new(ctx.clone(), || -> Result<()> { panic!("failed") }).unwrap();
+            ctx.globals().set("fail", fail).unwrap();
+            let res = panic::catch_unwind(AssertUnwindSafe(|| {
+                ctx.eval_with_timeout::<(), _>("fail()", Duration::from_secs(60))
+            }));
+            assert!(res.is_err());
+            assert!(unsafe { (*ctx.get_opaque()).deadline }.is_none());
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "tracker panicked")]
+    fn rejection_tracker_panic() {
+        use crate::Context;
+
+        let rt = Runtime::new().unwrap();
+        rt.set_host_promise_rejection_tracker(Some(Box::new(|_, _, _, _| {
+            panic!("tracker panicked")
+        })));
+        let ctx = Context::full(&rt).unwrap();
+        ctx.with(|ctx| {
+            // The evaluation itself succeeds, the panic is continued once it returns.
+            let _: () = ctx.eval("Promise.reject(1); undefined").unwrap();
+        });
+    }
+
+    #[test]
+    fn rejection_tracker() {
+        use crate::{Context, Exception};
+        use std::sync::{Arc, Mutex};
+
+        let rejections = Arc::new(Mutex::new(Vec::new()));
+        let rt = Runtime::new().unwrap();
+        rt.set_host_promise_rejection_tracker(Some(Box::new({
+            let rejections = rejections.clone();
+            move |_ctx, _promise, reason, is_handled| {
+                let message = reason
+                    .into_object()
+                    .and_then(Exception::from_object)
+                    .and_then(|exception| exception.message())
+                    .unwrap_or_default();
+                rejections.lock().unwrap().push((message, is_handled));
+            }
+        })));
+        let ctx = Context::full(&rt).unwrap();
+        ctx.with(|ctx| {
+            let _: () = ctx
+                .eval(
+                    r#"
+                    Promise.reject(new Error("unhandled"));
+                    globalThis.late = Promise.reject(new Error("late"));
+                    let reject;
+                    new Promise((_, r) => reject = r).catch(() => {});
+                    reject(new Error("handled"));
+                    "#,
+                )
+                .unwrap();
+            let _: () = ctx.eval("late.catch(() => {})").unwrap();
+        });
+        rt.execute_pending_jobs().unwrap();
+
+        assert_eq!(
+            *rejections.lock().unwrap(),
+            [
+                ("unhandled".to_string(), false),
+                ("late".to_string(), false),
+                ("late".to_string(), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn exception_formatter() {
+        use crate::{CatchResultExt, Context};
+
+        let rt = Runtime::new().unwrap();
+        rt.set_exception_formatter(Some(Box::new(|e| {
+            format!(
+                r#"{{"message":{:?},"line":{}}}"#,
+                e.message().unwrap_or_default(),
+                e.line().unwrap_or(-1)
+            )
+        })));
+        let ctx = Context::full(&rt).unwrap();
+        ctx.with(|ctx| {
+            let err = ctx
+                .eval::<(), _>("\n throw new Error('oops')")
+                .catch(&ctx)
+                .unwrap_err();
+            assert_eq!(err.to_string(), r#"{"message":"oops","line":2}"#);
+        });
+
+        rt.set_exception_formatter(None);
+        ctx.with(|ctx| {
+            let err = ctx
+                .eval::<(), _>("throw new Error('oops')")
+                .catch(&ctx)
+                .unwrap_err();
+            assert!(err.to_string().starts_with("Error:"));
+        });
+    }
 }
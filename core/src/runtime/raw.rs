@@ -1,17 +1,23 @@
 use std::{
-    any::Any, ffi::CString, marker::PhantomData, mem, panic, ptr::NonNull,
+    any::Any,
+    collections::HashMap,
+    ffi::CString,
+    marker::PhantomData,
+    mem, panic,
+    ptr::NonNull,
     result::Result as StdResult,
+    time::{Duration, Instant},
 };
 
 #[cfg(feature = "allocator")]
 use crate::allocator::{Allocator, AllocatorHolder};
 #[cfg(feature = "loader")]
 use crate::loader::{LoaderHolder, RawLoader, Resolver};
-use crate::qjs;
+use crate::{qjs, Ctx, Value};
 
 #[cfg(feature = "futures")]
 use super::spawner::Spawner;
-use super::InterruptHandler;
+use super::{ExceptionFormatter, InterruptHandler, RejectionTracker};
 
 /// Opaque book keeping data for Rust.
 pub(crate) struct Opaque<'js> {
@@ -21,6 +27,28 @@ pub(crate) struct Opaque<'js> {
     /// The user provided interrupt handler, if any.
     pub interrupt_handler: Option<InterruptHandler>,
 
+    /// The user provided exception formatter, if any.
+    pub exception_formatter: Option<ExceptionFormatter>,
+
+    /// The user provided promise rejection tracker, if any.
+    pub rejection_tracker: Option<RejectionTracker>,
+
+    /// The time limit of each evaluation, set with `Runtime::set_execution_limit`.
+    pub execution_limit: Option<Duration>,
+
+    /// The deadline of the current evaluation, if it is limited.
+    pub deadline: Option<Instant>,
+
+    /// The deadline which interrupted the evaluation, until the evaluation is finished.
+    pub timed_out: Option<Instant>,
+
+    /// Values stored in the registry with the context which stored them, keyed by the id of
+    /// their `RegisteryKey`.
+    pub registery: HashMap<u64, (*mut qjs::JSContext, qjs::JSValue)>,
+
+    /// The private symbol which keys the Rust error of exceptions, created on first use.
+    pub custom_error_key: Option<qjs::JSAtom>,
+
     #[cfg(feature = "futures")]
     pub spawner: Option<Spawner<'js>>,
 
@@ -32,6 +60,13 @@ impl<'js> Opaque<'js> {
         Opaque {
             panic: None,
             interrupt_handler: None,
+            exception_formatter: None,
+            rejection_tracker: None,
+            execution_limit: None,
+            deadline: None,
+            timed_out: None,
+            registery: HashMap::new(),
+            custom_error_key: None,
             #[cfg(feature = "futures")]
             spawner: None,
             _marker: PhantomData,
@@ -43,6 +78,13 @@ impl<'js> Opaque<'js> {
         Opaque {
             panic: None,
             interrupt_handler: None,
+            exception_formatter: None,
+            rejection_tracker: None,
+            execution_limit: None,
+            deadline: None,
+            timed_out: None,
+            registery: HashMap::new(),
+            custom_error_key: None,
             #[cfg(feature = "futures")]
             spawner: Some(Spawner::new()),
             _marker: PhantomData,
@@ -71,6 +113,9 @@ pub(crate) struct RawRuntime {
     #[cfg(feature = "loader")]
     #[allow(dead_code)]
     pub loader: Option<LoaderHolder>,
+
+    /// The maximum number of jobs executed by one call to `Runtime::execute_pending_jobs`.
+    pub max_pending_jobs: usize,
 }
 
 #[cfg(feature = "parallel")]
@@ -81,6 +126,12 @@ impl Drop for RawRuntime {
         unsafe {
             let ptr = qjs::JS_GetRuntimeOpaque(self.rt.as_ptr());
             let opaque: Box<Opaque> = Box::from_raw(ptr as *mut _);
+            for (_, (_, value)) in opaque.registery.iter() {
+                qjs::JS_FreeValueRT(self.rt.as_ptr(), *value);
+            }
+            if let Some(atom) = opaque.custom_error_key {
+                qjs::JS_FreeAtomRT(self.rt.as_ptr(), atom);
+            }
             mem::drop(opaque);
             qjs::JS_FreeRuntime(self.rt.as_ptr())
         }
@@ -111,6 +162,7 @@ impl RawRuntime {
             allocator: None,
             #[cfg(feature = "loader")]
             loader: None,
+            max_pending_jobs: 0,
         })
     }
 
@@ -135,6 +187,7 @@ impl RawRuntime {
             allocator: Some(allocator),
             #[cfg(feature = "loader")]
             loader: None,
+            max_pending_jobs: 0,
         })
     }
 
@@ -153,19 +206,36 @@ impl RawRuntime {
         0 != unsafe { qjs::JS_IsJobPending(self.rt.as_ptr()) }
     }
 
-    pub fn execute_pending_job(&mut self) -> StdResult<bool, *mut qjs::JSContext> {
+    pub fn pending_job_count(&self) -> usize {
+        unsafe { qjs::JS_PendingJobCount(self.rt.as_ptr()) as usize }
+    }
+
+    /// Execute the first pending job.
+    ///
+    /// Returns the context of the job if one was executed. The context is not owned, it has to
+    /// be duplicated before it is wrapped in a [`Context`](crate::Context).
+    pub fn execute_pending_job(
+        &mut self,
+    ) -> StdResult<Option<NonNull<qjs::JSContext>>, NonNull<qjs::JSContext>> {
         let mut ctx_ptr = mem::MaybeUninit::<*mut qjs::JSContext>::uninit();
         self.update_stack_top();
         let result = unsafe { qjs::JS_ExecutePendingJob(self.rt.as_ptr(), ctx_ptr.as_mut_ptr()) };
+        // Continue a panic of a callback which can't raise an exception, like the rejection
+        // tracker, once the job which triggered it returned.
+        if let Some(panic) = unsafe { self.get_opaque_mut() }.panic.take() {
+            panic::resume_unwind(panic)
+        }
         if result == 0 {
             // no jobs executed
-            return Ok(false);
+            return Ok(None);
         }
+        let ctx = NonNull::new(unsafe { ctx_ptr.assume_init() })
+            .expect("QuickJS returned null ptr for executed job");
         if result == 1 {
             // single job executed
-            return Ok(true);
+            return Ok(Some(ctx));
         }
-        Err(unsafe { ctx_ptr.assume_init() })
+        Err(ctx)
     }
 
     #[cfg(feature = "loader")]
@@ -189,8 +259,7 @@ impl RawRuntime {
     ///
     /// Setting the limit to 0 is equivalent to unlimited memory.
     ///
-    /// Note that is a Noop when a custom allocator is being used,
-    /// as is the case for the "rust-alloc" or "allocator" features.
+    /// Custom allocators check the limit in `AllocatorHolder`.
     pub unsafe fn set_memory_limit(&mut self, limit: usize) {
         qjs::JS_SetMemoryLimit(self.rt.as_ptr(), limit as _)
     }
@@ -228,32 +297,130 @@ impl RawRuntime {
     /// If the provided closure returns `true` the interpreter will raise and uncatchable
     /// exception and return control flow to the caller.
     pub unsafe fn set_interrupt_handler(&mut self, handler: Option<InterruptHandler>) {
-        unsafe extern "C" fn interrupt_handler_trampoline(
-            _rt: *mut qjs::JSRuntime,
+        self.get_opaque_mut().interrupt_handler = handler;
+        update_interrupt_handler(self.rt.as_ptr());
+    }
+
+    /// Set the time limit of each evaluation.
+    pub unsafe fn set_execution_limit(&mut self, limit: Option<Duration>) {
+        self.get_opaque_mut().execution_limit = limit;
+        update_interrupt_handler(self.rt.as_ptr());
+    }
+
+    /// Set a closure which is called when a promise is rejected without a handler and when a
+    /// handler is added to a promise which was already rejected.
+    pub unsafe fn set_host_promise_rejection_tracker(&mut self, tracker: Option<RejectionTracker>) {
+        unsafe extern "C" fn rejection_tracker_trampoline(
+            ctx: *mut qjs::JSContext,
+            promise: qjs::JSValue,
+            reason: qjs::JSValue,
+            is_handled: ::std::os::raw::c_int,
             opaque: *mut ::std::os::raw::c_void,
-        ) -> ::std::os::raw::c_int {
-            let should_interrupt = match panic::catch_unwind(move || {
+        ) {
+            let res = panic::catch_unwind(move || {
+                let opaque = &*(opaque as *mut Opaque);
+                let ctx = Ctx::from_ptr(ctx);
+                // The values are borrowed from the caller.
+                let promise = Value::from_js_value_const(ctx.clone(), promise);
+                let reason = Value::from_js_value_const(ctx.clone(), reason);
+                opaque.rejection_tracker.as_ref().expect("tracker is set")(
+                    ctx,
+                    promise,
+                    reason,
+                    is_handled != 0,
+                );
+            });
+            if let Err(panic) = res {
+                // The tracker can't raise an exception, the panic is resumed by the Rust code
+                // running the interpreter once the current evaluation, call or job returns.
                 let opaque = &mut *(opaque as *mut Opaque);
-                opaque.interrupt_handler.as_mut().expect("handler is set")()
-            }) {
-                Ok(should_interrupt) => should_interrupt,
-                Err(panic) => {
-                    let opaque = &mut *(opaque as *mut Opaque);
-                    opaque.panic = Some(panic);
-                    // Returning true here will cause the interpreter to raise an un-catchable exception.
-                    // The Rust code that is running the interpreter will see that exception and continue
-                    // the panic handling. See crate::result::{handle_exception, handle_panic} for details.
-                    true
-                }
-            };
-            should_interrupt as _
+                opaque.panic = Some(panic);
+            }
         }
 
-        qjs::JS_SetInterruptHandler(
+        qjs::JS_SetHostPromiseRejectionTracker(
             self.rt.as_ptr(),
-            handler.as_ref().map(|_| interrupt_handler_trampoline as _),
+            tracker.as_ref().map(|_| rejection_tracker_trampoline as _),
             qjs::JS_GetRuntimeOpaque(self.rt.as_ptr()),
         );
-        self.get_opaque_mut().interrupt_handler = handler;
+        self.get_opaque_mut().rejection_tracker = tracker;
+    }
+
+    pub unsafe fn set_exception_formatter(&mut self, formatter: Option<ExceptionFormatter>) {
+        self.get_opaque_mut().exception_formatter = formatter;
+    }
+}
+
+/// Free a context, releasing the values it stored in the registry if this is its last reference.
+pub(crate) unsafe fn free_context(ctx: *mut qjs::JSContext) {
+    let header = &*(ctx as *const qjs::JSRefCountHeader);
+    if header.ref_count <= 1 {
+        let opaque = &mut *(qjs::JS_GetRuntimeOpaque(qjs::JS_GetRuntime(ctx)) as *mut Opaque);
+        let keys: Vec<u64> = opaque
+            .registery
+            .iter()
+            .filter(|(_, (owner, _))| *owner == ctx)
+            .map(|(key, _)| *key)
+            .collect();
+        // Removed first as freeing the values may run finalizers which use the registry.
+        let values: Vec<qjs::JSValue> = keys
+            .iter()
+            .filter_map(|key| opaque.registery.remove(key))
+            .map(|(_, value)| value)
+            .collect();
+        for value in values {
+            qjs::JS_FreeValue(ctx, value);
+        }
     }
+    qjs::JS_FreeContext(ctx)
+}
+
+/// Install the interrupt handler of the runtime which checks the deadline of the current
+/// evaluation and calls the user provided interrupt handler.
+///
+/// The handler is only installed while there is a user provided handler, an execution limit or a
+/// deadline, so the interpreter doesn't call into Rust otherwise.
+pub(crate) unsafe fn update_interrupt_handler(rt: *mut qjs::JSRuntime) {
+    unsafe extern "C" fn interrupt_handler_trampoline(
+        _rt: *mut qjs::JSRuntime,
+        opaque: *mut ::std::os::raw::c_void,
+    ) -> ::std::os::raw::c_int {
+        let state = &mut *(opaque as *mut Opaque);
+        if let Some(deadline) = state.deadline {
+            if Instant::now() >= deadline {
+                state.timed_out = Some(deadline);
+                return 1;
+            }
+        }
+        if state.interrupt_handler.is_none() {
+            return 0;
+        }
+
+        let should_interrupt = match panic::catch_unwind(move || {
+            let opaque = &mut *(opaque as *mut Opaque);
+            opaque.interrupt_handler.as_mut().expect("handler is set")()
+        }) {
+            Ok(should_interrupt) => should_interrupt,
+            Err(panic) => {
+                let opaque = &mut *(opaque as *mut Opaque);
+                opaque.panic = Some(panic);
+                // Returning true here will cause the interpreter to raise an un-catchable exception.
+                // The Rust code that is running the interpreter will see that exception and continue
+                // the panic handling. See crate::result::{handle_exception, handle_panic} for details.
+                true
+            }
+        };
+        should_interrupt as _
+    }
+
+    let opaque = qjs::JS_GetRuntimeOpaque(rt);
+    let state = &*(opaque as *mut Opaque);
+    let needed = state.interrupt_handler.is_some()
+        || state.execution_limit.is_some()
+        || state.deadline.is_some();
+    qjs::JS_SetInterruptHandler(
+        rt,
+        needed.then_some(interrupt_handler_trampoline as _),
+        opaque,
+    );
 }
@@ -151,9 +151,20 @@ impl Future for DriveFuture {
                 .spawner()
                 .listen(cx.waker().clone());
 
+            // Jobs executed during this poll, limited so a flood of jobs can't starve the
+            // executor.
+            let limit = lock.runtime.max_pending_jobs;
+            let mut count = 0;
             loop {
+                if limit != 0 && count >= limit && lock.runtime.is_job_pending() {
+                    cx.waker().wake_by_ref();
+                    self.state = DriveFutureState::Initial;
+                    return Poll::Pending;
+                }
+
                 // TODO: Handle error.
-                if let Ok(true) = lock.runtime.execute_pending_job() {
+                if let Ok(Some(_)) = lock.runtime.execute_pending_job() {
+                    count += 1;
                     continue;
                 }
 
@@ -164,7 +175,15 @@ impl Future for DriveFuture {
                     Poll::Pending => {
                         // Execute pending jobs to ensure we don't dead lock when waiting on
                         // QuickJS futures.
-                        while let Ok(true) = lock.runtime.execute_pending_job() {}
+                        while limit == 0 || count < limit {
+                            match lock.runtime.execute_pending_job() {
+                                Ok(Some(_)) => count += 1,
+                                _ => break,
+                            }
+                        }
+                        if limit != 0 && count >= limit && lock.runtime.is_job_pending() {
+                            cx.waker().wake_by_ref();
+                        }
                         self.state = DriveFutureState::Initial;
                         return Poll::Pending;
                     }
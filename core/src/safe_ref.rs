@@ -2,7 +2,7 @@
 use std::cell::RefCell as Cell;
 
 #[cfg(feature = "parallel")]
-use std::sync::Mutex as Cell;
+use std::sync::{Mutex as Cell, PoisonError};
 
 #[cfg(not(feature = "parallel"))]
 pub use std::{
@@ -29,6 +29,11 @@ impl<T: Default> Default for Mut<T> {
 }
 
 impl<T: ?Sized> Mut<T> {
+    /// Lock the value.
+    ///
+    /// A lock poisoned by a panic while it was held is recovered. The runtime state protected by
+    /// these locks has no invariants which a panicking callback can break halfway, so it stays
+    /// usable after a panic.
     pub fn lock(&self) -> Lock<T> {
         #[cfg(not(feature = "parallel"))]
         {
@@ -37,7 +42,7 @@ impl<T: ?Sized> Mut<T> {
 
         #[cfg(feature = "parallel")]
         {
-            self.0.lock().unwrap()
+            self.0.lock().unwrap_or_else(PoisonError::into_inner)
         }
     }
 
@@ -49,7 +54,29 @@ impl<T: ?Sized> Mut<T> {
 
         #[cfg(feature = "parallel")]
         {
-            self.0.lock().ok()
+            Some(self.0.lock().unwrap_or_else(PoisonError::into_inner))
         }
     }
 }
+
+#[cfg(all(test, feature = "parallel"))]
+mod test {
+    use super::{Mut, Ref};
+
+    #[test]
+    fn recover_poisoned() {
+        let value = Ref::new(Mut::new(1));
+        let res = std::thread::spawn({
+            let value = value.clone();
+            move || {
+                let _guard = value.lock();
+                panic!("poison the lock");
+            }
+        })
+        .join();
+        assert!(res.is_err());
+
+        *value.lock() += 1;
+        assert_eq!(*value.try_lock().unwrap(), 2);
+    }
+}
@@ -1,14 +1,19 @@
-use crate::{qjs, Ctx, Error, Result};
+use crate::{context::ValueCloner, qjs, Ctx, Error, Result};
 use std::{fmt, hash::Hash, mem, ops::Deref, result::Result as StdResult, str};
 
 pub mod array;
 pub mod atom;
 mod bigint;
 pub mod convert;
+pub mod date;
 pub(crate) mod exception;
 pub mod function;
+pub mod map;
 pub mod module;
 pub mod object;
+mod promise;
+pub mod regexp;
+pub mod set;
 mod string;
 mod symbol;
 
@@ -16,11 +21,16 @@ pub use array::Array;
 pub use atom::Atom;
 pub use bigint::BigInt;
 pub use convert::{Coerced, FromAtom, FromIteratorJs, FromJs, IntoAtom, IntoJs, IteratorJs};
+pub use date::Date;
 pub use exception::Exception;
 pub use function::{Constructor, Function};
+pub use map::Map;
 pub use module::Module;
 pub use object::{Filter, Object};
-pub use string::String;
+pub use promise::{PromiseObject, PromiseState};
+pub use regexp::RegExp;
+pub use set::Set;
+pub use string::{StrRef, String};
 pub use symbol::Symbol;
 
 #[cfg(feature = "array-buffer")]
@@ -29,7 +39,7 @@ pub mod array_buffer;
 pub mod typed_array;
 
 #[cfg(feature = "array-buffer")]
-pub use array_buffer::ArrayBuffer;
+pub use array_buffer::{ArrayBuffer, SharedMemory};
 #[cfg(feature = "array-buffer")]
 pub use typed_array::TypedArray;
 
@@ -39,6 +49,10 @@ pub struct Value<'js> {
     pub(crate) value: qjs::JSValue,
 }
 
+/// Compares the representation of the values.
+///
+/// Objects are equal when they are the same object, but strings are only equal when they share the
+/// same allocation. Use [`Value::strict_eq`] or [`Value::same_value`] to compare like JavaScript.
 impl<'js> PartialEq for Value<'js> {
     fn eq(&self, other: &Self) -> bool {
         let tag = unsafe { qjs::JS_VALUE_GET_TAG(self.value) };
@@ -53,6 +67,33 @@ impl<'js> PartialEq for Value<'js> {
 
 impl<'js> Eq for Value<'js> {}
 
+/// Compares against a number without coercion, integers and floats are compared by value.
+impl<'js> PartialEq<i32> for Value<'js> {
+    fn eq(&self, other: &i32) -> bool {
+        self.as_number() == Some(*other as f64)
+    }
+}
+
+impl<'js> PartialEq<f64> for Value<'js> {
+    fn eq(&self, other: &f64) -> bool {
+        self.as_number() == Some(*other)
+    }
+}
+
+/// Compares against a boolean without coercion.
+impl<'js> PartialEq<bool> for Value<'js> {
+    fn eq(&self, other: &bool) -> bool {
+        self.as_bool() == Some(*other)
+    }
+}
+
+/// Compares against a string without coercion.
+impl<'js> PartialEq<&str> for Value<'js> {
+    fn eq(&self, other: &&str) -> bool {
+        matches!(self.as_string().map(|s| s.to_string()), Some(Ok(s)) if s == *other)
+    }
+}
+
 impl<'js> Hash for Value<'js> {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         let tag = unsafe { qjs::JS_VALUE_GET_TAG(self.value) };
@@ -177,6 +218,23 @@ impl<'js> Value<'js> {
         &self.ctx
     }
 
+    /// Deep copy the value into a context of any runtime, similar to `structuredClone`.
+    ///
+    /// Plain objects and arrays are copied recursively, preserving shared references and cycles.
+    /// Strings, typed arrays, array buffers and the other values supported by the serialization
+    /// format of QuickJS are copied through it. Other values, like functions, errors and class
+    /// instances, can't be copied and result in an error.
+    ///
+    /// The own enumerable properties of plain objects are copied, including symbol keyed ones,
+    /// and objects without a prototype stay without one. Symbols are owned by their runtime, so
+    /// symbol keys can only be copied into a context of the same runtime. Values nested more than
+    /// 256 levels deep result in an error.
+    ///
+    /// Unlike [`Ctx::clone_value_into`] the target context may belong to another runtime.
+    pub fn clone_into<'to>(&self, ctx: &Ctx<'to>) -> Result<Value<'to>> {
+        ValueCloner::new(self.ctx(), ctx, false)?.clone(self)
+    }
+
     // unsafe because no type checking
     #[inline]
     pub(crate) unsafe fn get_bool(&self) -> bool {
@@ -371,6 +429,86 @@ impl<'js> Value<'js> {
         0 != unsafe { qjs::JS_IsError(self.ctx.as_ptr(), self.value) }
     }
 
+    /// Check if the value is a `Promise` object
+    #[inline]
+    pub fn is_promise(&self) -> bool {
+        unsafe { qjs::JS_PromiseState(self.ctx.as_ptr(), self.value) >= 0 }
+    }
+
+    /// Check if the value is a `Date` object
+    #[inline]
+    pub fn is_date(&self) -> bool {
+        0 != unsafe { qjs::JS_IsDate(self.value) }
+    }
+
+    /// Check if the value is a `RegExp` object
+    #[inline]
+    pub fn is_regexp(&self) -> bool {
+        0 != unsafe { qjs::JS_IsRegExp(self.value) }
+    }
+
+    /// Check if the value is a thenable, i.e. an object with a callable `then` property
+    ///
+    /// Native promises as well as promise-like objects from other libraries are thenables and can
+    /// be converted into a [`Promise`](crate::promise::Promise) to await them.
+    pub fn is_thenable(&self) -> Result<bool> {
+        match self.as_object() {
+            Some(object) => object
+                .get::<_, Value>(atom::PredefinedAtom::Then)
+                .map(|then| then.is_function()),
+            None => Ok(false),
+        }
+    }
+
+    /// Check if the value is an instance of the given constructor, like the `instanceof` operator
+    ///
+    /// Unlike [`Object::is_instance_of`] any value can be checked, primitives are never instances.
+    /// Errors thrown during the check, for example when `class` is not callable, are returned.
+    pub fn instance_of(&self, class: &Object<'js>) -> Result<bool> {
+        let res =
+            unsafe { qjs::JS_IsInstanceOf(self.ctx.as_ptr(), self.value, class.as_js_value()) };
+        if res < 0 {
+            return Err(self.ctx.raise_exception());
+        }
+        Ok(res == 1)
+    }
+
+    /// Compare with another value like the `===` operator
+    ///
+    /// Unlike the [`PartialEq`] implementation, which compares the representation of the values,
+    /// strings and big integers are compared by their contents, `NaN` is not equal to itself and
+    /// `0` is equal to `-0`.
+    pub fn strict_eq(&self, other: &Value<'js>) -> bool {
+        0 != unsafe { qjs::JS_StrictEq(self.ctx.as_ptr(), self.value, other.value) }
+    }
+
+    /// Compare with another value like the `==` operator
+    ///
+    /// Objects are converted to primitives for the comparison, which can call user defined
+    /// methods, so errors thrown during the conversion are returned.
+    pub fn loose_eq(&self, other: &Value<'js>) -> Result<bool> {
+        let res = unsafe { qjs::JS_LooseEq(self.ctx.as_ptr(), self.value, other.value) };
+        if res < 0 {
+            return Err(self.ctx.raise_exception());
+        }
+        Ok(res == 1)
+    }
+
+    /// Compare with another value like `Object.is`
+    ///
+    /// The same as [`Value::strict_eq`] except that `NaN` is equal to itself and `0` is not equal
+    /// to `-0`.
+    pub fn same_value(&self, other: &Value<'js>) -> bool {
+        0 != unsafe { qjs::JS_SameValue(self.ctx.as_ptr(), self.value, other.value) }
+    }
+
+    /// Compare with another value like the keys of a `Map` or `Array.prototype.includes`
+    ///
+    /// The same as [`Value::same_value`] except that `0` is equal to `-0`.
+    pub fn same_value_zero(&self, other: &Value<'js>) -> bool {
+        0 != unsafe { qjs::JS_SameValueZero(self.ctx.as_ptr(), self.value, other.value) }
+    }
+
     /// Reference as value
     #[inline]
     pub fn as_value(&self) -> &Self {
@@ -383,6 +521,9 @@ impl<'js> Value<'js> {
     }
 
     /// Convert from value to specified type
+    ///
+    /// The conversion uses the context the value belongs to, so values can be converted at any
+    /// point during their lifetime without passing the [`Ctx`] along.
     pub fn get<T: FromJs<'js>>(&self) -> Result<T> {
         T::from_js(self.ctx(), self.clone())
     }
@@ -517,8 +658,10 @@ type_impls! {
     BigInt: big_int => JS_TAG_BIG_INT,
 }
 
+/// Types which are not a [`Type`] of their own are checked with a predicate on the value and
+/// named like `Head[is_head: "head"]->Object->Value`.
 macro_rules! sub_types {
-    ($( $head:ident$(->$sub_type:ident)* $as:ident $ref:ident $into:ident $try_into:ident $from:ident,)*) => {
+    ($( $head:ident$([$check:ident: $name:literal])?$(->$sub_type:ident)* $as:ident $ref:ident $into:ident $try_into:ident $from:ident,)*) => {
         $(
             impl<'js> $head<'js> {
                 /// Reference to value
@@ -549,11 +692,10 @@ macro_rules! sub_types {
 
                 /// Convert from value
                 pub fn from_value(value: Value<'js>) -> Result<Self> {
-                    let type_ = value.type_of();
-                    if type_.interpretable_as(Type::$head) {
+                    if sub_types!(@is $head [$($check)?] value) {
                         Ok(sub_types!(@wrap $head$(->$sub_type)*  value))
                     } else {
-                        Err(Error::new_from_js(type_.as_str(), Type::$head.as_str()))
+                        Err(Error::new_from_js(value.type_name(), sub_types!(@name $head [$($name)?])))
                     }
                 }
 
@@ -600,7 +742,7 @@ macro_rules! sub_types {
 
                 #[doc = concat!("Try reinterpret as [`",stringify!($head),"`]")]
                 pub fn $as(&self) -> Option<&$head<'js>> {
-                    if self.type_of().interpretable_as(Type::$head) {
+                    if sub_types!(@is $head [$($check)?] self) {
                         Some(unsafe { self.$ref() })
                     } else {
                         None
@@ -609,7 +751,7 @@ macro_rules! sub_types {
 
                 #[doc = concat!("Try convert into [`",stringify!($head),"`]")]
                 pub fn $into(self) -> Option<$head<'js>> {
-                    if self.type_of().interpretable_as(Type::$head) {
+                    if sub_types!(@is $head [$($check)?] self) {
                         Some(sub_types!(@wrap $head$(->$sub_type)* self))
                     } else {
                         None
@@ -618,7 +760,7 @@ macro_rules! sub_types {
 
                 #[doc = concat!("Try convert into [`",stringify!($head),"`] returning self if the conversion fails.")]
                 pub fn $try_into(self) -> std::result::Result<$head<'js>, Value<'js>> {
-                    if self.type_of().interpretable_as(Type::$head) {
+                    if sub_types!(@is $head [$($check)?] self) {
                         Ok(sub_types!(@wrap $head$(->$sub_type)* self))
                     } else {
                         Err(self)
@@ -659,6 +801,12 @@ macro_rules! sub_types {
 
     (@type $type:ident) => { $type<'js> };
 
+    (@is $head:ident [] $val:expr) => { $val.type_of().interpretable_as(Type::$head) };
+    (@is $head:ident [$check:ident] $val:expr) => { $val.$check() };
+
+    (@name $head:ident []) => { Type::$head.as_str() };
+    (@name $head:ident [$name:literal]) => { $name };
+
     (@head $head:ident $(rem:ident)*)  => { $head };
     (@head_ty $head:ident$(,$rem:ident)*)  => { $head<'js> };
 
@@ -702,6 +850,9 @@ sub_types! {
     Constructor->Function->Object->Value as_constructor ref_constructor into_constructor try_into_constructor from_constructor,
     Array->Object->Value as_array ref_array into_array try_into_array from_array,
     Exception->Object->Value as_exception ref_exception into_exception try_into_exception from_exception,
+    PromiseObject[is_promise: "promise"]->Object->Value as_promise ref_promise into_promise try_into_promise from_promise,
+    Date[is_date: "Date"]->Object->Value as_date ref_date into_date try_into_date from_date,
+    RegExp[is_regexp: "RegExp"]->Object->Value as_regexp ref_regexp into_regexp try_into_regexp from_regexp,
     BigInt->Value as_big_int ref_big_int into_big_int try_into_big_int from_big_int,
 }
 
@@ -758,6 +909,216 @@ void_types! {
 mod test {
     use crate::*;
 
+    #[test]
+    fn instance_of() {
+        test_with(|ctx| {
+            let array: Object = ctx.globals().get("Array").unwrap();
+            let error: Object = ctx.globals().get("TypeError").unwrap();
+            let value: Value = ctx.eval("[1, 2]").unwrap();
+            assert!(value.instance_of(&array).unwrap());
+            assert!(!value.instance_of(&error).unwrap());
+            let value: Value = ctx.eval("new TypeError('oops')").unwrap();
+            assert!(value.instance_of(&error).unwrap());
+            assert!(value.is_error());
+            let value = Value::new_int(ctx.clone(), 1);
+            assert!(!value.instance_of(&array).unwrap());
+
+            let not_callable = Object::new(ctx.clone()).unwrap();
+            let err = value.instance_of(&not_callable).unwrap_err();
+            assert!(matches!(err, Error::Exception));
+        })
+    }
+
+    #[test]
+    fn equality() {
+        test_with(|ctx| {
+            let values: Array = ctx
+                .eval(
+                    r#"
+                    const obj = { valueOf() { return 1 } };
+                    [obj, obj, {}, "a" + "b", "ab", NaN, 0, -0, 1, "1", 10n, 10n, null, undefined]
+                    "#,
+                )
+                .unwrap();
+            let v = |idx: usize| values.get::<Value>(idx).unwrap();
+
+            assert!(v(0).strict_eq(&v(1)));
+            assert!(!v(0).strict_eq(&v(2)));
+            assert!(v(3).strict_eq(&v(4)));
+            assert!(!v(5).strict_eq(&v(5)));
+            assert!(v(6).strict_eq(&v(7)));
+            assert!(!v(8).strict_eq(&v(9)));
+            assert!(v(10).strict_eq(&v(11)));
+            assert!(!v(12).strict_eq(&v(13)));
+
+            assert!(v(8).loose_eq(&v(9)).unwrap());
+            assert!(v(0).loose_eq(&v(8)).unwrap());
+            assert!(v(12).loose_eq(&v(13)).unwrap());
+            assert!(!v(5).loose_eq(&v(5)).unwrap());
+            let throwing: Value = ctx
+                .eval("({ valueOf() { throw new Error('no') } })")
+                .unwrap();
+            assert!(matches!(v(8).loose_eq(&throwing), Err(Error::Exception)));
+
+            assert!(v(5).same_value(&v(5)));
+            assert!(!v(6).same_value(&v(7)));
+            assert!(v(5).same_value_zero(&v(5)));
+            assert!(v(6).same_value_zero(&v(7)));
+        })
+    }
+
+    #[test]
+    fn clone_into_other_runtime() {
+        let rt1 = Runtime::new().unwrap();
+        let ctx1 = Context::full(&rt1).unwrap();
+        let rt2 = Runtime::new().unwrap();
+        let ctx2 = Context::full(&rt2).unwrap();
+
+        ctx1.with(|source| {
+            let value: Value = source
+                .eval(
+                    r#"
+                    const shared = { n: 1 };
+                    const value = {
+                        list: [1, "two", shared, shared],
+                        bytes: new Uint8Array([1, 2, 3]),
+                        floats: new Float64Array([0.5]),
+                    };
+                    value.self = value;
+                    value
+                    "#,
+                )
+                .unwrap();
+            let func: Value = source.eval("() => {}").unwrap();
+
+            ctx2.with(|target| {
+                let copy = value.clone_into(&target).unwrap();
+                target.globals().set("copy", copy).unwrap();
+                let ok: bool = target
+                    .eval(
+                        r#"
+                        copy.self === copy &&
+                        copy.list[2] === copy.list[3] &&
+                        copy.list.join() === "1,two,[object Object],[object Object]" &&
+                        copy.bytes instanceof Uint8Array && copy.bytes.join() === "1,2,3" &&
+                        copy.floats[0] === 0.5
+                        "#,
+                    )
+                    .unwrap();
+                assert!(ok);
+
+                let err = func.clone_into(&target).unwrap_err();
+                assert!(err.to_string().contains("can not be structurally cloned"));
+            });
+        });
+    }
+
+    #[test]
+    fn clone_into_object_shapes() {
+        let rt = Runtime::new().unwrap();
+        let ctx1 = Context::full(&rt).unwrap();
+        let ctx2 = Context::full(&rt).unwrap();
+
+        ctx1.with(|source| {
+            let value: Value = source
+                .eval(
+                    r#"
+                    const bare = Object.create(null);
+                    bare.x = 1;
+                    const key = Symbol.for("key");
+                    ({
+                        polluted: JSON.parse('{"__proto__":{"polluted":1}}'),
+                        bare,
+                        [key]: 2,
+                    })
+                    "#,
+                )
+                .unwrap();
+            let deep: Value = source
+                .eval("let d = {}; for (let i = 0; i < 100000; i++) d = { d }; d")
+                .unwrap();
+
+            ctx2.with(|target| {
+                let copy = value.clone_into(&target).unwrap();
+                target.globals().set("copy", copy).unwrap();
+                let ok: bool = target
+                    .eval(
+                        r#"
+                        Object.getPrototypeOf(copy.polluted) === Object.prototype &&
+                        Object.prototype.hasOwnProperty.call(copy.polluted, "__proto__") &&
+                        copy.polluted.polluted === undefined &&
+                        Object.getPrototypeOf(copy.bare) === null && copy.bare.x === 1 &&
+                        copy[Symbol.for("key")] === 2
+                        "#,
+                    )
+                    .unwrap();
+                assert!(ok);
+
+                let err = deep.clone_into(&target).unwrap_err();
+                assert!(err.to_string().contains("depth"));
+            });
+        });
+    }
+
+    #[test]
+    fn deferred_conversion() {
+        fn sum(values: &[Value]) -> Result<i32> {
+            values.iter().map(|value| value.get::<i32>()).sum()
+        }
+
+        test_with(|ctx| {
+            let values: Vec<Value> = ctx.eval("[1, 2, 3]").unwrap();
+            assert_eq!(sum(&values).unwrap(), 6);
+            let values: Vec<Value> = ctx.eval("[1, 'a']").unwrap();
+            assert!(sum(&values).is_err());
+        })
+    }
+
+    #[test]
+    fn is_thenable() {
+        test_with(|ctx| {
+            let thenable: Value = ctx.eval("({ then(resolve) { resolve(1) } })").unwrap();
+            assert!(thenable.is_thenable().unwrap());
+            let promise: Value = ctx.eval("Promise.resolve(1)").unwrap();
+            assert!(promise.is_thenable().unwrap());
+
+            let not_callable: Value = ctx.eval("({ then: 1 })").unwrap();
+            assert!(!not_callable.is_thenable().unwrap());
+            let number: Value = ctx.eval("1").unwrap();
+            assert!(!number.is_thenable().unwrap());
+
+            let throwing: Value = ctx
+                .eval("({ get then() { throw new Error('oops') } })")
+                .unwrap();
+            assert!(throwing.is_thenable().is_err());
+        })
+    }
+
+    #[test]
+    fn eq_primitives() {
+        test_with(|ctx| {
+            let int = Value::new_int(ctx.clone(), 5);
+            assert!(int == 5);
+            assert!(int != 6);
+            assert!(int == 5.0);
+            assert!(int != "5");
+            assert!(int != true);
+
+            let float = Value::new_float(ctx.clone(), 2.5);
+            assert!(float == 2.5);
+            assert!(float != 2);
+
+            let boolean = Value::new_bool(ctx.clone(), true);
+            assert!(boolean == true);
+            assert!(boolean != 1);
+
+            let string: Value = ctx.eval("'five'").unwrap();
+            assert!(string == "five");
+            assert!(string != "six");
+            assert!(string != false);
+        });
+    }
+
     #[test]
     fn type_matches() {
         assert!(Type::Bool.interpretable_as(Type::Bool));
@@ -29,6 +29,19 @@ impl<'js> Array<'js> {
         }))
     }
 
+    /// Create a new JavaScript array from the values of an iterator.
+    ///
+    /// The values are defined directly on the new array instead of being assigned, which skips
+    /// the lookup of setters on the prototype chain and keeps the array in the optimized
+    /// representation, so this is also the fast way to convert a `Vec<T>`.
+    pub fn from_iter<I, V>(ctx: Ctx<'js>, iter: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = V>,
+        V: IntoJs<'js>,
+    {
+        Self::from_iter_js(&ctx, iter)
+    }
+
     /// Get the length of the JavaScript array.
     pub fn len(&self) -> usize {
         let ctx = self.ctx();
@@ -70,6 +83,93 @@ impl<'js> Array<'js> {
         Ok(())
     }
 
+    /// Append a value to the end of the JavaScript array.
+    pub fn push<V: IntoJs<'js>>(&self, val: V) -> Result<()> {
+        self.set(self.len(), val)
+    }
+
+    /// Remove the last value of the JavaScript array and return it.
+    ///
+    /// Returns `None` if the array is empty. The array is left unchanged if the value can't be
+    /// converted.
+    pub fn pop<V: FromJs<'js>>(&self) -> Result<Option<V>> {
+        let len = self.len();
+        if len == 0 {
+            return Ok(None);
+        }
+        let val: V = self.get(len - 1)?;
+        self.set_len(len - 1)?;
+        Ok(Some(val))
+    }
+
+    /// Insert a value at an index in the JavaScript array, shifting all values after it up by
+    /// one.
+    ///
+    /// If the index is greater than the length of the array the value is appended.
+    pub fn insert<V: IntoJs<'js>>(&self, idx: usize, val: V) -> Result<()> {
+        let len = self.len();
+        let idx = idx.min(len);
+        let val = val.into_js(self.ctx())?;
+        for from in (idx..len).rev() {
+            self.set(from + 1, self.get::<Value>(from)?)?;
+        }
+        self.set(idx, val)
+    }
+
+    /// Remove the value at an index in the JavaScript array and return it, shifting all values
+    /// after it down by one.
+    ///
+    /// Returns `None` if the index is out of bounds. The array is left unchanged if the value
+    /// can't be converted.
+    pub fn remove<V: FromJs<'js>>(&self, idx: usize) -> Result<Option<V>> {
+        let len = self.len();
+        if idx >= len {
+            return Ok(None);
+        }
+        let val: V = self.get(idx)?;
+        for from in idx + 1..len {
+            self.set(from - 1, self.get::<Value>(from)?)?;
+        }
+        self.set_len(len - 1)?;
+        Ok(Some(val))
+    }
+
+    /// Define a new value at an index, used when building new arrays.
+    fn define(&self, idx: usize, val: Value<'js>) -> Result<()> {
+        let ctx = self.ctx();
+        unsafe {
+            if 0 > qjs::JS_DefinePropertyValueUint32(
+                ctx.as_ptr(),
+                self.0.as_js_value(),
+                idx as _,
+                val.into_js_value(),
+                (qjs::JS_PROP_C_W_E | qjs::JS_PROP_THROW) as _,
+            ) {
+                return Err(ctx.raise_exception());
+            }
+        }
+        Ok(())
+    }
+
+    fn set_len(&self, len: usize) -> Result<()> {
+        self.0.set(PredefinedAtom::Length, len as u32)
+    }
+
+    /// Append all values of an iterator to the end of the JavaScript array.
+    ///
+    /// The length of the array is only read once, before appending.
+    pub fn extend<I, V>(&self, iter: I) -> Result<()>
+    where
+        I: IntoIterator<Item = V>,
+        V: IntoJs<'js>,
+    {
+        let base = self.len();
+        for (idx, val) in iter.into_iter().enumerate() {
+            self.set(base + idx, val)?;
+        }
+        Ok(())
+    }
+
     /// Get an iterator over elements of an array
     pub fn iter<T: FromJs<'js>>(&self) -> ArrayIter<'js, T> {
         let count = self.len() as _;
@@ -161,6 +261,15 @@ impl<'js> IntoIterator for Array<'js> {
     }
 }
 
+impl<'a, 'js> IntoIterator for &'a Array<'js> {
+    type Item = Result<Value<'js>>;
+    type IntoIter = ArrayIter<'js, Value<'js>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 impl<'js, A> FromIteratorJs<'js, A> for Array<'js>
 where
     A: IntoJs<'js>,
@@ -174,7 +283,7 @@ where
         let array = Array::new(ctx.clone())?;
         for (idx, item) in iter.into_iter().enumerate() {
             let item = item.into_js(ctx)?;
-            array.set(idx as _, item)?;
+            array.define(idx, item)?;
         }
         Ok(array)
     }
@@ -221,6 +330,82 @@ mod test {
         })
     }
 
+    #[test]
+    fn mutate() {
+        test_with(|ctx| {
+            let val = Array::new(ctx.clone()).unwrap();
+            val.push(1).unwrap();
+            val.push("two").unwrap();
+            val.push(3).unwrap();
+            assert_eq!(val.len(), 3);
+            assert_eq!(val.pop::<i32>().unwrap(), Some(3));
+            assert_eq!(val.len(), 2);
+
+            val.insert(0, 0).unwrap();
+            val.insert(10, 4).unwrap();
+            let items: Vec<Value> = val.iter().collect::<Result<_>>().unwrap();
+            assert_eq!(items.len(), 4);
+            assert_eq!(val.get::<i32>(0).unwrap(), 0);
+            assert_eq!(val.get::<StdString>(2).unwrap(), "two");
+            assert_eq!(val.get::<i32>(3).unwrap(), 4);
+
+            assert_eq!(val.remove::<StdString>(2).unwrap().as_deref(), Some("two"));
+            assert_eq!(val.remove::<i32>(3).unwrap(), None);
+            let items: Vec<i32> = val.iter().collect::<Result<_>>().unwrap();
+            assert_eq!(items, [0, 1, 4]);
+
+            assert!(val.pop::<Object>().is_err());
+            assert!(val.remove::<Object>(0).is_err());
+            let items: Vec<i32> = val.iter().collect::<Result<_>>().unwrap();
+            assert_eq!(items, [0, 1, 4]);
+
+            while val.pop::<i32>().unwrap().is_some() {}
+            assert!(val.is_empty());
+            assert_eq!(val.pop::<i32>().unwrap(), None);
+
+            let is_array: bool = ctx
+                .globals()
+                .get::<_, Object>("Array")
+                .unwrap()
+                .get::<_, Function>("isArray")
+                .unwrap()
+                .call((val,))
+                .unwrap();
+            assert!(is_array);
+        })
+    }
+
+    #[test]
+    fn from_iter() {
+        test_with(|ctx| {
+            let array = Array::from_iter(ctx.clone(), vec![1, 2, 3]).unwrap();
+            assert_eq!(array.len(), 3);
+            let items: Vec<i32> = array.iter().collect::<Result<_>>().unwrap();
+            assert_eq!(items, [1, 2, 3]);
+
+            let array = Array::from_iter(ctx.clone(), (0..4).map(|x| x.to_string())).unwrap();
+            ctx.globals().set("array", array).unwrap();
+            let joined: StdString = ctx
+                .eval("Array.isArray(array) ? array.join('-') : ''")
+                .unwrap();
+            assert_eq!(joined, "0-1-2-3");
+
+            let empty = Array::from_iter(ctx.clone(), Vec::<i32>::new()).unwrap();
+            assert!(empty.is_empty());
+        })
+    }
+
+    #[test]
+    fn extend() {
+        test_with(|ctx| {
+            let val: Array = ctx.eval("[1]").unwrap();
+            val.extend([2, 3, 4]).unwrap();
+            assert_eq!(val.len(), 4);
+            let items: Vec<i32> = val.iter().collect::<Result<_>>().unwrap();
+            assert_eq!(items, [1, 2, 3, 4]);
+        })
+    }
+
     #[test]
     fn into_iter() {
         test_with(|ctx| {
@@ -258,6 +443,29 @@ mod test {
         })
     }
 
+    #[test]
+    fn iter_errors() {
+        test_with(|ctx| {
+            let val: Array = ctx.eval("[1, 'a', 3]").unwrap();
+            let mut iter = val.iter::<i32>();
+            assert_eq!(iter.len(), 3);
+            assert_eq!(iter.next().unwrap().unwrap(), 1);
+            assert!(iter.next().unwrap().unwrap_err().is_from_js());
+            assert_eq!(iter.next().unwrap().unwrap(), 3);
+            assert!(iter.next().is_none());
+
+            let reversed: Vec<i32> = val.iter().rev().filter_map(|item| item.ok()).collect();
+            assert_eq!(reversed, [3, 1]);
+
+            let mut count = 0;
+            for item in &val {
+                assert!(!item.unwrap().is_undefined());
+                count += 1;
+            }
+            assert_eq!(count, 3);
+        })
+    }
+
     #[test]
     fn collect_js() {
         test_with(|ctx| {
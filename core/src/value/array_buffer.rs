@@ -8,6 +8,10 @@ use std::{
     ptr::NonNull,
     result::Result as StdResult,
     slice,
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        Arc,
+    },
 };
 
 use super::typed_array::TypedArrayItem;
@@ -21,6 +25,7 @@ pub struct RawArrayBuffer {
 pub enum AsSliceError {
     BufferUsed,
     InvalidAlignment,
+    Shared,
 }
 
 impl fmt::Display for AsSliceError {
@@ -30,12 +35,87 @@ impl fmt::Display for AsSliceError {
             AsSliceError::InvalidAlignment => {
                 write!(f, "Buffer had a different alignment than was requested")
             }
+            AsSliceError::Shared => {
+                write!(f, "Buffer is shared and can only be accessed atomically")
+            }
+        }
+    }
+}
+
+/// A block of memory which can be shared between contexts and threads.
+///
+/// The memory is exposed to scripts as the contents of `SharedArrayBuffer` objects created with
+/// [`ArrayBuffer::new_shared`] without copying, so every buffer created from clones of the same
+/// memory observes the writes made through the others, even across runtimes. The memory is freed
+/// when the last clone and the last buffer using it are dropped.
+///
+/// Because scripts may write to the memory at any time, Rust only accesses it through atomics,
+/// the slice accessors of [`ArrayBuffer`] and [`TypedArray`](crate::TypedArray) refuse shared
+/// buffers.
+#[cfg_attr(feature = "doc-cfg", doc(cfg(feature = "array-buffer")))]
+#[derive(Clone)]
+pub struct SharedMemory(Arc<[AtomicU8]>);
+
+impl SharedMemory {
+    /// Allocate zeroed memory of the given length in bytes
+    pub fn new(len: usize) -> Self {
+        Self((0..len).map(|_| AtomicU8::new(0)).collect())
+    }
+
+    /// Get the length of the memory in bytes.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns whether the memory is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the bytes of the memory.
+    pub fn as_atomic_bytes(&self) -> &[AtomicU8] {
+        &self.0
+    }
+
+    /// Copy the current contents of the memory into a vector.
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.0
+            .iter()
+            .map(|byte| byte.load(Ordering::SeqCst))
+            .collect()
+    }
+
+    /// Overwrite the start of the memory with the given bytes.
+    ///
+    /// # Panics
+    /// Panics if the bytes are longer than the memory.
+    pub fn write(&self, bytes: &[u8]) {
+        assert!(bytes.len() <= self.len(), "bytes exceed the shared memory");
+        for (byte, src) in self.0.iter().zip(bytes) {
+            byte.store(*src, Ordering::SeqCst);
         }
     }
 }
 
+impl From<&[u8]> for SharedMemory {
+    fn from(bytes: &[u8]) -> Self {
+        Self(bytes.iter().map(|byte| AtomicU8::new(*byte)).collect())
+    }
+}
+
+impl fmt::Debug for SharedMemory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedMemory")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
 /// Rust representation of a JavaScript object of class ArrayBuffer.
 ///
+/// Objects of class SharedArrayBuffer are represented by this type too, see
+/// [`ArrayBuffer::new_shared`].
+///
 #[cfg_attr(feature = "doc-cfg", doc(cfg(feature = "array-buffer")))]
 #[derive(Debug, PartialEq, Clone, Eq, Hash)]
 #[repr(transparent)]
@@ -92,6 +172,47 @@ impl<'js> ArrayBuffer<'js> {
         })))
     }
 
+    /// Create a `SharedArrayBuffer` backed by the given shared memory
+    ///
+    /// The buffer keeps the memory alive until it is garbage collected. Scripts can wrap it into
+    /// typed arrays and use `Atomics` on it like on any other `SharedArrayBuffer`. From Rust the
+    /// contents can only be accessed through [`ArrayBuffer::as_atomic_bytes`] or
+    /// [`SharedMemory::as_atomic_bytes`].
+    pub fn new_shared(ctx: Ctx<'js>, memory: SharedMemory) -> Result<Self> {
+        let ptr = memory.0.as_ptr() as *mut u8;
+        let size = memory.len();
+        let opaque = Box::into_raw(Box::new(memory));
+
+        extern "C" fn drop_shared(
+            _rt: *mut qjs::JSRuntime,
+            opaque: *mut c_void,
+            _ptr: *mut c_void,
+        ) {
+            unsafe { drop(Box::from_raw(opaque as *mut SharedMemory)) };
+        }
+
+        Ok(Self(Object(unsafe {
+            let val = qjs::JS_NewArrayBuffer(
+                ctx.as_ptr(),
+                ptr,
+                size as _,
+                Some(drop_shared),
+                opaque as _,
+                1,
+            );
+            ctx.handle_exception(val).map_err(|error| {
+                drop(Box::from_raw(opaque));
+                error
+            })?;
+            Value::from_js_value(ctx, val)
+        })))
+    }
+
+    /// Returns whether the buffer is a `SharedArrayBuffer`.
+    pub fn is_shared(&self) -> bool {
+        0 != unsafe { qjs::JS_IsSharedArrayBuffer(self.0.as_js_value()) }
+    }
+
     /// Get the length of the array buffer in bytes.
     pub fn len(&self) -> usize {
         Self::get_raw(&self.0).expect("Not an ArrayBuffer").len
@@ -104,16 +225,29 @@ impl<'js> ArrayBuffer<'js> {
 
     /// Returns the underlying bytes of the buffer,
     ///
-    /// Returns `None` if the array is detached.
+    /// Returns `None` if the array is detached or shared, see [`ArrayBuffer::as_atomic_bytes`].
     pub fn as_bytes(&self) -> Option<&[u8]> {
-        let raw = Self::get_raw(self.as_value())?;
+        let raw = self.get_raw_exclusive().ok()?;
         Some(unsafe { slice::from_raw_parts_mut(raw.ptr.as_ptr(), raw.len) })
     }
 
+    /// Returns the underlying bytes of a `SharedArrayBuffer`.
+    ///
+    /// Returns `None` if the buffer is not shared. Other threads may change the memory at any time,
+    /// so it is only accessed through atomics.
+    pub fn as_atomic_bytes(&self) -> Option<&[AtomicU8]> {
+        if !self.is_shared() {
+            return None;
+        }
+        let raw = Self::get_raw(self.as_value())?;
+        // SAFETY: `AtomicU8` has the same in-memory representation as `u8`.
+        Some(unsafe { slice::from_raw_parts(raw.ptr.as_ptr().cast(), raw.len) })
+    }
+
     /// Returns a slice if the buffer underlying buffer is properly aligned for the type and the
-    /// buffer is not detached.
+    /// buffer is neither detached nor shared.
     pub fn as_slice<T: TypedArrayItem>(&self) -> StdResult<&[T], AsSliceError> {
-        let raw = Self::get_raw(&self.0).ok_or(AsSliceError::BufferUsed)?;
+        let raw = self.get_raw_exclusive()?;
         if raw.ptr.as_ptr().align_offset(mem::align_of::<T>()) != 0 {
             return Err(AsSliceError::InvalidAlignment);
         }
@@ -121,6 +255,34 @@ impl<'js> ArrayBuffer<'js> {
         Ok(unsafe { slice::from_raw_parts(raw.ptr.as_ptr().cast(), len) })
     }
 
+    /// Returns the underlying bytes of the buffer for modification.
+    ///
+    /// Returns `None` if the array is detached or shared. Scripts see the changes without
+    /// copying, including through views like typed arrays which share the buffer.
+    ///
+    /// # Safety
+    /// Other handles to the same buffer, like clones of this handle or typed arrays viewing the
+    /// buffer, may access the bytes too. While the returned slice is alive no other reference to
+    /// the bytes may be used, the buffer must not be detached and no JavaScript code may run.
+    pub unsafe fn as_bytes_mut(&mut self) -> Option<&mut [u8]> {
+        let raw = self.get_raw_exclusive().ok()?;
+        Some(slice::from_raw_parts_mut(raw.ptr.as_ptr(), raw.len))
+    }
+
+    /// Returns a mutable slice if the underlying buffer is properly aligned for the type
+    /// and the buffer is neither detached nor shared.
+    ///
+    /// # Safety
+    /// The same rules apply as for [`ArrayBuffer::as_bytes_mut`].
+    pub unsafe fn as_slice_mut<T: TypedArrayItem>(&mut self) -> StdResult<&mut [T], AsSliceError> {
+        let raw = self.get_raw_exclusive()?;
+        if raw.ptr.as_ptr().align_offset(mem::align_of::<T>()) != 0 {
+            return Err(AsSliceError::InvalidAlignment);
+        }
+        let len = raw.len / size_of::<T>();
+        Ok(slice::from_raw_parts_mut(raw.ptr.as_ptr().cast(), len))
+    }
+
     /// Detach array buffer
     pub fn detach(&mut self) {
         unsafe { qjs::JS_DetachArrayBuffer(self.0.ctx.as_ptr(), self.0.as_js_value()) }
@@ -171,6 +333,14 @@ impl<'js> ArrayBuffer<'js> {
         Self::get_raw(self.as_value())
     }
 
+    /// Returns the raw buffer if it is not shared with other threads.
+    fn get_raw_exclusive(&self) -> StdResult<RawArrayBuffer, AsSliceError> {
+        if self.is_shared() {
+            return Err(AsSliceError::Shared);
+        }
+        Self::get_raw(self.as_value()).ok_or(AsSliceError::BufferUsed)
+    }
+
     pub(crate) fn get_raw(val: &Value<'js>) -> Option<RawArrayBuffer> {
         let ctx = val.ctx();
         let val = val.as_js_value();
@@ -190,7 +360,8 @@ impl<'js> ArrayBuffer<'js> {
 
 impl<'js, T: TypedArrayItem> AsRef<[T]> for ArrayBuffer<'js> {
     fn as_ref(&self) -> &[T] {
-        self.as_slice().expect("ArrayBuffer was detached")
+        self.as_slice()
+            .expect("ArrayBuffer was detached or is shared")
     }
 }
 
@@ -254,7 +425,9 @@ impl<'js> Object<'js> {
 
 #[cfg(test)]
 mod test {
+    use super::AsSliceError;
     use crate::*;
+    use std::sync::atomic::Ordering;
 
     #[test]
     fn from_javascript_i8() {
@@ -349,4 +522,70 @@ mod test {
             assert_eq!(val.as_bytes().unwrap(), &res)
         });
     }
+
+    #[test]
+    fn as_bytes_mut() {
+        test_with(|ctx| {
+            let mut buffer = ArrayBuffer::new(ctx.clone(), vec![0u8; 4]).unwrap();
+            unsafe { buffer.as_bytes_mut() }
+                .unwrap()
+                .copy_from_slice(&[1, 2, 3, 4]);
+            ctx.globals().set("buffer", buffer.clone()).unwrap();
+            let sum: i32 = ctx
+                .eval("new Uint8Array(buffer).reduce((a, b) => a + b)")
+                .unwrap();
+            assert_eq!(sum, 10);
+
+            unsafe { buffer.as_slice_mut::<u16>() }.unwrap()[0] = 0x0101;
+            let first: i32 = ctx.eval("new Uint8Array(buffer)[1]").unwrap();
+            assert_eq!(first, 1);
+
+            buffer.detach();
+            assert!(unsafe { buffer.as_bytes_mut() }.is_none());
+        });
+    }
+
+    #[test]
+    fn shared_memory() {
+        let memory = SharedMemory::from(&[1u8, 2, 3, 4][..]);
+        let rt_a = Runtime::new().unwrap();
+        let ctx_a = Context::full(&rt_a).unwrap();
+        let rt_b = Runtime::new().unwrap();
+        let ctx_b = Context::full(&rt_b).unwrap();
+
+        ctx_a.with(|ctx| {
+            let buffer = ArrayBuffer::new_shared(ctx.clone(), memory.clone()).unwrap();
+            assert!(buffer.is_shared());
+            assert!(!ArrayBuffer::new(ctx.clone(), vec![0u8])
+                .unwrap()
+                .is_shared());
+            assert_eq!(buffer.len(), 4);
+            assert!(buffer.as_bytes().is_none());
+            assert_eq!(buffer.as_slice::<u8>(), Err(AsSliceError::Shared));
+            buffer.as_atomic_bytes().unwrap()[1].store(20, Ordering::SeqCst);
+            ctx.globals().set("shared", buffer).unwrap();
+            let is_shared: bool = ctx.eval("shared instanceof SharedArrayBuffer").unwrap();
+            assert!(is_shared);
+            ctx.eval::<(), _>("new Uint8Array(shared)[0] = 10").unwrap();
+        });
+        assert_eq!(memory.to_vec(), [10, 20, 3, 4]);
+
+        ctx_b.with(|ctx| {
+            let buffer = ArrayBuffer::new_shared(ctx.clone(), memory.clone()).unwrap();
+            ctx.globals().set("shared", buffer).unwrap();
+            let sum: i32 = ctx
+                .eval("new Uint8Array(shared).reduce((a, b) => a + b)")
+                .unwrap();
+            assert_eq!(sum, 37);
+            let array: TypedArray<u8> = ctx.eval("new Uint8Array(shared)").unwrap();
+            assert!(array.as_slice().is_none());
+            assert!(array.as_bytes().is_none());
+        });
+
+        memory.write(&[5]);
+        ctx_a.with(|ctx| {
+            let first: i32 = ctx.eval("new Uint8Array(shared)[0]").unwrap();
+            assert_eq!(first, 5);
+        });
+    }
 }
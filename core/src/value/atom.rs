@@ -31,6 +31,19 @@ impl<'js> PartialEq for Atom<'js> {
 }
 impl<'js> Eq for Atom<'js> {}
 
+/// Compares the atom with the atom of the string, integer keys are equal to their decimal string.
+impl<'js> PartialEq<str> for Atom<'js> {
+    fn eq(&self, other: &str) -> bool {
+        matches!(Atom::from_str(self.ctx.clone(), other), Ok(other) if *self == other)
+    }
+}
+
+impl<'js> PartialEq<&str> for Atom<'js> {
+    fn eq(&self, other: &&str) -> bool {
+        *self == **other
+    }
+}
+
 impl<'js> Hash for Atom<'js> {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         state.write_u32(self.atom)
@@ -141,6 +154,28 @@ impl<'js> Atom<'js> {
         self.to_js_string().map(|String(value)| value)
     }
 
+    /// Convert the atom to the JavaScript value of the property key.
+    ///
+    /// Unlike [`Atom::to_value`] symbol atoms are converted to the symbol itself, all other atoms
+    /// to a string.
+    pub fn to_property_key(&self) -> Result<Value<'js>> {
+        unsafe {
+            let val = qjs::JS_AtomToValue(self.ctx.as_ptr(), self.atom);
+            let val = self.ctx.handle_exception(val)?;
+            Ok(Value::from_js_value(self.ctx.clone(), val))
+        }
+    }
+
+    /// Returns whether the atom is a symbol, as opposed to a string or index property key.
+    pub fn is_symbol(&self) -> bool {
+        unsafe {
+            let val = qjs::JS_AtomToValue(self.ctx.as_ptr(), self.atom);
+            let res = qjs::JS_IsSymbol(val);
+            qjs::JS_FreeValue(self.ctx.as_ptr(), val);
+            res
+        }
+    }
+
     pub(crate) unsafe fn from_atom_val(ctx: Ctx<'js>, val: qjs::JSAtom) -> Self {
         Atom { atom: val, ctx }
     }
@@ -168,3 +203,29 @@ impl<'js> Drop for Atom<'js> {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{atom::PredefinedAtom, test_with, Atom, Object};
+
+    #[test]
+    fn interning() {
+        test_with(|ctx| {
+            let a = Atom::from_str(ctx.clone(), "length").unwrap();
+            let b = Atom::from_predefined(ctx.clone(), PredefinedAtom::Length);
+            assert_eq!(a, b);
+            assert_eq!(a, "length");
+            assert_ne!(a, "size");
+            assert_eq!(Atom::from_u32(ctx.clone(), 12).unwrap(), "12");
+            assert_eq!(a.to_string().unwrap(), "length");
+
+            let key = Atom::from_str(ctx.clone(), "count").unwrap();
+            let obj = Object::new(ctx.clone()).unwrap();
+            for count in 0..3 {
+                obj.set(&key, count).unwrap();
+            }
+            assert_eq!(obj.get::<_, i32>(&key).unwrap(), 2);
+            assert_eq!(obj.keys::<Atom>().next().unwrap().unwrap(), key);
+        })
+    }
+}
@@ -1,6 +1,11 @@
-use crate::{qjs, Ctx, Error, Result, Value};
+use crate::{convert::Coerced, qjs, Ctx, Error, Result, StdString, Value};
+use std::{ffi::CString, str::FromStr};
 
 /// Rust representation of a JavaScript big int.
+///
+/// Integers which don't fit into 64 bits are converted through their decimal representation.
+/// Arbitrary precision integers of the `num-bigint` crate are supported with the `num-bigint`
+/// feature.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[repr(transparent)]
 pub struct BigInt<'js>(pub(crate) Value<'js>);
@@ -20,6 +25,27 @@ impl<'js> BigInt<'js> {
         }
     }
 
+    pub fn from_i128(ctx: Ctx<'js>, v: i128) -> Result<Self> {
+        match i64::try_from(v) {
+            Ok(v) => Self::from_i64(ctx, v),
+            Err(_) => Self::from_decimal(ctx, &v.to_string()),
+        }
+    }
+
+    pub fn from_u128(ctx: Ctx<'js>, v: u128) -> Result<Self> {
+        match u64::try_from(v) {
+            Ok(v) => Self::from_u64(ctx, v),
+            Err(_) => Self::from_decimal(ctx, &v.to_string()),
+        }
+    }
+
+    #[cfg(feature = "num-bigint")]
+    #[cfg_attr(feature = "doc-cfg", doc(cfg(feature = "num-bigint")))]
+    pub fn from_num_bigint(ctx: Ctx<'js>, v: &num_bigint::BigInt) -> Result<Self> {
+        Self::from_decimal(ctx, &v.to_string())
+    }
+
+    /// Convert to a `i64`, wrapping around like `BigInt.asIntN(64, value)` when out of range
     pub fn to_i64(self) -> Result<i64> {
         unsafe {
             let mut res: i64 = 0;
@@ -29,6 +55,52 @@ impl<'js> BigInt<'js> {
             Ok(res)
         }
     }
+
+    /// Convert to a `u64`, returns an error when out of range
+    pub fn to_u64(self) -> Result<u64> {
+        self.parse_decimal("u64")
+    }
+
+    /// Convert to a `i128`, returns an error when out of range
+    pub fn to_i128(self) -> Result<i128> {
+        self.parse_decimal("i128")
+    }
+
+    /// Convert to a `u128`, returns an error when out of range
+    pub fn to_u128(self) -> Result<u128> {
+        self.parse_decimal("u128")
+    }
+
+    #[cfg(feature = "num-bigint")]
+    #[cfg_attr(feature = "doc-cfg", doc(cfg(feature = "num-bigint")))]
+    pub fn to_num_bigint(self) -> Result<num_bigint::BigInt> {
+        self.parse_decimal("num_bigint::BigInt")
+    }
+
+    /// Parse a decimal with the engine, not with the `BigInt` global which scripts can replace
+    fn from_decimal(ctx: Ctx<'js>, decimal: &str) -> Result<Self> {
+        let decimal = CString::new(decimal)?;
+        let value = unsafe {
+            let value = qjs::JS_NewBigIntFromString(ctx.as_ptr(), decimal.as_ptr());
+            Value::from_js_value(ctx.clone(), ctx.handle_exception(value)?)
+        };
+        value
+            .into_big_int()
+            .ok_or_else(|| Error::new_from_js("value", "bigint"))
+    }
+
+    /// Convert to an integer through the decimal representation with range checking
+    pub(crate) fn parse_decimal<T: FromStr>(self, to: &'static str) -> Result<T> {
+        let decimal = self.0.get::<Coerced<StdString>>()?.0;
+        decimal.parse().map_err(|_| {
+            let message = if decimal.starts_with('-') {
+                "Underflow"
+            } else {
+                "Overflow"
+            };
+            Error::new_from_js_message("bigint", to, message)
+        })
+    }
 }
 
 #[cfg(test)]
@@ -59,4 +131,56 @@ mod test {
             func.call::<_, ()>((bigint,)).unwrap();
         })
     }
+
+    #[test]
+    fn wide_integers() {
+        test_with(|ctx| {
+            let big = BigInt::from_i128(ctx.clone(), i128::MIN).unwrap();
+            ctx.globals().set("big", big.clone()).unwrap();
+            let matches: bool = ctx.eval(format!("big == {}n", i128::MIN)).unwrap();
+            assert!(matches);
+            assert_eq!(big.to_i128().unwrap(), i128::MIN);
+
+            let big = BigInt::from_u128(ctx.clone(), u128::MAX).unwrap();
+            assert_eq!(big.clone().to_u128().unwrap(), u128::MAX);
+            assert!(big.to_u64().is_err());
+
+            let res: u64 = ctx.eval(format!("{}n", u64::MAX)).unwrap();
+            assert_eq!(res, u64::MAX);
+            let res: i128 = ctx.eval("-(2n ** 100n)").unwrap();
+            assert_eq!(res, -(1 << 100));
+            let res: i64 = ctx.eval("42").unwrap();
+            assert_eq!(res, 42);
+            assert!(ctx.eval::<i64, _>("2n ** 63n").is_err());
+            assert!(ctx.eval::<u128, _>("-1n").is_err());
+
+            let is_big_int: Function = ctx.eval("x => typeof x === 'bigint'").unwrap();
+            assert!(is_big_int.call::<_, bool>((1i128,)).unwrap());
+            let res: bool = is_big_int.call((u128::MAX,)).unwrap();
+            assert!(res);
+            let round_trip: Function = ctx.eval("x => x * 1n").unwrap();
+            let res: u128 = round_trip.call((u128::MAX,)).unwrap();
+            assert_eq!(res, u128::MAX);
+
+            // A replaced `BigInt` global isn't used for the conversion.
+            let _: () = ctx.eval("globalThis.BigInt = () => 'fake'").unwrap();
+            let big = BigInt::from_i128(ctx.clone(), i128::MAX).unwrap();
+            assert_eq!(big.to_i128().unwrap(), i128::MAX);
+        })
+    }
+
+    #[cfg(feature = "num-bigint")]
+    #[test]
+    fn num_bigint() {
+        test_with(|ctx| {
+            let value = num_bigint::BigInt::from(u128::MAX) * 1000;
+            let big = BigInt::from_num_bigint(ctx.clone(), &value).unwrap();
+            assert_eq!(big.to_num_bigint().unwrap(), value);
+
+            let double: Function = ctx.eval("x => x * 2n").unwrap();
+            let res: num_bigint::BigInt = double.call((&value,)).unwrap();
+            assert_eq!(res, value * 2);
+            assert!(ctx.eval::<num_bigint::BigInt, _>("1").is_err());
+        })
+    }
 }
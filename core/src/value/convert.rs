@@ -6,9 +6,17 @@ mod atom;
 mod coerce;
 mod from;
 mod into;
+#[cfg(feature = "serde_json")]
+mod json;
 
 /// The wrapper for values to force coercion
 ///
+/// The [`FromJs`] implementations of plain types are strict and only accept values of the
+/// matching JavaScript type, integers for example must be numbers without a fractional part which
+/// fit in the range of the type. `Coerced` instead applies the conversion rules of JavaScript, like
+/// `ToString`, `ToNumber` or `ToBoolean`. Integers smaller than 64 bit wrap around like the values
+/// stored in typed arrays.
+///
 /// ```
 /// # use rquickjs::{Runtime, Context, Result, convert::Coerced};
 /// # let rt = Runtime::new().unwrap();
@@ -34,6 +42,14 @@ mod into;
 ///
 /// assert!(ctx.eval::<f64, _>("`123.5`").is_err());
 /// assert_eq!(ctx.eval::<Coerced<f64>, _>("`123.5`")?.0, 123.5);
+///
+/// // Wrapping coercion to small integers
+/// assert!(ctx.eval::<u8, _>("257").is_err());
+/// assert_eq!(ctx.eval::<Coerced<u8>, _>("257")?.0, 1);
+///
+/// // Coercion to boolean
+/// assert!(ctx.eval::<bool, _>("0").is_err());
+/// assert!(!ctx.eval::<Coerced<bool>, _>("0")?.0);
 /// #
 /// # Ok(())
 /// # }).unwrap();
@@ -42,6 +58,59 @@ mod into;
 #[repr(transparent)]
 pub struct Coerced<T>(pub T);
 
+/// The wrapper for numbers to force saturating conversion
+///
+/// The value is coerced to a number and then clamped to the bounds of the target type in the same
+/// way as an `as` cast from `f64`. `NaN` is converted to `0`.
+///
+/// ```
+/// # use rquickjs::{Runtime, Context, Result, convert::Saturated};
+/// # let rt = Runtime::new().unwrap();
+/// # let ctx = Context::full(&rt).unwrap();
+/// # ctx.with(|ctx| -> Result<()> {
+/// #
+/// assert!(ctx.eval::<u8, _>("1e9").is_err());
+/// assert_eq!(ctx.eval::<Saturated<u8>, _>("1e9")?.0, 255);
+/// assert_eq!(ctx.eval::<Saturated<u8>, _>("-1")?.0, 0);
+/// assert_eq!(ctx.eval::<Saturated<i32>, _>("-Infinity")?.0, i32::MIN);
+/// assert_eq!(ctx.eval::<Saturated<i32>, _>("NaN")?.0, 0);
+/// #
+/// # Ok(())
+/// # }).unwrap();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Saturated<T>(pub T);
+
+/// The wrapper for locks to convert their value even if the lock is poisoned
+///
+/// A [`Mutex`](std::sync::Mutex) or [`RwLock`](std::sync::RwLock) is poisoned when a thread
+/// panicked while holding it. Converting a poisoned lock returns an error, wrapping the lock
+/// converts the value it protects regardless, like [`PoisonError::into_inner`](std::sync::PoisonError::into_inner).
+///
+/// ```
+/// # use rquickjs::{Runtime, Context, Result, convert::IgnorePoison};
+/// # use std::sync::Mutex;
+/// # let rt = Runtime::new().unwrap();
+/// # let ctx = Context::full(&rt).unwrap();
+/// # ctx.with(|ctx| -> Result<()> {
+/// #
+/// let lock = Mutex::new(1);
+/// let _ = std::panic::catch_unwind(|| {
+///     let _guard = lock.lock().unwrap();
+///     panic!("poisoned");
+/// });
+/// assert!(ctx.globals().set("value", &lock).is_err());
+/// ctx.globals().set("value", IgnorePoison(&lock))?;
+/// assert_eq!(ctx.eval::<i32, _>("value")?, 1);
+/// #
+/// # Ok(())
+/// # }).unwrap();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct IgnorePoison<T>(pub T);
+
 /// For converting JavaScript values to Rust values
 ///
 /// This trait automatically converts any value which can be
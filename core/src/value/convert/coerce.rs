@@ -1,4 +1,7 @@
-use crate::{convert::Coerced, qjs, Ctx, FromJs, Result, StdString, String, Value};
+use crate::{
+    convert::{Coerced, Saturated},
+    qjs, Ctx, FromJs, Result, StdString, String, Value,
+};
 use std::{
     mem::MaybeUninit,
     ops::{Deref, DerefMut},
@@ -83,6 +86,38 @@ coerce_impls! {
     f64 JS_ToFloat64,
 }
 
+macro_rules! coerce_cast_impls {
+	  ($($(#[$meta:meta])* $type:ident $base:ident,)*) => {
+		    $(
+            $(#[$meta])*
+            impl<'js> FromJs<'js> for Coerced<$type> {
+                fn from_js(ctx: &Ctx<'js>, value: Value<'js>) -> Result<Self> {
+                    <Coerced<$base>>::from_js(ctx, value).map(|Coerced(value)| Coerced(value as $type))
+                }
+            }
+        )*
+	  };
+}
+
+coerce_cast_impls! {
+    /// Coerce a value to a `i8` in the same way JavaScript would coerce values for an `Int8Array`
+    i8 i32,
+    /// Coerce a value to a `u8` in the same way JavaScript would coerce values for an `Uint8Array`
+    u8 i32,
+    /// Coerce a value to a `i16` in the same way JavaScript would coerce values for an `Int16Array`
+    i16 i32,
+    /// Coerce a value to a `u16` in the same way JavaScript would coerce values for an `Uint16Array`
+    u16 i32,
+    /// Coerce a value to a `u32` in the same way JavaScript would coerce values for the `>>>` operator
+    u32 i32,
+    /// Coerce a value to a `isize` in the same way JavaScript would coerce values
+    isize i64,
+    /// Coerce a value to a `usize` in the same way JavaScript would coerce values to an index
+    usize u64,
+    /// Coerce a value to a `f32` in the same way JavaScript would coerce values for a `Float32Array`
+    f32 f64,
+}
+
 /// Coerce a value to a `bool` in the same way JavaScript would coerce values
 impl<'js> FromJs<'js> for Coerced<bool> {
     fn from_js(ctx: &Ctx<'js>, value: Value<'js>) -> Result<Self> {
@@ -95,3 +130,58 @@ impl<'js> FromJs<'js> for Coerced<bool> {
         }))
     }
 }
+
+macro_rules! saturate_impls {
+	  ($($type:ident)*) => {
+		    $(
+            /// Convert a value to a number and clamp it to the bounds of the type
+            impl<'js> FromJs<'js> for Saturated<$type> {
+                fn from_js(ctx: &Ctx<'js>, value: Value<'js>) -> Result<Self> {
+                    let value = <Coerced<f64>>::from_js(ctx, value)?;
+                    Ok(Saturated(value.0 as $type))
+                }
+            }
+        )*
+	  };
+}
+
+saturate_impls! {
+    i8 i16 i32 i64 isize u8 u16 u32 u64 usize
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        convert::{Coerced, Saturated},
+        test_with,
+    };
+
+    #[test]
+    fn saturating() {
+        test_with(|ctx| {
+            assert_eq!(ctx.eval::<Saturated<u8>, _>("1e9").unwrap().0, 255);
+            assert_eq!(ctx.eval::<Saturated<u8>, _>("-3").unwrap().0, 0);
+            assert_eq!(
+                ctx.eval::<Saturated<i16>, _>("'40000'").unwrap().0,
+                i16::MAX
+            );
+            assert_eq!(ctx.eval::<Saturated<i32>, _>("12.7").unwrap().0, 12);
+            assert_eq!(ctx.eval::<Saturated<u64>, _>("NaN").unwrap().0, 0);
+        })
+    }
+
+    #[test]
+    fn wrapping_coercion() {
+        test_with(|ctx| {
+            assert_eq!(ctx.eval::<Coerced<u8>, _>("257").unwrap().0, 1);
+            assert_eq!(ctx.eval::<Coerced<i8>, _>("'200'").unwrap().0, -56);
+            assert_eq!(ctx.eval::<Coerced<u16>, _>("-1").unwrap().0, u16::MAX);
+            assert_eq!(ctx.eval::<Coerced<u32>, _>("-1.5").unwrap().0, u32::MAX);
+            assert_eq!(ctx.eval::<Coerced<usize>, _>("'12'").unwrap().0, 12);
+            assert!(ctx.eval::<Coerced<usize>, _>("-1").is_err());
+            ctx.catch();
+            assert_eq!(ctx.eval::<Coerced<f32>, _>("[2.5]").unwrap().0, 2.5);
+            assert!(ctx.eval::<u8, _>("'1'").is_err());
+        })
+    }
+}
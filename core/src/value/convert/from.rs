@@ -1,9 +1,10 @@
 use crate::{
-    convert::List, Array, Ctx, Error, FromAtom, FromJs, Object, Result, StdString, String, Type,
-    Value,
+    convert::List, Array, Atom, Ctx, Error, FromAtom, FromJs, Map, Object, Result, Set, StdString,
+    String, Type, Value,
 };
 use std::{
     cell::{Cell, RefCell},
+    cmp::Ordering,
     collections::{BTreeMap, BTreeSet, HashMap, HashSet, LinkedList, VecDeque},
     hash::{BuildHasher, Hash},
     rc::Rc,
@@ -36,7 +37,7 @@ impl<'js> FromJs<'js> for () {
     }
 }
 
-/// Convert from JS as optional
+/// Convert from JS as optional, `undefined` and `null` are converted to `None`
 impl<'js, T> FromJs<'js> for Option<T>
 where
     T: FromJs<'js>,
@@ -102,22 +103,58 @@ fn tuple_match_size(actual: usize, expected: usize) -> Result<()> {
     }
 }
 
+/// Convert from JS array to Rust array of a fixed size
+impl<'js, T, const N: usize> FromJs<'js> for [T; N]
+where
+    T: FromJs<'js>,
+{
+    fn from_js(_ctx: &Ctx<'js>, value: Value<'js>) -> Result<Self> {
+        let array = Array::from_value(value)?;
+        let size_error = |len: usize| {
+            Error::new_from_js_message(
+                "array",
+                "fixed size array",
+                format!("Expected {} values but got {}", N, len),
+            )
+        };
+        let len = array.len();
+        if len != N {
+            return Err(size_error(len));
+        }
+        let values = array.iter().collect::<Result<Vec<T>>>()?;
+        values
+            .try_into()
+            .map_err(|values: Vec<T>| size_error(values.len()))
+    }
+}
+
+/// Checks that `val` is within `min..max_exclusive`.
+///
+/// The upper bound is exclusive as the maximum of 64 bit integers can't be represented exactly
+/// by a float, `MAX + 1` on the other hand is a power of two which can.
 fn number_match_range<T: PartialOrd>(
     val: T,
     min: T,
-    max: T,
+    max_exclusive: T,
     from: &'static str,
     to: &'static str,
 ) -> Result<()> {
-    if val < min {
-        Err(Error::new_from_js_message(from, to, "Underflow"))
-    } else if val > max {
-        Err(Error::new_from_js_message(from, to, "Overflow"))
-    } else {
-        Ok(())
+    match val.partial_cmp(&min) {
+        None => Err(Error::new_from_js_message(from, to, "Not a number")),
+        Some(Ordering::Less) => Err(Error::new_from_js_message(from, to, "Underflow")),
+        _ if val >= max_exclusive => Err(Error::new_from_js_message(from, to, "Overflow")),
+        _ => Ok(()),
     }
 }
 
+/// Rejects numbers with a fractional part, must be called after checking the range
+fn number_is_integer(val: f64, from: &'static str, to: &'static str) -> Result<()> {
+    if val.fract() != 0.0 {
+        return Err(Error::new_from_js_message(from, to, "Not an integer"));
+    }
+    Ok(())
+}
+
 macro_rules! from_js_impls {
     // for reference types
     (ref: $($(#[$meta:meta])* $type:ident,)*) => {
@@ -166,6 +203,9 @@ macro_rules! from_js_impls {
                 $($param: $($pguard)*,)*
             {
                 fn from_js(_ctx: &Ctx<'js>, value: Value<'js>) -> Result<Self> {
+                    if let Some(set) = value.as_object().cloned().and_then(Set::from_object) {
+                        return set.iter().collect::<Result<_>>();
+                    }
                     let array = Array::from_value(value)?;
                     array.iter().collect::<Result<_>>()
                 }
@@ -183,7 +223,25 @@ macro_rules! from_js_impls {
                 V: FromJs<'js>,
                 $($param: $($pguard)*,)*
             {
-                fn from_js(_ctx: &Ctx<'js>, value: Value<'js>) -> Result<Self> {
+                fn from_js(ctx: &Ctx<'js>, value: Value<'js>) -> Result<Self> {
+                    // The keys of maps are converted like property keys, objects would all become
+                    // the same string so they are rejected
+                    if let Some(map) = value.as_object().cloned().and_then(Map::from_object) {
+                        return map
+                            .iter::<Value, V>()
+                            .map(|entry| {
+                                let (key, value) = entry?;
+                                if key.is_object() {
+                                    return Err(Error::new_from_js_message(
+                                        key.type_name(),
+                                        "property key",
+                                        "Map keys must be primitive values",
+                                    ));
+                                }
+                                Ok((K::from_atom(Atom::from_value(ctx.clone(), &key)?)?, value))
+                            })
+                            .collect::<Result<_>>();
+                    }
                     let object = Object::from_value(value)?;
                     object.props().collect::<Result<_>>()
                 }
@@ -214,7 +272,8 @@ macro_rules! from_js_impls {
                 impl<'js> FromJs<'js> for $type {
                     fn from_js(ctx: &Ctx<'js>, value: Value<'js>) -> Result<Self> {
                         let num = <$base>::from_js(ctx, value)?;
-                        number_match_range(num, $type::MIN as $base, $type::MAX as $base, stringify!($base), stringify!($type))?;
+                        number_match_range(num, $type::MIN as $base, $type::MAX as $base + 1 as $base, stringify!($base), stringify!($type))?;
+                        from_js_impls!(@integer $base num, stringify!($type));
                         Ok(num as $type)
                     }
                 }
@@ -224,6 +283,9 @@ macro_rules! from_js_impls {
 
     (@one $($t:tt)*) => { 1 };
 
+    (@integer f64 $num:ident, $to:expr) => { number_is_integer($num, "f64", $to)? };
+    (@integer $base:ident $num:ident, $to:expr) => {};
+
     (@idx A) => { 0 };
     (@idx B) => { 1 };
     (@idx C) => { 2 };
@@ -245,16 +307,66 @@ macro_rules! from_js_impls {
 from_js_impls! {
     val:
     i32: i8 u8 i16 u16,
-    f64: u32 u64 i64 usize isize,
+    f64: u32 usize isize,
+}
+
+// The wide integer types also accept big ints, which represent them without loss of precision
+macro_rules! wide_int_from_js_impls {
+    ($($type:ident)*) => {
+        $(
+            impl<'js> FromJs<'js> for $type {
+                fn from_js(ctx: &Ctx<'js>, value: Value<'js>) -> Result<Self> {
+                    let value = match value.try_into_big_int() {
+                        Ok(big_int) => return big_int.parse_decimal(stringify!($type)),
+                        Err(value) => value,
+                    };
+                    let num = f64::from_js(ctx, value)?;
+                    number_match_range(num, $type::MIN as f64, $type::MAX as f64 + 1.0, "f64", stringify!($type))?;
+                    number_is_integer(num, "f64", stringify!($type))?;
+                    Ok(num as $type)
+                }
+            }
+        )*
+    };
+}
+
+wide_int_from_js_impls! {
+    i64 u64 i128 u128
+}
+
+#[cfg(feature = "num-bigint")]
+#[cfg_attr(feature = "doc-cfg", doc(cfg(feature = "num-bigint")))]
+impl<'js> FromJs<'js> for num_bigint::BigInt {
+    fn from_js(_ctx: &Ctx<'js>, value: Value<'js>) -> Result<Self> {
+        crate::BigInt::from_value(value)?.to_num_bigint()
+    }
 }
 
 from_js_impls! {
     val:
     bool => Bool get_bool,
-    i32 => Int get_int,
     f64 => Float get_float Int get_int,
 }
 
+impl<'js> FromJs<'js> for i32 {
+    fn from_js(_ctx: &Ctx<'js>, value: Value<'js>) -> Result<Self> {
+        let type_ = value.type_of();
+        match type_ {
+            Type::Int => Ok(unsafe { value.get_int() }),
+            // QuickJS may store integers as floats, for example as the result of arithmetic.
+            Type::Float => {
+                let num = unsafe { value.get_float() };
+                if num.fract() != 0.0 {
+                    return Err(Error::new_from_js_message("float", "i32", "Not an integer"));
+                }
+                number_match_range(num, i32::MIN as f64, i32::MAX as f64 + 1.0, "float", "i32")?;
+                Ok(num as i32)
+            }
+            _ => Err(Error::new_from_js(type_.as_str(), "i32")),
+        }
+    }
+}
+
 from_js_impls! {
     ref:
     Box,
@@ -294,9 +406,9 @@ from_js_impls! {
     VecDeque,
     /// Convert from JS array to Rust linked list
     LinkedList,
-    /// Convert from JS array to Rust hash set
+    /// Convert from JS array or set to Rust hash set
     HashSet {S: Default + BuildHasher} (Eq + Hash),
-    /// Convert from JS array to Rust btree set
+    /// Convert from JS array or set to Rust btree set
     BTreeSet (Eq + Ord),
     /// Convert from JS array to Rust index set
     #[cfg(feature = "indexmap")]
@@ -306,11 +418,13 @@ from_js_impls! {
 
 from_js_impls! {
     map:
-    /// Convert from JS object to Rust hash map
+    /// Convert from JS object or map to Rust hash map
     HashMap {S: Default + BuildHasher} (Eq + Hash),
-    /// Convert from JS object to Rust btree map
+    /// Convert from JS object or map to Rust btree map
     BTreeMap (Eq + Ord),
     /// Convert from JS object to Rust index map
+    ///
+    /// The keys are kept in the enumeration order of the object properties.
     #[cfg(feature = "indexmap")]
     #[cfg_attr(feature = "doc-cfg", doc(cfg(feature = "indexmap")))]
     IndexMap {S: Default + BuildHasher} (Eq + Hash),
@@ -322,19 +436,11 @@ impl<'js> FromJs<'js> for f32 {
     }
 }
 
-fn date_to_millis<'js>(ctx: &Ctx<'js>, value: Value<'js>) -> Result<i64> {
-    let global = ctx.globals();
-    let date_ctor: Object = global.get("Date")?;
-
+fn date_to_millis<'js>(_ctx: &Ctx<'js>, value: Value<'js>) -> Result<i64> {
     let value = Object::from_value(value)?;
-
-    if !value.is_instance_of(&date_ctor) {
-        return Err(Error::new_from_js("Object", "Date"));
-    }
-
-    let get_time_fn: crate::Function = value.get("getTime")?;
-
-    get_time_fn.call((crate::function::This(value),))
+    let millis = value.as_date_millis()?;
+    // Valid time values are limited to +-8.64e15 so they always fit in an i64.
+    Ok(millis as i64)
 }
 
 impl<'js> FromJs<'js> for SystemTime {
@@ -386,6 +492,108 @@ chrono_from_js_impls! {
 
 #[cfg(test)]
 mod test {
+    #[test]
+    fn integer_valued_floats() {
+        use crate::{Context, Runtime, Value};
+
+        let runtime = Runtime::new().unwrap();
+        let ctx = Context::full(&runtime).unwrap();
+
+        ctx.with(|ctx| {
+            let res: i64 = ctx.eval("2**40").unwrap();
+            assert_eq!(res, 1 << 40);
+            let res: i64 = ctx.eval("2**53").unwrap();
+            assert_eq!(res, 1 << 53);
+            let res: i64 = ctx.eval("2**53 - 1").unwrap();
+            assert_eq!(res, (1 << 53) - 1);
+            let res: i64 = ctx.eval("-(2**53)").unwrap();
+            assert_eq!(res, -(1 << 53));
+            let res: u64 = ctx.eval("JSON.parse('9007199254740993')").unwrap();
+            assert_eq!(res, 1u64 << 53);
+            let res: i64 = ctx.eval("-(2**63)").unwrap();
+            assert_eq!(res, i64::MIN);
+
+            assert!(ctx.eval::<i64, _>("2**63").is_err());
+            assert!(ctx.eval::<u64, _>("2**64").is_err());
+            assert!(ctx.eval::<u64, _>("-1").is_err());
+            assert!(ctx.eval::<i64, _>("NaN").is_err());
+
+            let float = Value::new_float(ctx.clone(), 5.0);
+            assert_eq!(float.get::<i32>().unwrap(), 5);
+            assert_eq!(float.get::<u8>().unwrap(), 5);
+            assert_eq!(float.get::<u32>().unwrap(), 5);
+            assert_eq!(float.get::<u64>().unwrap(), 5);
+            let float = Value::new_float(ctx.clone(), 1.5);
+            assert!(float.get::<i32>().is_err());
+            assert!(float.get::<u32>().is_err());
+            assert!(float.get::<usize>().is_err());
+            assert!(float.get::<isize>().is_err());
+            assert!(float.get::<i64>().is_err());
+            assert!(float.get::<u64>().is_err());
+            let float = Value::new_float(ctx.clone(), 2f64.powi(31));
+            assert!(float.get::<i32>().is_err());
+        });
+    }
+
+    #[test]
+    fn fixed_size_arrays() {
+        use crate::{Context, Runtime};
+
+        let runtime = Runtime::new().unwrap();
+        let ctx = Context::full(&runtime).unwrap();
+
+        ctx.with(|ctx| {
+            let res: [i32; 3] = ctx.eval("[1, 2, 3]").unwrap();
+            assert_eq!(res, [1, 2, 3]);
+            let res: [String; 0] = ctx.eval("[]").unwrap();
+            assert_eq!(res.len(), 0);
+            let err = ctx.eval::<[i32; 2], _>("[1, 2, 3]").unwrap_err();
+            assert!(err.is_from_js());
+            assert!(ctx.eval::<[i32; 2], _>("[1]").is_err());
+            assert!(ctx.eval::<[i32; 1], _>("({ 0: 1, length: 1 })").is_err());
+
+            ctx.globals().set("pair", [1.5, 2.5]).unwrap();
+            ctx.globals()
+                .set("names", &["a".to_string(), "b".to_string()])
+                .unwrap();
+            let res: bool = ctx
+                .eval("Array.isArray(pair) && pair[1] === 2.5 && names.join() === 'a,b'")
+                .unwrap();
+            assert!(res);
+        });
+    }
+
+    #[cfg(feature = "indexmap")]
+    #[test]
+    fn index_map_order() {
+        use crate::{Context, Object, Runtime};
+        use indexmap::IndexMap;
+
+        let runtime = Runtime::new().unwrap();
+        let ctx = Context::full(&runtime).unwrap();
+
+        ctx.with(|ctx| {
+            let res: IndexMap<String, i32> = ctx.eval("({ z: 1, a: 2, m: 3 })").unwrap();
+            assert_eq!(
+                res.iter().collect::<Vec<_>>(),
+                [
+                    (&"z".to_string(), &1),
+                    (&"a".to_string(), &2),
+                    (&"m".to_string(), &3)
+                ]
+            );
+
+            let obj: Object = ctx.eval("({})").unwrap();
+            obj.set("map", res).unwrap();
+            let keys: String = ctx
+                .eval::<crate::Function, _>("(obj) => Object.keys(obj.map).join()")
+                .unwrap()
+                .call((obj,))
+                .unwrap();
+            assert_eq!(keys, "z,a,m");
+        });
+    }
+
     #[test]
     fn js_to_system_time() {
         use crate::{Context, Runtime};
@@ -436,5 +644,10 @@ mod test {
                 .unwrap();
             assert_eq!(1654309010000, res.timestamp_millis());
         });
+
+        ctx.with(|ctx| {
+            let res: Result<DateTime<Utc>, _> = ctx.eval("new Date('invalid')");
+            assert!(res.unwrap_err().is_from_js());
+        });
     }
 }
@@ -1,12 +1,13 @@
 use crate::{
-    convert::{IteratorJs, List},
+    convert::{IgnorePoison, IteratorJs, List},
     value::Constructor,
-    Array, Ctx, Error, IntoAtom, IntoJs, Object, Result, StdResult, StdString, String, Value,
+    Array, BigInt, Ctx, Error, IntoAtom, IntoJs, Object, Result, StdResult, StdString, String,
+    Value,
 };
 use std::{
     cell::{Cell, RefCell},
     collections::{BTreeMap, BTreeSet, HashMap, HashSet, LinkedList, VecDeque},
-    sync::{Mutex, RwLock},
+    sync::{Mutex, PoisonError, RwLock},
     time::SystemTime,
 };
 
@@ -57,6 +58,26 @@ where
     }
 }
 
+impl<'js, T, const N: usize> IntoJs<'js> for [T; N]
+where
+    T: IntoJs<'js>,
+{
+    fn into_js(self, ctx: &Ctx<'js>) -> Result<Value<'js>> {
+        self.into_iter()
+            .collect_js(ctx)
+            .map(|Array(value)| value.into_value())
+    }
+}
+
+impl<'js, T, const N: usize> IntoJs<'js> for &[T; N]
+where
+    for<'a> &'a T: IntoJs<'js>,
+{
+    fn into_js(self, ctx: &Ctx<'js>) -> Result<Value<'js>> {
+        self[..].into_js(ctx)
+    }
+}
+
 impl<'js> IntoJs<'js> for () {
     fn into_js(self, ctx: &Ctx<'js>) -> Result<Value<'js>> {
         Ok(Value::new_undefined(ctx.clone()))
@@ -69,6 +90,7 @@ impl<'js> IntoJs<'js> for &() {
     }
 }
 
+/// Convert the option into JS, `None` is converted to `undefined`
 impl<'js, T> IntoJs<'js> for Option<T>
 where
     T: IntoJs<'js>,
@@ -93,6 +115,11 @@ where
     }
 }
 
+/// Convert the result into JS
+///
+/// An `Err` becomes the error of the conversion. When the result is returned from a Rust function
+/// called by JS the error is thrown, so returning
+/// [`Error::new_type_error`](crate::Error::new_type_error) for example throws a `TypeError`.
 impl<'js, T, E> IntoJs<'js> for StdResult<T, E>
 where
     T: IntoJs<'js>,
@@ -184,13 +211,17 @@ where
     }
 }
 
+/// Returns the error for converting a poisoned lock.
+fn poisoned<T>(_: PoisonError<T>) -> Error {
+    Error::new_into_js_message("lock", "value", "The lock was poisoned")
+}
+
 impl<'js, T> IntoJs<'js> for Mutex<T>
 where
     T: IntoJs<'js>,
 {
     fn into_js(self, ctx: &Ctx<'js>) -> Result<Value<'js>> {
-        // TODO: determine we should panic her or just unpack the value.
-        self.into_inner().expect("mutex was poisoned").into_js(ctx)
+        self.into_inner().map_err(poisoned)?.into_js(ctx)
     }
 }
 
@@ -199,8 +230,7 @@ where
     for<'r> &'r T: IntoJs<'js>,
 {
     fn into_js(self, ctx: &Ctx<'js>) -> Result<Value<'js>> {
-        // TODO: determine we should panic her or just unpack the value.
-        self.lock().expect("mutex was poisoned").into_js(ctx)
+        self.lock().map_err(poisoned)?.into_js(ctx)
     }
 }
 
@@ -209,8 +239,7 @@ where
     T: IntoJs<'js>,
 {
     fn into_js(self, ctx: &Ctx<'js>) -> Result<Value<'js>> {
-        // TODO: determine we should panic her or just unpack the value.
-        self.into_inner().expect("lock was poisoned").into_js(ctx)
+        self.into_inner().map_err(poisoned)?.into_js(ctx)
     }
 }
 
@@ -219,8 +248,55 @@ where
     for<'r> &'r T: IntoJs<'js>,
 {
     fn into_js(self, ctx: &Ctx<'js>) -> Result<Value<'js>> {
-        // TODO: determine we should panic her or just unpack the value.
-        self.read().expect("lock was poisoned").into_js(ctx)
+        self.read().map_err(poisoned)?.into_js(ctx)
+    }
+}
+
+impl<'js, T> IntoJs<'js> for IgnorePoison<Mutex<T>>
+where
+    T: IntoJs<'js>,
+{
+    fn into_js(self, ctx: &Ctx<'js>) -> Result<Value<'js>> {
+        self.0
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+            .into_js(ctx)
+    }
+}
+
+impl<'js, T> IntoJs<'js> for IgnorePoison<&Mutex<T>>
+where
+    for<'r> &'r T: IntoJs<'js>,
+{
+    fn into_js(self, ctx: &Ctx<'js>) -> Result<Value<'js>> {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .into_js(ctx)
+    }
+}
+
+impl<'js, T> IntoJs<'js> for IgnorePoison<RwLock<T>>
+where
+    T: IntoJs<'js>,
+{
+    fn into_js(self, ctx: &Ctx<'js>) -> Result<Value<'js>> {
+        self.0
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+            .into_js(ctx)
+    }
+}
+
+impl<'js, T> IntoJs<'js> for IgnorePoison<&RwLock<T>>
+where
+    for<'r> &'r T: IntoJs<'js>,
+{
+    fn into_js(self, ctx: &Ctx<'js>) -> Result<Value<'js>> {
+        self.0
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .into_js(ctx)
     }
 }
 
@@ -447,6 +523,47 @@ into_js_impls! {
     i32 f64 => i64 u32 u64 usize isize,
 }
 
+// Numbers can't represent the 128 bit integers without losing precision, so they are always
+// converted to big ints
+macro_rules! wide_int_into_js_impls {
+    ($($type:ident $new:ident,)*) => {
+        $(
+            impl<'js> IntoJs<'js> for $type {
+                fn into_js(self, ctx: &Ctx<'js>) -> Result<Value<'js>> {
+                    BigInt::$new(ctx.clone(), self).map(BigInt::into_value)
+                }
+            }
+
+            impl<'js> IntoJs<'js> for &$type {
+                fn into_js(self, ctx: &Ctx<'js>) -> Result<Value<'js>> {
+                    (*self).into_js(ctx)
+                }
+            }
+        )*
+    };
+}
+
+wide_int_into_js_impls! {
+    i128 from_i128,
+    u128 from_u128,
+}
+
+#[cfg(feature = "num-bigint")]
+#[cfg_attr(feature = "doc-cfg", doc(cfg(feature = "num-bigint")))]
+impl<'js> IntoJs<'js> for num_bigint::BigInt {
+    fn into_js(self, ctx: &Ctx<'js>) -> Result<Value<'js>> {
+        (&self).into_js(ctx)
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+#[cfg_attr(feature = "doc-cfg", doc(cfg(feature = "num-bigint")))]
+impl<'js> IntoJs<'js> for &num_bigint::BigInt {
+    fn into_js(self, ctx: &Ctx<'js>) -> Result<Value<'js>> {
+        BigInt::from_num_bigint(ctx.clone(), self).map(BigInt::into_value)
+    }
+}
+
 fn millis_to_date<'js>(ctx: &Ctx<'js>, millis: i64) -> Result<Value<'js>> {
     let date_ctor: Constructor = ctx.globals().get("Date")?;
 
@@ -499,6 +616,37 @@ impl<'js, Tz: chrono::TimeZone> IntoJs<'js> for chrono::DateTime<Tz> {
 
 #[cfg(test)]
 mod test {
+    #[test]
+    fn option_and_result() {
+        use crate::{Context, Error, Function, Result, Runtime};
+
+        let runtime = Runtime::new().unwrap();
+        let ctx = Context::full(&runtime).unwrap();
+
+        ctx.with(|ctx| {
+            let is_undefined: Function = ctx.eval("(v) => v === undefined").unwrap();
+            assert!(is_undefined.call::<_, bool>((None::<i32>,)).unwrap());
+            assert!(!is_undefined.call::<_, bool>((Some(0),)).unwrap());
+
+            assert_eq!(ctx.eval::<Option<i32>, _>("null").unwrap(), None);
+            assert_eq!(ctx.eval::<Option<i32>, _>("undefined").unwrap(), None);
+            assert_eq!(ctx.eval::<Option<i32>, _>("1").unwrap(), Some(1));
+            assert!(ctx.eval::<Option<i32>, _>("'1'").is_err());
+
+            let parse = Function::new(ctx.clone(), |text: std::string::String| -> Result<i32> {
+                text.parse()
+                    .map_err(|_| Error::new_type_error("not a number"))
+            })
+            .unwrap();
+            ctx.globals().set("parse", parse).unwrap();
+            assert_eq!(ctx.eval::<i32, _>("parse('12')").unwrap(), 12);
+            let res: std::string::String = ctx
+                .eval("try { parse('x') } catch (e) { `${e.name}: ${e.message}` }")
+                .unwrap();
+            assert_eq!(res, "TypeError: not a number");
+        });
+    }
+
     #[test]
     fn system_time_to_js() {
         use crate::{Context, IntoJs, Runtime};
@@ -530,7 +678,7 @@ mod test {
             let globs = ctx.globals();
             globs.set("ts", ts.into_js(&ctx).unwrap()).unwrap();
             let res: i64 = ctx.eval("ts.getTime()").unwrap();
-            assert_eq!(-(millis as i64), res as _);
+            assert_eq!(-(millis as i64), res);
         });
     }
 
@@ -0,0 +1,210 @@
+use crate::{
+    convert::FromIteratorJs, Array, Ctx, Error, FromJs, IntoJs, Object, Result, StdString, Type,
+    Value,
+};
+use serde_json::{Map, Number, Value as JsonValue};
+
+/// The maximum nesting depth of arrays and objects converted to JSON values
+const MAX_DEPTH: usize = 256;
+
+/// Convert from a JS value to a JSON value
+///
+/// `undefined` is converted to `null` and numbers without a fractional part to integers. Like with
+/// `JSON.stringify` numbers which are not finite are converted to `null`. Values which have no
+/// JSON representation, like functions and symbols, as well as cyclic values and values nested
+/// deeper than 256 levels result in an error.
+impl<'js> FromJs<'js> for JsonValue {
+    fn from_js(_: &Ctx<'js>, value: Value<'js>) -> Result<Self> {
+        to_json(value, &mut Vec::new())
+    }
+}
+
+/// Convert a value, `parents` contains the arrays and objects which contain the value.
+fn to_json<'js>(value: Value<'js>, parents: &mut Vec<Object<'js>>) -> Result<JsonValue> {
+    if let Some(object) = value.as_object() {
+        if parents.contains(object) {
+            return Err(Error::new_from_js_message(
+                value.type_name(),
+                "JSON value",
+                "Converting circular structure to JSON",
+            ));
+        }
+        if parents.len() >= MAX_DEPTH {
+            return Err(Error::new_from_js_message(
+                value.type_name(),
+                "JSON value",
+                "Maximum nesting depth exceeded",
+            ));
+        }
+    }
+    Ok(match value.type_of() {
+        Type::Uninitialized | Type::Undefined | Type::Null => JsonValue::Null,
+        Type::Bool => JsonValue::Bool(value.as_bool().unwrap()),
+        Type::Int => JsonValue::Number(value.as_int().unwrap().into()),
+        Type::Float => {
+            let value = value.as_float().unwrap();
+            if value.fract() == 0.0 && value.abs() < i64::MAX as f64 {
+                JsonValue::Number((value as i64).into())
+            } else {
+                Number::from_f64(value).map_or(JsonValue::Null, JsonValue::Number)
+            }
+        }
+        Type::String => JsonValue::String(value.get::<StdString>()?),
+        Type::BigInt => {
+            // `to_i64` wraps like `BigInt.asIntN`, so the range is checked on the decimal
+            let big_int = value.into_big_int().unwrap();
+            if let Ok(value) = big_int.clone().parse_decimal::<i64>("i64") {
+                JsonValue::Number(value.into())
+            } else if let Ok(value) = big_int.parse_decimal::<u64>("u64") {
+                JsonValue::Number(value.into())
+            } else {
+                return Err(Error::new_from_js("bigint", "json number"));
+            }
+        }
+        Type::Array => {
+            let array = value.into_array().unwrap();
+            parents.push(array.clone().into_object());
+            let values = array
+                .iter()
+                .map(|value| to_json(value?, parents))
+                .collect::<Result<_>>();
+            parents.pop();
+            JsonValue::Array(values?)
+        }
+        Type::Object | Type::Exception => {
+            let object = value.into_object().unwrap();
+            parents.push(object.clone());
+            let props = object
+                .props::<StdString, Value>()
+                .map(|prop| {
+                    let (key, value) = prop?;
+                    Ok((key, to_json(value, parents)?))
+                })
+                .collect::<Result<Map<_, _>>>();
+            parents.pop();
+            JsonValue::Object(props?)
+        }
+        type_ => return Err(Error::new_from_js(type_.as_str(), "JSON value")),
+    })
+}
+
+/// Convert from a JSON value to a JS value
+///
+/// Like with `JSON.parse` the entries of objects are defined as own properties, so a `__proto__`
+/// key creates a property instead of changing the prototype.
+impl<'js> IntoJs<'js> for &JsonValue {
+    fn into_js(self, ctx: &Ctx<'js>) -> Result<Value<'js>> {
+        Ok(match self {
+            JsonValue::Null => Value::new_null(ctx.clone()),
+            JsonValue::Bool(value) => Value::new_bool(ctx.clone(), *value),
+            JsonValue::Number(number) => {
+                if let Some(value) = number.as_i64() {
+                    value.into_js(ctx)?
+                } else if let Some(value) = number.as_u64() {
+                    value.into_js(ctx)?
+                } else {
+                    Value::new_float(ctx.clone(), number.as_f64().unwrap_or(f64::NAN))
+                }
+            }
+            JsonValue::String(value) => value.into_js(ctx)?,
+            JsonValue::Array(values) => Array::from_iter_js(ctx, values)?.into_value(),
+            JsonValue::Object(props) => {
+                let props = props.iter().map(|(key, value)| (key.as_str(), value));
+                Object::from_iter_js(ctx, props)?.into_value()
+            }
+        })
+    }
+}
+
+/// Convert from a JSON value to a JS value
+impl<'js> IntoJs<'js> for JsonValue {
+    fn into_js(self, ctx: &Ctx<'js>) -> Result<Value<'js>> {
+        (&self).into_js(ctx)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{test_with, Function, IntoJs, Object};
+    use serde_json::{json, Value as JsonValue};
+
+    #[test]
+    fn json_round_trip() {
+        test_with(|ctx| {
+            let json = json!({
+                "name": "test",
+                "values": [1, -2.5, 3_000_000_000u64, true, null],
+                "nested": { "empty": [] },
+            });
+            let check: Function = ctx
+                .eval(
+                    r#"(v) => v.name === "test" && v.values[1] === -2.5 && v.values[2] === 3e9
+                        && v.values[4] === null && Array.isArray(v.nested.empty)"#,
+                )
+                .unwrap();
+            assert!(check.call::<_, bool>((&json,)).unwrap());
+
+            let copy: Function = ctx.eval("(v) => JSON.parse(JSON.stringify(v))").unwrap();
+            let back: JsonValue = copy.call((json.clone(),)).unwrap();
+            assert_eq!(back, json);
+        })
+    }
+
+    #[test]
+    fn from_script() {
+        test_with(|ctx| {
+            let value: JsonValue = ctx
+                .eval("({ a: undefined, b: 2.0, c: NaN, d: 10n, e: [0.5] })")
+                .unwrap();
+            assert_eq!(
+                value,
+                json!({ "a": null, "b": 2, "c": null, "d": 10, "e": [0.5] })
+            );
+            assert!(ctx.eval::<JsonValue, _>("({ f() {} }).f").is_err());
+            assert!(ctx.eval::<JsonValue, _>("[Symbol()]").is_err());
+
+            let value: JsonValue = ctx
+                .eval("[2n ** 63n, 2n ** 64n - 1n, -(2n ** 63n)]")
+                .unwrap();
+            assert_eq!(value, json!([1u64 << 63, u64::MAX, i64::MIN]));
+            assert!(ctx.eval::<JsonValue, _>("2n ** 64n").is_err());
+            assert!(ctx.eval::<JsonValue, _>("2n ** 70n").is_err());
+            assert!(ctx.eval::<JsonValue, _>("-(2n ** 63n) - 1n").is_err());
+        })
+    }
+
+    #[test]
+    fn cyclic_and_deep_values() {
+        test_with(|ctx| {
+            let err = ctx
+                .eval::<JsonValue, _>("const a = {}; a.a = a; a")
+                .unwrap_err();
+            assert!(err.to_string().contains("circular"));
+            assert!(ctx
+                .eval::<JsonValue, _>("const b = [1]; b.push([b]); b")
+                .is_err());
+            let err = ctx
+                .eval::<JsonValue, _>("let c = []; for (let i = 0; i < 100000; i++) c = [c]; c")
+                .unwrap_err();
+            assert!(err.to_string().contains("depth"));
+
+            // The same object may occur more than once as long as it doesn't contain itself.
+            let value: JsonValue = ctx.eval("const d = { x: 1 }; [d, d]").unwrap();
+            assert_eq!(value, json!([{ "x": 1 }, { "x": 1 }]));
+        })
+    }
+
+    #[test]
+    fn proto_key() {
+        test_with(|ctx| {
+            let json = json!({ "__proto__": { "polluted": true } });
+            let object: Object = (&json).into_js(&ctx).unwrap().into_object().unwrap();
+            assert!(object.has_own("__proto__").unwrap());
+            assert!(!object.contains_key("polluted").unwrap());
+            let check: Function = ctx
+                .eval("(v) => Object.getPrototypeOf(v) === Object.prototype")
+                .unwrap();
+            assert!(check.call::<_, bool>((object,)).unwrap());
+        })
+    }
+}
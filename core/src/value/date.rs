@@ -0,0 +1,133 @@
+//! JavaScript date types.
+
+use crate::{atom::PredefinedAtom, value::Constructor, Ctx, IntoJs, Object, Result};
+use std::time::SystemTime;
+
+/// Rust representation of a JavaScript `Date` object.
+///
+/// Dates convert from and to [`SystemTime`] and, with the `chrono` feature, the
+/// `chrono::DateTime` types. Those types can also be converted directly with [`FromJs`] and
+/// [`IntoJs`], this type is useful to keep the object itself, for example to pass it back to
+/// scripts unchanged.
+#[derive(Debug, PartialEq, Clone, Eq, Hash)]
+#[repr(transparent)]
+pub struct Date<'js>(pub(crate) Object<'js>);
+
+impl<'js> Date<'js> {
+    /// Create a date for the current time
+    pub fn now(ctx: Ctx<'js>) -> Result<Self> {
+        let ctor: Constructor = ctx.globals().get(PredefinedAtom::Date)?;
+        ctor.construct(())
+    }
+
+    /// Create a date from the milliseconds since the unix epoch
+    ///
+    /// Values which are not finite or too large result in an invalid date.
+    pub fn from_millis(ctx: Ctx<'js>, millis: f64) -> Result<Self> {
+        let ctor: Constructor = ctx.globals().get(PredefinedAtom::Date)?;
+        ctor.construct((millis,))
+    }
+
+    /// Create a date from a system time
+    pub fn from_system_time(ctx: Ctx<'js>, time: SystemTime) -> Result<Self> {
+        time.into_js(&ctx)?.get()
+    }
+
+    /// Create a date from a `chrono` date time
+    #[cfg(feature = "chrono")]
+    #[cfg_attr(feature = "doc-cfg", doc(cfg(feature = "chrono")))]
+    pub fn from_chrono<Tz: chrono::TimeZone>(
+        ctx: Ctx<'js>,
+        time: chrono::DateTime<Tz>,
+    ) -> Result<Self> {
+        time.into_js(&ctx)?.get()
+    }
+
+    /// Get the milliseconds since the unix epoch
+    ///
+    /// Returns an error if the date is invalid.
+    pub fn millis(&self) -> Result<f64> {
+        self.0.as_date_millis()
+    }
+
+    /// Returns whether the date has a valid time value
+    pub fn is_valid(&self) -> bool {
+        self.millis().is_ok()
+    }
+
+    /// Convert to a system time
+    pub fn to_system_time(&self) -> Result<SystemTime> {
+        self.as_value().get()
+    }
+
+    /// Convert to a `chrono` date time in UTC
+    #[cfg(feature = "chrono")]
+    #[cfg_attr(feature = "doc-cfg", doc(cfg(feature = "chrono")))]
+    pub fn to_chrono(&self) -> Result<chrono::DateTime<chrono::Utc>> {
+        self.as_value().get()
+    }
+
+    /// Reference as an object
+    #[inline]
+    pub fn as_object(&self) -> &Object<'js> {
+        &self.0
+    }
+
+    /// Convert into an object
+    #[inline]
+    pub fn into_object(self) -> Object<'js> {
+        self.0
+    }
+
+    /// Convert from an object if it is a date
+    pub fn from_object(object: Object<'js>) -> Option<Self> {
+        object.is_date().then_some(Self(object))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn system_time_round_trip() {
+        test_with(|ctx| {
+            let time = SystemTime::UNIX_EPOCH + Duration::from_millis(1_600_000_000_123);
+            let date = Date::from_system_time(ctx.clone(), time).unwrap();
+            assert_eq!(date.millis().unwrap(), 1_600_000_000_123.0);
+            ctx.globals().set("date", date).unwrap();
+            let year: i32 = ctx.eval("date.getUTCFullYear()").unwrap();
+            assert_eq!(year, 2020);
+
+            let date: Date = ctx.eval("new Date(date.getTime() + 1000)").unwrap();
+            assert_eq!(
+                date.to_system_time().unwrap(),
+                time + Duration::from_secs(1)
+            );
+
+            let invalid = Date::from_millis(ctx.clone(), f64::NAN).unwrap();
+            assert!(!invalid.is_valid());
+            assert!(invalid.to_system_time().is_err());
+            assert!(Date::now(ctx.clone()).unwrap().is_valid());
+            assert!(ctx.eval::<Date, _>("Date.now()").is_err());
+
+            let value: Value = ctx.eval("new Date(0)").unwrap();
+            assert_eq!(value.as_date().unwrap().millis().unwrap(), 0.0);
+            assert!(Value::new_int(ctx.clone(), 0).into_date().is_none());
+        })
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_round_trip() {
+        use chrono::{TimeZone, Utc};
+
+        test_with(|ctx| {
+            let time = Utc.timestamp_millis_opt(-86_400_000).unwrap();
+            let date = Date::from_chrono(ctx.clone(), time).unwrap();
+            assert_eq!(date.millis().unwrap(), -86_400_000.0);
+            assert_eq!(date.to_chrono().unwrap(), time);
+        })
+    }
+}
@@ -1,6 +1,13 @@
-use std::{error::Error as ErrorTrait, ffi::CStr, fmt, usize};
+use std::{error::Error as ErrorTrait, ffi::CStr, fmt, sync::Arc, usize};
 
-use crate::{atom::PredefinedAtom, convert::Coerced, qjs, Ctx, Error, Object, Result, Value};
+use crate::{
+    atom::PredefinedAtom,
+    class::{Class, ClassId, JsClass, Readable, Trace, Tracer},
+    convert::Coerced,
+    qjs,
+    value::Constructor,
+    Atom, Ctx, Error, ErrorKind, Object, Result, Value,
+};
 
 /// A JavaScript instance of Error
 ///
@@ -69,6 +76,14 @@ impl<'js> Exception<'js> {
         Ok(Exception(obj))
     }
 
+    /// Creates a new instance of a builtin error class with a given message.
+    ///
+    /// The exception isn't thrown, use [`Exception::throw`] or [`Error::new_throw`] for that.
+    pub fn from_kind(ctx: Ctx<'js>, kind: ErrorKind, message: &str) -> Result<Self> {
+        let ctor: Constructor = ctx.globals().get(kind.name())?;
+        ctor.construct((message,))
+    }
+
     /// Creates a new exception with a given message, file name and line number.
     pub fn from_message_location(
         ctx: Ctx<'js>,
@@ -98,6 +113,39 @@ impl<'js> Exception<'js> {
             .map(|x| x.0)
     }
 
+    /// Returns the name of the error class, like `TypeError`.
+    ///
+    /// Same as retrieving `error.name` in JavaScript, so subclasses defined by scripts return the
+    /// name they set.
+    pub fn name(&self) -> Option<String> {
+        self.get::<_, Option<Coerced<String>>>(PredefinedAtom::Name)
+            .ok()
+            .and_then(|x| x)
+            .map(|x| x.0)
+    }
+
+    /// Returns the builtin error class the error is an instance of.
+    ///
+    /// Returns `None` for instances of `Error` itself and of subclasses which don't derive from
+    /// one of the classes of [`ErrorKind`].
+    pub fn kind(&self) -> Option<ErrorKind> {
+        let globals = self.0.ctx.globals();
+        [
+            ErrorKind::Type,
+            ErrorKind::Range,
+            ErrorKind::Syntax,
+            ErrorKind::Reference,
+            ErrorKind::Internal,
+        ]
+        .into_iter()
+        .find(|kind| {
+            globals
+                .get::<_, Object>(kind.name())
+                .map(|class| self.0.is_instance_of(class))
+                .unwrap_or(false)
+        })
+    }
+
     /// Returns the file name from with the error originated..
     ///
     /// Same as retrieving `error.fileName` in JavaScript.
@@ -264,6 +312,14 @@ impl<'js> Exception<'js> {
         Error::Exception
     }
 
+    /// Returns the Rust error of an exception which was thrown for an [`Error::Custom`].
+    pub fn custom_error(&self) -> Option<Arc<dyn ErrorTrait + Send + Sync + 'static>> {
+        let key = custom_error_key(&self.0.ctx).ok()?;
+        let holder: Class<CustomError> = self.0.get(key).ok()?;
+        let error = holder.borrow().0.clone();
+        Some(error)
+    }
+
     /// Sets the exception as the current error an returns `Error::Exception`
     pub fn throw(self) -> Error {
         let ctx = self.ctx().clone();
@@ -271,8 +327,97 @@ impl<'js> Exception<'js> {
     }
 }
 
+/// Returns the key of the property which keeps the Rust error of an exception thrown for
+/// [`Error::Custom`].
+///
+/// The key is a private symbol of the runtime, so scripts can neither read nor forge the property.
+fn custom_error_key<'js>(ctx: &Ctx<'js>) -> Result<Atom<'js>> {
+    let description = cstr!("customError");
+    unsafe {
+        let opaque = &mut *ctx.get_opaque();
+        if let Some(atom) = opaque.custom_error_key {
+            return Ok(Atom::from_atom_val_dup(ctx.clone(), atom));
+        }
+        let symbol = qjs::JS_NewPrivateSymbol(ctx.as_ptr(), description.as_ptr());
+        let symbol = ctx.handle_exception(symbol)?;
+        let atom = qjs::JS_ValueToAtom(ctx.as_ptr(), symbol);
+        qjs::JS_FreeValue(ctx.as_ptr(), symbol);
+        if atom == qjs::JS_ATOM_NULL {
+            return Err(ctx.raise_exception());
+        }
+        opaque.custom_error_key = Some(atom);
+        Ok(Atom::from_atom_val_dup(ctx.clone(), atom))
+    }
+}
+
+/// The object which keeps the Rust error of an exception.
+struct CustomError(Arc<dyn ErrorTrait + Send + Sync + 'static>);
+
+impl<'js> Trace<'js> for CustomError {
+    fn trace<'a>(&self, _tracer: Tracer<'a, 'js>) {}
+}
+
+impl<'js> JsClass<'js> for CustomError {
+    const NAME: &'static str = "CustomError";
+
+    type Mutable = Readable;
+
+    fn class_id() -> &'static ClassId {
+        static ID: ClassId = ClassId::new();
+        &ID
+    }
+
+    fn prototype(_ctx: &Ctx<'js>) -> Result<Option<Object<'js>>> {
+        Ok(None)
+    }
+
+    fn constructor(_ctx: &Ctx<'js>) -> Result<Option<Constructor<'js>>> {
+        Ok(None)
+    }
+}
+
+/// Throw an `Error` with the message of the Rust error which keeps the error.
+pub(crate) fn throw_custom(
+    ctx: &Ctx<'_>,
+    error: &Arc<dyn ErrorTrait + Send + Sync + 'static>,
+) -> qjs::JSValue {
+    let create = || -> Result<Exception> {
+        let exception = Exception::from_message(ctx.clone(), &error.to_string())?;
+        let holder = Class::instance(ctx.clone(), CustomError(error.clone()))?;
+        let key = custom_error_key(ctx)?;
+        // Not writable or configurable so the error can't be replaced.
+        let res = unsafe {
+            qjs::JS_DefinePropertyValue(
+                ctx.as_ptr(),
+                exception.as_js_value(),
+                key.atom,
+                holder.into_value().into_js_value(),
+                qjs::JS_PROP_THROW as _,
+            )
+        };
+        if res < 0 {
+            return Err(Error::Exception);
+        }
+        Ok(exception)
+    };
+    match create() {
+        Ok(exception) => {
+            exception.throw();
+            qjs::JS_EXCEPTION
+        }
+        // The exception of the failure is already pending.
+        Err(Error::Exception) => qjs::JS_EXCEPTION,
+        Err(e) => e.throw(ctx),
+    }
+}
+
 impl fmt::Display for Exception<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let opaque = unsafe { &*self.0.ctx.get_opaque() };
+        if let Some(formatter) = opaque.exception_formatter.as_ref() {
+            return formatter(self).fmt(f);
+        }
+
         "Error:".fmt(f)?;
         let mut has_file = false;
         if let Some(file) = self.file() {
@@ -43,6 +43,10 @@ pub struct Function<'js>(pub(crate) Object<'js>);
 
 impl<'js> Function<'js> {
     /// Create a new function from a Rust function which implements [`IntoJsFunc`].
+    ///
+    /// Closures are supported, the arguments are converted with [`FromParam`] and the return
+    /// value with [`IntoJs`]. The closure is owned by the JavaScript function object and dropped
+    /// when the function is freed by the garbage collector.
     pub fn new<P, F>(ctx: Ctx<'js>, f: F) -> Result<Self>
     where
         F: IntoJsFunc<'js, P> + 'js,
@@ -58,6 +62,10 @@ impl<'js> Function<'js> {
     }
 
     /// Call the function with given arguments.
+    ///
+    /// The `this` value of the call can be given with a [`This`] argument, so `func.call((This(obj),
+    /// 1, 2))` is the same as `func.call(obj, 1, 2)` in JavaScript. To invoke a function with
+    /// `new` convert it into a [`Constructor`] and use [`Constructor::construct`].
     pub fn call<A, R>(&self, args: A) -> Result<R>
     where
         A: IntoArgs<'js>,
@@ -387,6 +395,29 @@ mod test {
         assert_eq!(res[4], 2);
     }
 
+    #[test]
+    fn call_js_fn_with_dynamic_args() {
+        test_with(|ctx| {
+            let sum: Function = ctx
+                .eval("function sum() { let s = 0; for (const x of arguments) s += x; return [arguments.length, s] } sum")
+                .unwrap();
+
+            let args: Vec<i32> = (1..=4).collect();
+            let res: Vec<i32> = sum.call(args.as_slice()).unwrap();
+            assert_eq!(res, [4, 10]);
+
+            let res: Vec<i32> = sum.call(args).unwrap();
+            assert_eq!(res, [4, 10]);
+
+            let res: Vec<i32> = sum.call(Vec::<i32>::new()).unwrap();
+            assert_eq!(res, [0, 0]);
+
+            let max: Function = ctx.eval("Math.max").unwrap();
+            let res: i32 = max.call(vec![3, 9, 2]).unwrap();
+            assert_eq!(res, 9);
+        })
+    }
+
     #[test]
     fn call_js_fn_with_no_args_and_throw() {
         test_with(|ctx| {
@@ -433,6 +464,39 @@ mod test {
         })
     }
 
+    #[test]
+    fn construct_js_class() {
+        use crate::value::Constructor;
+
+        test_with(|ctx| {
+            let value: Value = ctx
+                .eval(
+                    r#"
+                    class Point {
+                        constructor(x, y) { this.x = x; this.y = y; }
+                        sum(offset) { return this.x + this.y + offset; }
+                    }
+                    Point
+                    "#,
+                )
+                .unwrap();
+            let func: Function = value.clone().get().unwrap();
+            assert!(func.is_constructor());
+            assert!(func.call::<_, Object>((1, 2)).is_err());
+            ctx.catch();
+
+            let ctor: Constructor = value.get().unwrap();
+            let point: Object = ctor.construct((1, 2)).unwrap();
+            assert_eq!(point.get::<_, i32>("x").unwrap(), 1);
+            let sum: Function = point.get("sum").unwrap();
+            let res: i32 = sum.call((This(point), 3)).unwrap();
+            assert_eq!(res, 6);
+
+            let arrow: Value = ctx.eval("() => {}").unwrap();
+            assert!(arrow.get::<Constructor>().is_err());
+        })
+    }
+
     #[test]
     fn call_js_fn_with_1_arg_deferred() {
         let rt = Runtime::new().unwrap();
@@ -578,6 +642,30 @@ mod test {
         })
     }
 
+    #[test]
+    fn closure_dropped_with_function() {
+        use std::sync::Arc;
+
+        let state = Arc::new(());
+        let rt = Runtime::new().unwrap();
+        let ctx = Context::full(&rt).unwrap();
+        ctx.with(|ctx| {
+            let captured = state.clone();
+            let f = Function::new(ctx.clone(), move |a: i32, b: i32| {
+                let _ = &captured;
+                a + b
+            })
+            .unwrap();
+            ctx.globals().set("add", f).unwrap();
+            let res: i32 = ctx.eval("add(1, 2)").unwrap();
+            assert_eq!(res, 3);
+            assert_eq!(Arc::strong_count(&state), 2);
+            ctx.globals().remove("add").unwrap();
+        });
+        rt.run_gc();
+        assert_eq!(Arc::strong_count(&state), 1);
+    }
+
     #[test]
     fn multiple_const_callbacks() {
         test_with(|ctx| {
@@ -738,6 +826,44 @@ mod test {
         assert_eq!(res, 11.0);
     }
 
+    #[test]
+    fn call_rust_fn_with_this_opt_and_rest_args() {
+        test_with(|ctx| {
+            let func = Function::new(
+                ctx.clone(),
+                |this: This<Object>, x: i32, y: Opt<i32>, rest: Rest<i32>| {
+                    let base: i32 = this.get("base").unwrap();
+                    let y = y.0.unwrap_or(10);
+                    base + x + y + rest.iter().sum::<i32>()
+                },
+            )
+            .unwrap();
+            ctx.globals().set("test_fn", func).unwrap();
+            let res: Vec<i32> = ctx
+                .eval(
+                    r#"
+                    const obj = { base: 100 };
+                    [
+                        test_fn.call(obj, 1),
+                        test_fn.call(obj, 1, 2),
+                        test_fn.call(obj, 1, undefined, 3, 4),
+                        test_fn.call(obj, 1, 2, 3, 4),
+                    ]
+                    "#,
+                )
+                .unwrap();
+            assert_eq!(res, [111, 103, 118, 110]);
+
+            let err = ctx
+                .eval::<i32, _>("test_fn.call({ base: 0 })")
+                .catch(&ctx)
+                .unwrap_err();
+            assert!(err
+                .to_string()
+                .contains("Error calling function with 0 argument(s)"));
+        })
+    }
+
     #[test]
     fn call_rust_fn_with_var_args() {
         let res: Vec<i8> = test_with(|ctx| {
@@ -306,6 +306,29 @@ impl<'js, T: IntoArgs<'js>> IntoArg<'js> for Flat<T> {
     }
 }
 
+impl<'js, T: IntoJs<'js>> IntoArgs<'js> for Vec<T> {
+    fn num_args(&self) -> usize {
+        self.len()
+    }
+
+    fn into_args(self, args: &mut Args<'js>) -> Result<()> {
+        args.push_args(self)
+    }
+}
+
+impl<'js, T> IntoArgs<'js> for &[T]
+where
+    for<'a> &'a T: IntoJs<'js>,
+{
+    fn num_args(&self) -> usize {
+        self.len()
+    }
+
+    fn into_args(self, args: &mut Args<'js>) -> Result<()> {
+        args.push_args(self)
+    }
+}
+
 macro_rules! impl_into_args {
     ($($t:ident),*) => {
         #[allow(non_snake_case)]
@@ -6,7 +6,10 @@ use crate::{
 };
 
 #[cfg(feature = "futures")]
-use crate::{function::types::Async, promise::Promised};
+use crate::{
+    function::types::Async,
+    promise::{rejected, Promised},
+};
 #[cfg(feature = "futures")]
 use std::future::Future;
 
@@ -48,7 +51,11 @@ macro_rules! impl_to_js_function {
             #[allow(non_snake_case)]
             fn call(&self, params: Params<'_, 'js>) -> Result<Value<'js>> {
                 let ctx = params.ctx().clone();
-                let ($($t,)*) = <($($t,)*)>::from_params(&mut params.access())?;
+                // Errors of an async function reject the returned promise instead of being thrown
+                let ($($t,)*) = match <($($t,)*)>::from_params(&mut params.access()) {
+                    Ok(args) => args,
+                    Err(error) => return rejected(&ctx, error),
+                };
                 let fut = (self.0)($($t),*);
                 Promised(fut).into_js(&ctx)
             }
@@ -92,8 +99,15 @@ macro_rules! impl_to_js_function {
             #[allow(non_snake_case)]
             fn call(&self, params: Params<'_, 'js>) -> Result<Value<'js>> {
                 let ctx = params.ctx().clone();
-                let ($($t,)*) = <($($t,)*)>::from_params(&mut params.access())?;
-                let mut lock = self.0.0.try_borrow_mut().map_err(|_| Error::FunctionBorrow(BorrowError::AlreadyBorrowed))?;
+                // Errors of an async function reject the returned promise instead of being thrown
+                let ($($t,)*) = match <($($t,)*)>::from_params(&mut params.access()) {
+                    Ok(args) => args,
+                    Err(error) => return rejected(&ctx, error),
+                };
+                let mut lock = match self.0.0.try_borrow_mut() {
+                    Ok(lock) => lock,
+                    Err(_) => return rejected(&ctx, Error::FunctionBorrow(BorrowError::AlreadyBorrowed)),
+                };
                 let fut = (lock)($($t),*);
                 Promised(fut).into_js(&ctx)
             }
@@ -136,8 +150,15 @@ macro_rules! impl_to_js_function {
             #[allow(non_snake_case)]
             fn call(&self, params: Params<'_, 'js>) -> Result<Value<'js>> {
                 let ctx = params.ctx().clone();
-                let ($($t,)*) = <($($t,)*)>::from_params(&mut params.access())?;
-                let lock = self.0.0.take().ok_or(Error::FunctionBorrow(BorrowError::AlreadyUsed))?;
+                // Errors of an async function reject the returned promise instead of being thrown
+                let ($($t,)*) = match <($($t,)*)>::from_params(&mut params.access()) {
+                    Ok(args) => args,
+                    Err(error) => return rejected(&ctx, error),
+                };
+                let lock = match self.0.0.take() {
+                    Some(lock) => lock,
+                    None => return rejected(&ctx, Error::FunctionBorrow(BorrowError::AlreadyUsed)),
+                };
                 let fut = (lock)($($t),*);
                 Promised(fut).into_js(&ctx)
             }
@@ -276,12 +276,14 @@ impl<'js, T: FromJs<'js>> FromParam<'js> for Opt<T> {
     }
 
     fn from_param<'a>(params: &mut ParamsAccessor<'a, 'js>) -> Result<Self> {
-        if !params.is_empty() {
-            let ctx = params.ctx().clone();
-            Ok(Opt(Some(T::from_js(&ctx, params.arg())?)))
-        } else {
-            Ok(Opt(None))
+        if params.is_empty() {
+            return Ok(Opt(None));
+        }
+        let arg = params.arg();
+        if arg.is_undefined() {
+            return Ok(Opt(None));
         }
+        T::from_js(params.ctx(), arg).map(|arg| Opt(Some(arg)))
     }
 }
 
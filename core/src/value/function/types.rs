@@ -46,6 +46,8 @@ pub struct This<T>(pub T);
 pub struct FuncArg<T>(pub T);
 
 /// Helper type for optional parameters.
+///
+/// Like default parameters in JavaScript an argument which is missing or `undefined` is `None`.
 pub struct Opt<T>(pub Option<T>);
 
 /// Helper type for rest and spread arguments.
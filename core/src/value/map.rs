@@ -0,0 +1,275 @@
+//! JavaScript map types.
+
+use crate::{
+    atom::PredefinedAtom,
+    convert::{FromIteratorJs, List},
+    function::This,
+    qjs,
+    value::Constructor,
+    Ctx, Error, FromJs, Function, IntoAtom, IntoJs, Object, Result, Symbol, Value,
+};
+use std::marker::PhantomData;
+
+/// Rust representation of a JavaScript `Map` object.
+///
+/// Unlike the properties of plain objects the keys of a map can be values of any type, which are
+/// compared by identity instead of being converted to strings. Maps are created from Rust
+/// iterators with [`IteratorJs::collect_js`](crate::IteratorJs::collect_js).
+#[derive(Debug, PartialEq, Clone, Eq, Hash)]
+#[repr(transparent)]
+pub struct Map<'js>(pub(crate) Object<'js>);
+
+impl<'js> Map<'js> {
+    /// Create a new empty map
+    pub fn new(ctx: Ctx<'js>) -> Result<Self> {
+        let ctor: Constructor = ctx.globals().get(PredefinedAtom::Map)?;
+        ctor.construct(())
+    }
+
+    /// Get the value of a key in the map, `undefined` if the key is missing
+    pub fn get<K: IntoJs<'js>, V: FromJs<'js>>(&self, key: K) -> Result<V> {
+        self.method(PredefinedAtom::Getter)?
+            .call((This(self.0.clone()), key))
+    }
+
+    /// Set the value of a key in the map
+    pub fn set<K: IntoJs<'js>, V: IntoJs<'js>>(&self, key: K, value: V) -> Result<()> {
+        self.method(PredefinedAtom::Setter)?
+            .call::<_, Value>((This(self.0.clone()), key, value))
+            .map(|_| ())
+    }
+
+    /// Returns whether the map contains the key
+    pub fn has<K: IntoJs<'js>>(&self, key: K) -> Result<bool> {
+        self.method(PredefinedAtom::Has)?
+            .call((This(self.0.clone()), key))
+    }
+
+    /// Remove a key from the map, returns whether the key was present
+    pub fn delete<K: IntoJs<'js>>(&self, key: K) -> Result<bool> {
+        self.method(PredefinedAtom::Delete)?
+            .call((This(self.0.clone()), key))
+    }
+
+    /// Remove all entries of the map
+    pub fn clear(&self) -> Result<()> {
+        self.method("clear")?.call((This(self.0.clone()),))
+    }
+
+    /// Get the number of entries in the map
+    pub fn size(&self) -> Result<usize> {
+        self.0.get("size")
+    }
+
+    /// Get an iterator over the entries of the map in insertion order
+    pub fn iter<K: FromJs<'js>, V: FromJs<'js>>(&self) -> MapIter<'js, K, V> {
+        MapIter {
+            iter: JsIter::new(self.0.clone()),
+            marker: PhantomData,
+        }
+    }
+
+    /// Reference to value
+    #[inline]
+    pub fn as_value(&self) -> &Value<'js> {
+        self.0.as_value()
+    }
+
+    /// Convert into value
+    #[inline]
+    pub fn into_value(self) -> Value<'js> {
+        self.0.into_value()
+    }
+
+    /// Reference as an object
+    #[inline]
+    pub fn as_object(&self) -> &Object<'js> {
+        &self.0
+    }
+
+    /// Convert into an object
+    #[inline]
+    pub fn into_object(self) -> Object<'js> {
+        self.0
+    }
+
+    /// Convert from an object if it is a map
+    pub fn from_object(object: Object<'js>) -> Option<Self> {
+        let is_map = unsafe { qjs::JS_IsMap(object.as_js_value()) } != 0;
+        is_map.then_some(Self(object))
+    }
+
+    fn method<K: IntoAtom<'js>>(&self, name: K) -> Result<Function<'js>> {
+        self.0.get(name)
+    }
+}
+
+/// Drives the iterator which is returned by the `Symbol.iterator` method of an object
+pub(crate) struct JsIter<'js> {
+    source: Object<'js>,
+    iter: Option<(Object<'js>, Function<'js>)>,
+    done: bool,
+}
+
+impl<'js> JsIter<'js> {
+    pub(crate) fn new(source: Object<'js>) -> Self {
+        Self {
+            source,
+            iter: None,
+            done: false,
+        }
+    }
+
+    fn step(&mut self) -> Result<Option<Value<'js>>> {
+        if self.iter.is_none() {
+            let method: Function = self
+                .source
+                .get(Symbol::iterator(self.source.ctx().clone()))?;
+            let iter: Object = method.call((This(self.source.clone()),))?;
+            let next: Function = iter.get(PredefinedAtom::Next)?;
+            self.iter = Some((iter, next));
+        }
+        let (iter, next) = self.iter.as_ref().expect("iterator should be started");
+        let result: Object = next.call((This(iter.clone()),))?;
+        if result.get(PredefinedAtom::Done)? {
+            return Ok(None);
+        }
+        result.get(PredefinedAtom::Value).map(Some)
+    }
+}
+
+impl<'js> Iterator for JsIter<'js> {
+    type Item = Result<Value<'js>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let res = self.step().transpose();
+        // An exception ends the iteration like in a `for..of` loop
+        self.done = !matches!(res, Some(Ok(_)));
+        res
+    }
+}
+
+/// The iterator for a map
+pub struct MapIter<'js, K, V> {
+    iter: JsIter<'js>,
+    marker: PhantomData<(K, V)>,
+}
+
+impl<'js, K, V> Iterator for MapIter<'js, K, V>
+where
+    K: FromJs<'js>,
+    V: FromJs<'js>,
+{
+    type Item = Result<(K, V)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = self.iter.next()?;
+        Some(entry.and_then(|entry| entry.get::<List<(K, V)>>().map(|List(entry)| entry)))
+    }
+}
+
+impl<'js> IntoIterator for Map<'js> {
+    type Item = Result<(Value<'js>, Value<'js>)>;
+    type IntoIter = MapIter<'js, Value<'js>, Value<'js>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'js, K, V> FromIteratorJs<'js, (K, V)> for Map<'js>
+where
+    K: IntoJs<'js>,
+    V: IntoJs<'js>,
+{
+    type Item = (Value<'js>, Value<'js>);
+
+    fn from_iter_js<T>(ctx: &Ctx<'js>, iter: T) -> Result<Self>
+    where
+        T: IntoIterator<Item = (K, V)>,
+    {
+        let map = Map::new(ctx.clone())?;
+        for (key, value) in iter {
+            map.set(key, value)?;
+        }
+        Ok(map)
+    }
+}
+
+impl<'js> FromJs<'js> for Map<'js> {
+    fn from_js(_: &Ctx<'js>, value: Value<'js>) -> Result<Self> {
+        let ty_name = value.type_name();
+        value
+            .into_object()
+            .and_then(Self::from_object)
+            .ok_or_else(|| Error::new_from_js(ty_name, "Map"))
+    }
+}
+
+impl<'js> IntoJs<'js> for Map<'js> {
+    fn into_js(self, _: &Ctx<'js>) -> Result<Value<'js>> {
+        Ok(self.0.into_value())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn map_methods() {
+        test_with(|ctx| {
+            let map = Map::new(ctx.clone()).unwrap();
+            let key = Object::new(ctx.clone()).unwrap();
+            map.set(key.clone(), "object").unwrap();
+            map.set(1, "number").unwrap();
+            map.set("1", "string").unwrap();
+            assert_eq!(map.size().unwrap(), 3);
+            assert_eq!(map.get::<_, StdString>(key.clone()).unwrap(), "object");
+            assert_eq!(map.get::<_, StdString>(1).unwrap(), "number");
+            assert!(map.get::<_, Option<StdString>>(2).unwrap().is_none());
+            assert!(map.has("1").unwrap());
+            assert!(map.delete("1").unwrap());
+            assert!(!map.delete("1").unwrap());
+
+            let values = map
+                .iter::<Value, StdString>()
+                .map(|entry| entry.map(|(_, value)| value))
+                .collect::<Result<Vec<_>>>()
+                .unwrap();
+            assert_eq!(values, ["object", "number"]);
+            map.clear().unwrap();
+            assert_eq!(map.size().unwrap(), 0);
+        })
+    }
+
+    #[test]
+    fn map_conversions() {
+        test_with(|ctx| {
+            let map: Map = ctx.eval("new Map([[1, 'a'], [2, 'b']])").unwrap();
+            let entries = map
+                .iter()
+                .collect::<Result<HashMap<i32, StdString>>>()
+                .unwrap();
+            assert_eq!(entries[&2], "b");
+            assert!(ctx.eval::<Map, _>("({})").is_err());
+
+            let map: Map = [(1.5, true)].into_iter().collect_js(&ctx).unwrap();
+            ctx.globals().set("map", map).unwrap();
+            let value: bool = ctx.eval("map instanceof Map && map.get(1.5)").unwrap();
+            assert!(value);
+
+            let map: HashMap<StdString, i32> = ctx.eval("new Map([['a', 1]])").unwrap();
+            assert_eq!(map["a"], 1);
+            let res = ctx.eval::<HashMap<StdString, i32>, _>("new Map([[{}, 1], [{}, 2]])");
+            assert!(res.is_err());
+
+            // Objects inheriting from `Map.prototype` are not maps
+            assert!(ctx.eval::<Map, _>("Object.create(Map.prototype)").is_err());
+        })
+    }
+}
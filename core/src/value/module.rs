@@ -13,7 +13,7 @@ use std::{
 #[cfg(feature = "exports")]
 use std::marker::PhantomData;
 
-use crate::{qjs, Atom, Context, Ctx, Error, FromAtom, FromJs, IntoJs, Result, Value};
+use crate::{qjs, Atom, Context, Ctx, Error, ErrorKind, FromAtom, FromJs, IntoJs, Result, Value};
 
 /// Helper macro to provide module init function.
 /// Use for exporting module definitions to be loaded as part of a dynamic library.
@@ -94,7 +94,8 @@ impl ModuleDataKind {
                 let ptr = NonNull::new(ptr).ok_or(Error::Unknown)?;
                 Ok(Module::from_module_def(ctx, ptr))
             }
-            ModuleDataKind::ByteCode(x) => Module::unsafe_declare_read_object(ctx, x.as_ref()),
+            ModuleDataKind::ByteCode(Cow::Borrowed(x)) => Module::read_object_raw(ctx, x, true),
+            ModuleDataKind::ByteCode(Cow::Owned(x)) => Module::read_object_raw(ctx, &x, false),
         }
     }
 }
@@ -167,6 +168,30 @@ impl ModuleData {
         }
     }
 
+    /// Replace the name under which the module will be declared.
+    pub(crate) fn with_name<N: Into<Vec<u8>>>(self, name: N) -> Self {
+        ModuleData {
+            name: name.into(),
+            data: self.data,
+        }
+    }
+
+    /// Replace the source of a module loaded from source.
+    #[cfg(feature = "loader")]
+    pub(crate) fn map_source<F>(self, f: F) -> Result<Self>
+    where
+        F: FnOnce(Vec<u8>) -> Result<Vec<u8>>,
+    {
+        let data = match self.data {
+            ModuleDataKind::Source(source) => ModuleDataKind::Source(f(source)?),
+            data => data,
+        };
+        Ok(ModuleData {
+            name: self.name,
+            data,
+        })
+    }
+
     /// Returns the kind of `ModuleData`.
     pub fn kind(&self) -> &ModuleDataKind {
         &self.data
@@ -381,13 +406,20 @@ impl<'js> Exports<'js> {
                 )
             };
 
-            // The below is wrong, if a value is applied but not previously defined the above
-            // function can error!
-            //
-            // WRONG: previous checks and the fact that we also previously added the export should ensure
-            // that the only error can be an allocation error.
+            // QuickJS fails to set an export if it wasn't declared, without raising an exception,
+            // or if it can't allocate the atom of the name, with a pending exception.
             if res < 0 {
-                return Err(Error::Unknown);
+                let exception = self.ctx.catch();
+                if !exception.is_null() {
+                    return Err(self.ctx.throw(exception));
+                }
+                return Err(Error::new_throw(
+                    ErrorKind::Reference,
+                    format!(
+                        "export '{}' was not declared by the module",
+                        name.to_string_lossy()
+                    ),
+                ));
             }
         }
         Ok(())
@@ -469,6 +501,9 @@ impl<'js> Module<'js> {
     /// Creates a new module from JS source, and evaluates it.
     ///
     /// It is unsafe to hold onto unevaluated modules across this call.
+    ///
+    /// Module evaluation is synchronous. Top-level `await` is not supported, the bundled QuickJS
+    /// version can't parse it and throws a `SyntaxError`.
     pub fn evaluate<N, S>(ctx: Ctx<'js>, name: N, source: S) -> Result<Module<'js>>
     where
         N: Into<Vec<u8>>,
@@ -632,13 +667,20 @@ impl<'js> Module<'js> {
     /// It is unsound to hold onto an unevaluated module across any call to this function which
     /// returns an error.
     pub unsafe fn unsafe_declare_read_object(ctx: Ctx<'js>, bytes: &[u8]) -> Result<Module<'js>> {
+        Self::read_object_raw(ctx, bytes, false)
+    }
+
+    /// Read module bytecode.
+    ///
+    /// With `rom_data` QuickJS doesn't copy the bytecode but references it, so the bytes need to
+    /// live as long as the runtime.
+    unsafe fn read_object_raw(ctx: Ctx<'js>, bytes: &[u8], rom_data: bool) -> Result<Module<'js>> {
+        let mut flags = qjs::JS_READ_OBJ_BYTECODE;
+        if rom_data {
+            flags |= qjs::JS_READ_OBJ_ROM_DATA;
+        }
         let module = unsafe {
-            qjs::JS_ReadObject(
-                ctx.as_ptr(),
-                bytes.as_ptr(),
-                bytes.len() as _,
-                (qjs::JS_READ_OBJ_BYTECODE | qjs::JS_READ_OBJ_ROM_DATA) as i32,
-            )
+            qjs::JS_ReadObject(ctx.as_ptr(), bytes.as_ptr(), bytes.len() as _, flags as i32)
         };
         let module = ctx.handle_exception(module)?;
         debug_assert_eq!(qjs::JS_TAG_MODULE, unsafe { qjs::JS_VALUE_GET_TAG(module) });
@@ -750,8 +792,10 @@ impl<'js> Module<'js> {
         unsafe {
             let value = qjs::JS_MKPTR(qjs::JS_TAG_MODULE, self.module.as_ptr().cast());
             // JS_EvalFunction `free's` the module so we should dup first
-            let ret = qjs::JS_EvalFunction(self.ctx.as_ptr(), qjs::JS_DupValue(value));
-            self.ctx.handle_exception(ret)?;
+            self.ctx.evaluate(|| {
+                let ret = qjs::JS_EvalFunction(self.ctx.as_ptr(), qjs::JS_DupValue(value));
+                self.ctx.handle_exception(ret)
+            })?;
         }
         Ok(())
     }
@@ -781,6 +825,10 @@ impl<'js> Module<'js> {
 #[cfg(feature = "exports")]
 impl<'js> Module<'js> {
     /// Return exported value by name
+    ///
+    /// Names which are not exported are returned as `undefined`, as are all names before the
+    /// module is linked. Names which are re-exported from another module with
+    /// `export { name } from "other"` are resolved through the namespace of the module.
     pub fn get<N, T>(&self, name: N) -> Result<T>
     where
         N: AsRef<str>,
@@ -907,10 +955,13 @@ where
         };
         let value = unsafe {
             let js_val = qjs::JS_GetModuleExportEntry(ctx.as_ptr(), ptr, self.index);
-            Value::from_js_value(ctx.clone(), js_val)
+            ctx.handle_exception(js_val)
+                .map(|js_val| Value::from_js_value(ctx.clone(), js_val))
         };
         self.index += 1;
-        Some(N::from_atom(name).and_then(|name| T::from_js(ctx, value).map(|value| (name, value))))
+        Some(N::from_atom(name).and_then(|name| {
+            value.and_then(|value| T::from_js(ctx, value).map(|value| (name, value)))
+        }))
     }
 }
 
@@ -947,6 +998,37 @@ mod test {
         }
     }
 
+    pub struct UndeclaredExportModule;
+
+    impl ModuleDef for UndeclaredExportModule {
+        fn evaluate<'js>(_ctx: &Ctx<'js>, exports: &mut Exports<'js>) -> Result<()> {
+            exports.export("missing", 1)?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn import_undeclared_export() {
+        let runtime = Runtime::new().unwrap();
+        let ctx = Context::full(&runtime).unwrap();
+        ctx.with(|ctx| {
+            Module::declare_def::<UndeclaredExportModule, _>(ctx.clone(), "undeclared_mod")
+                .unwrap();
+            let err = Module::import::<Value, _>(&ctx, "undeclared_mod")
+                .catch(&ctx)
+                .unwrap_err();
+            let CaughtError::Exception(err) = err else {
+                panic!("expected an exception, got {err}");
+            };
+            assert_eq!(
+                err.message().as_deref(),
+                Some("export 'missing' was not declared by the module")
+            );
+            let name: StdString = err.as_object().get("name").unwrap();
+            assert_eq!(name, "ReferenceError");
+        });
+    }
+
     #[test]
     fn from_rust_def() {
         test_with(|ctx| {
@@ -1093,4 +1175,82 @@ mod test {
             }
         });
     }
+
+    #[cfg(feature = "exports")]
+    #[test]
+    fn reexported_names() {
+        test_with(|ctx| {
+            Module::declare_def::<RustModule, _>(ctx.clone(), "rust_mod").unwrap();
+            let module: Module = ctx
+                .clone()
+                .compile(
+                    "reexport",
+                    r#"
+                export { hello } from "rust_mod";
+                export const own = 1;
+                "#,
+                )
+                .unwrap();
+
+            assert_eq!(module.get::<_, i32>("own").unwrap(), 1);
+            assert_eq!(module.get::<_, StdString>("hello").unwrap(), "world");
+            assert!(module.get::<_, Value>("missing").unwrap().is_undefined());
+
+            let entries: Vec<(StdString, Value)> = module.entries().collect::<Result<_>>().unwrap();
+            assert_eq!(entries.len(), 2);
+            let hello = entries.iter().find(|(name, _)| name == "hello").unwrap();
+            assert_eq!(hello.1.get::<StdString>().unwrap(), "world");
+        })
+    }
+
+    #[test]
+    fn bytecode_outlived_by_module() {
+        let bytecode = test_with(|ctx| {
+            let module = unsafe {
+                Module::unsafe_declare(
+                    ctx.clone(),
+                    "bytecode",
+                    "export function add(a, b) { return a + b; }",
+                )
+                .unwrap()
+            };
+            module.write_object_le().unwrap()
+        });
+
+        for owned in [false, true] {
+            let runtime = Runtime::new().unwrap();
+            let ctx = Context::full(&runtime).unwrap();
+            ctx.with(|ctx| {
+                let mut bytes = bytecode.clone();
+                if owned {
+                    unsafe { ModuleData::bytecode("bytecode", bytes.clone()) }
+                        .declare(ctx.clone())
+                        .unwrap();
+                } else {
+                    let _ = Module::instantiate_read_object(ctx.clone(), &bytes).unwrap();
+                }
+                bytes.fill(0);
+                drop(bytes);
+
+                let namespace: Object = Module::import(&ctx, "bytecode").unwrap();
+                let add: Function = namespace.get("add").unwrap();
+                assert_eq!(add.call::<_, i32>((1, 2)).unwrap(), 3);
+            });
+        }
+    }
+
+    #[test]
+    fn top_level_await_unsupported() {
+        test_with(|ctx| {
+            let res = ctx
+                .clone()
+                .compile("tla", "const x = await Promise.resolve(9); export { x }");
+            assert!(res.map(|_| ()).unwrap_err().is_exception());
+            let exception = ctx.catch().into_exception().unwrap();
+            assert_eq!(
+                exception.get::<_, StdString>("name").unwrap(),
+                "SyntaxError"
+            );
+        })
+    }
 }
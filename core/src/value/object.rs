@@ -1,8 +1,8 @@
 //! Module for types dealing with JS objects.
 
 use crate::{
-    convert::FromIteratorJs, qjs, Array, Atom, Ctx, FromAtom, FromJs, IntoAtom, IntoJs, Result,
-    Value,
+    convert::FromIteratorJs, function::This, qjs, Array, Atom, Ctx, Error, FromAtom, FromJs,
+    Function, IntoAtom, IntoJs, Result, Value,
 };
 use std::{
     iter::{DoubleEndedIterator, ExactSizeIterator, FusedIterator, IntoIterator, Iterator},
@@ -10,7 +10,7 @@ use std::{
     mem,
 };
 mod property;
-pub use property::{Accessor, AsProperty, Property, PropertyFlags};
+pub use property::{Accessor, AsProperty, Property, PropertyDescriptor, PropertyFlags};
 
 /// Rust representation of a JavaScript object.
 #[derive(Debug, PartialEq, Clone, Hash, Eq)]
@@ -27,9 +27,30 @@ impl<'js> Object<'js> {
         })
     }
 
+    /// Create a new JavaScript object from key value pairs
+    ///
+    /// Like `Object.fromEntries` the entries are defined as own enumerable properties, so a
+    /// `__proto__` key creates a property instead of changing the prototype.
+    pub fn from_entries<I, K, V>(ctx: Ctx<'js>, entries: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: IntoAtom<'js>,
+        V: IntoJs<'js>,
+    {
+        Self::from_iter_js(&ctx, entries)
+    }
+
     /// Get a new value
+    ///
+    /// The key is converted into an atom on every call, use [`Object::get_atom`] with an atom
+    /// created once when the same property is accessed repeatedly.
     pub fn get<K: IntoAtom<'js>, V: FromJs<'js>>(&self, k: K) -> Result<V> {
         let atom = k.into_atom(self.ctx())?;
+        self.get_atom(&atom)
+    }
+
+    /// Get a new value using a pre-interned key
+    pub fn get_atom<V: FromJs<'js>>(&self, atom: &Atom<'js>) -> Result<V> {
         V::from_js(self.ctx(), unsafe {
             let val = qjs::JS_GetProperty(self.0.ctx.as_ptr(), self.0.as_js_value(), atom.atom);
             let val = self.0.ctx.handle_exception(val)?;
@@ -37,6 +58,18 @@ impl<'js> Object<'js> {
         })
     }
 
+    /// Get a value if the object contains the key.
+    ///
+    /// Returns `None` when the property is absent. A property which is present but set to `null`
+    /// or `undefined` is converted with [`FromJs`] like with [`Object::get`].
+    pub fn get_opt<K: IntoAtom<'js>, V: FromJs<'js>>(&self, k: K) -> Result<Option<V>> {
+        let atom = k.into_atom(self.ctx())?;
+        if !self.contains_key(atom.clone())? {
+            return Ok(None);
+        }
+        self.get(atom).map(Some)
+    }
+
     /// check whether the object contains a certain key.
     pub fn contains_key<K>(&self, k: K) -> Result<bool>
     where
@@ -52,9 +85,39 @@ impl<'js> Object<'js> {
         }
     }
 
+    /// check whether the object has a certain key as its own property.
+    ///
+    /// Unlike [`Object::contains_key`] properties inherited from the prototype chain are not
+    /// considered, matching the semantics of `Object.prototype.hasOwnProperty`.
+    pub fn has_own<K>(&self, k: K) -> Result<bool>
+    where
+        K: IntoAtom<'js>,
+    {
+        let atom = k.into_atom(self.ctx())?;
+        unsafe {
+            let res = qjs::JS_GetOwnProperty(
+                self.0.ctx.as_ptr(),
+                std::ptr::null_mut(),
+                self.0.as_js_value(),
+                atom.atom,
+            );
+            if res < 0 {
+                return Err(self.0.ctx.raise_exception());
+            }
+            Ok(res == 1)
+        }
+    }
+
     /// Set a member of an object to a certain value
+    ///
+    /// Like with [`Object::get`] the key is converted on every call, see [`Object::set_atom`].
     pub fn set<K: IntoAtom<'js>, V: IntoJs<'js>>(&self, key: K, value: V) -> Result<()> {
         let atom = key.into_atom(self.ctx())?;
+        self.set_atom(&atom, value)
+    }
+
+    /// Set a member of an object to a certain value using a pre-interned key
+    pub fn set_atom<V: IntoJs<'js>>(&self, atom: &Atom<'js>, value: V) -> Result<()> {
         let val = value.into_js(self.ctx())?;
         unsafe {
             if qjs::JS_SetProperty(
@@ -70,6 +133,27 @@ impl<'js> Object<'js> {
         Ok(())
     }
 
+    /// Define a new own enumerable, writable and configurable property, used when building new
+    /// objects.
+    ///
+    /// Unlike [`Object::set`] no setters are called, so a `__proto__` key defines a property.
+    pub(crate) fn define_value<K: IntoAtom<'js>>(&self, key: K, value: Value<'js>) -> Result<()> {
+        let atom = key.into_atom(self.ctx())?;
+        unsafe {
+            if qjs::JS_DefinePropertyValue(
+                self.0.ctx.as_ptr(),
+                self.0.as_js_value(),
+                atom.atom,
+                value.into_js_value(),
+                (qjs::JS_PROP_C_W_E | qjs::JS_PROP_THROW) as _,
+            ) < 0
+            {
+                return Err(self.0.ctx.raise_exception());
+            }
+        }
+        Ok(())
+    }
+
     /// Remove a member of an object
     pub fn remove<K: IntoAtom<'js>>(&self, key: K) -> Result<()> {
         let atom = key.into_atom(self.ctx())?;
@@ -110,6 +194,14 @@ impl<'js> Object<'js> {
         }
     }
 
+    /// Get the names of all own properties of an object including the symbol keyed ones
+    ///
+    /// This is the combination of `Object.getOwnPropertyNames` and
+    /// `Object.getOwnPropertySymbols`, use [`Atom::is_symbol`] to tell the keys apart.
+    pub fn own_property_names_with_symbols(&self) -> Result<Vec<Atom<'js>>> {
+        self.own_keys(Filter::new().string().symbol()).collect()
+    }
+
     /// Get own string enumerable properties of an object
     pub fn props<K: FromAtom<'js>, V: FromJs<'js>>(&self) -> ObjectIter<'js, K, V> {
         self.own_props(Filter::default())
@@ -143,14 +235,19 @@ impl<'js> Object<'js> {
 
     /// Get an object prototype
     ///
-    /// Objects can have no prototype, in this case this function will return null.
-    pub fn get_prototype(&self) -> Option<Object<'js>> {
+    /// Objects can have no prototype, in this case this function will return `None`.
+    ///
+    /// This function will error if the object is a proxy whose `getPrototypeOf` trap throws.
+    pub fn get_prototype(&self) -> Result<Option<Object<'js>>> {
         unsafe {
             let proto = qjs::JS_GetPrototype(self.0.ctx.as_ptr(), self.0.as_js_value());
-            if qjs::JS_IsNull(proto) {
-                None
+            if qjs::JS_IsException(proto) {
+                Err(self.0.ctx.raise_exception())
+            } else if qjs::JS_IsNull(proto) {
+                Ok(None)
             } else {
-                Some(Object::from_js_value(self.0.ctx.clone(), proto))
+                // QuickJS returns a borrowed reference which is kept alive by the object.
+                Ok(Some(Object::from_js_value_const(self.0.ctx.clone(), proto)))
             }
         }
     }
@@ -183,6 +280,27 @@ impl<'js> Object<'js> {
         }
     }
 
+    /// Check if the object is a `Date` object
+    #[inline]
+    pub fn is_date(&self) -> bool {
+        self.as_value().is_date()
+    }
+
+    /// Get the time value of a `Date` object in milliseconds since the unix epoch
+    ///
+    /// Returns an error if the object is not a `Date` or if the date is invalid.
+    pub fn as_date_millis(&self) -> Result<f64> {
+        if !self.is_date() {
+            return Err(Error::new_from_js("object", "Date"));
+        }
+        let get_time: Function = self.get("getTime")?;
+        let millis: f64 = get_time.call((This(self.clone()),))?;
+        if millis.is_nan() {
+            return Err(Error::new_from_js_message("Date", "f64", "Invalid date"));
+        }
+        Ok(millis)
+    }
+
     /// Convert into an array
     pub fn into_array(self) -> Option<Array<'js>> {
         if self.is_array() {
@@ -240,6 +358,34 @@ impl Filter {
         self.flags |= qjs::JS_GPN_ENUM_ONLY as qjs::c_int;
         self
     }
+
+    /// Include integer index properties
+    ///
+    /// Integer keys like the indices of an array are string keys in JavaScript, so they are
+    /// always included by [`Filter::string`]. Use this filter without `string` to get the
+    /// integer keys only.
+    #[must_use]
+    pub fn integer(mut self) -> Self {
+        self.flags |= INDEX_MASK;
+        self
+    }
+}
+
+/// The filter flag for integer index properties, which is handled on the Rust side.
+const INDEX_MASK: qjs::c_int = 1 << 16;
+
+/// Returns whether the atom is an array index, i.e. the canonical form of an integer in
+/// `0..2^32 - 1`.
+fn is_index(atom: &Atom) -> bool {
+    if atom.is_symbol() {
+        return false;
+    }
+    match atom.to_string() {
+        Ok(name) => {
+            matches!(name.parse::<u32>(), Ok(idx) if idx != u32::MAX && idx.to_string() == name)
+        }
+        Err(_) => false,
+    }
 }
 
 struct IterState<'js> {
@@ -252,6 +398,9 @@ struct IterState<'js> {
 impl<'js> IterState<'js> {
     fn new(obj: &Value<'js>, flags: qjs::c_int) -> Result<Self> {
         let ctx = obj.ctx();
+        let string_mask = qjs::JS_GPN_STRING_MASK as qjs::c_int;
+        let index_only = flags & INDEX_MASK != 0 && flags & string_mask == 0;
+        let flags = flags & !INDEX_MASK | if index_only { string_mask } else { 0 };
 
         let mut enums = mem::MaybeUninit::uninit();
         let mut count = mem::MaybeUninit::uninit();
@@ -268,7 +417,22 @@ impl<'js> IterState<'js> {
                 return Err(ctx.raise_exception());
             }
             let enums = enums.assume_init();
-            let count = count.assume_init();
+            let mut count = count.assume_init();
+            if index_only {
+                // Drop the string keys which are not indices, keeping the symbols
+                let mut kept = 0;
+                for index in 0..count {
+                    let elem = *enums.offset(index as _);
+                    let atom = Atom::from_atom_val_dup(ctx.clone(), elem.atom);
+                    if atom.is_symbol() || is_index(&atom) {
+                        *enums.offset(kept as _) = elem;
+                        kept += 1;
+                    } else {
+                        qjs::JS_FreeAtom(ctx.as_ptr(), elem.atom);
+                    }
+                }
+                count = kept;
+            }
             (enums, count)
         };
 
@@ -602,7 +766,7 @@ where
         for (key, value) in iter {
             let key = key.into_atom(ctx)?;
             let value = value.into_js(ctx)?;
-            object.set(key, value)?;
+            object.define_value(key, value)?;
         }
         Ok(object)
     }
@@ -612,6 +776,156 @@ where
 mod test {
     use crate::*;
 
+    #[test]
+    fn prototype() {
+        test_with(|ctx| {
+            let base: Object = ctx
+                .eval("({ greet() { return 'hello ' + this.name } })")
+                .unwrap();
+            let child = Object::new(ctx.clone()).unwrap();
+            child.set("name", "child").unwrap();
+            assert!(child.get_prototype().unwrap().is_some());
+
+            child.set_prototype(Some(&base)).unwrap();
+            assert_eq!(child.get_prototype().unwrap(), Some(base.clone()));
+            let greet: Function = child.get("greet").unwrap();
+            let res: StdString = greet.call((crate::function::This(child.clone()),)).unwrap();
+            assert_eq!(res, "hello child");
+
+            // A cycle in the prototype chain is rejected.
+            assert!(base.set_prototype(Some(&child)).is_err());
+
+            child.set_prototype(None).unwrap();
+            assert_eq!(child.get_prototype().unwrap(), None);
+            assert!(!child.contains_key("greet").unwrap());
+            assert!(!child.contains_key("toString").unwrap());
+        });
+    }
+
+    #[test]
+    fn from_entries() {
+        test_with(|ctx| {
+            let obj = Object::from_entries(
+                ctx.clone(),
+                [
+                    ("name", "host".into_js(&ctx).unwrap()),
+                    ("size", 3.into_js(&ctx).unwrap()),
+                ],
+            )
+            .unwrap();
+            assert_eq!(obj.get::<_, StdString>("name").unwrap(), "host");
+            assert_eq!(obj.get::<_, i32>("size").unwrap(), 3);
+            let keys: Vec<StdString> = obj.keys().collect::<Result<_>>().unwrap();
+            assert_eq!(keys, ["name", "size"]);
+
+            let obj = Object::from_entries(ctx.clone(), [("__proto__", 1)]).unwrap();
+            assert!(obj.has_own("__proto__").unwrap());
+            assert_eq!(obj.get::<_, i32>("__proto__").unwrap(), 1);
+        });
+    }
+
+    #[test]
+    fn integer_keys() {
+        test_with(|ctx| {
+            let val: Object = ctx
+                .eval(
+                    r#"
+                    const obj = { a: 1, 2: "y", 0: "x", "01": 4, 4294967295: 5, [Symbol("s")]: 6 };
+                    Object.defineProperty(obj, "7", { value: "z" });
+                    obj
+                    "#,
+                )
+                .unwrap();
+
+            let keys: Vec<StdString> = val
+                .own_keys(Filter::new().integer())
+                .collect::<Result<_>>()
+                .unwrap();
+            assert_eq!(keys, ["0", "2", "7"]);
+            let keys: Vec<StdString> = val
+                .own_keys(Filter::new().integer().enum_only())
+                .collect::<Result<_>>()
+                .unwrap();
+            assert_eq!(keys, ["0", "2"]);
+            let values: Vec<StdString> = val
+                .own_values(Filter::new().integer().enum_only())
+                .collect::<Result<_>>()
+                .unwrap();
+            assert_eq!(values, ["x", "y"]);
+
+            let keys: Vec<Atom> = val
+                .own_keys(Filter::new().integer().symbol())
+                .collect::<Result<_>>()
+                .unwrap();
+            assert_eq!(keys.len(), 4);
+            assert!(keys[3].is_symbol());
+
+            assert_eq!(val.own_keys::<Atom>(Filter::new().string()).count(), 6);
+            assert_eq!(
+                val.own_keys::<Atom>(Filter::new().string().integer())
+                    .count(),
+                6
+            );
+        });
+    }
+
+    #[test]
+    fn own_property_names_with_symbols() {
+        test_with(|ctx| {
+            let val: Object = ctx
+                .eval("const obj = { key: 1, [Symbol('sym')]: 2 }; Object.defineProperty(obj, 'hidden', { value: 3 }); obj")
+                .unwrap();
+
+            let keys = val.own_property_names_with_symbols().unwrap();
+            assert_eq!(keys.len(), 3);
+            assert_eq!(keys[0].to_string().unwrap(), "key");
+            assert!(!keys[0].is_symbol());
+            assert_eq!(keys[1].to_string().unwrap(), "hidden");
+            assert!(!keys[1].is_symbol());
+            assert!(keys[2].is_symbol());
+            assert!(keys[2].to_property_key().unwrap().is_symbol());
+            assert!(keys[0].to_property_key().unwrap().is_string());
+            assert_eq!(val.get::<_, i32>(keys[2].clone()).unwrap(), 2);
+
+            let keys: Vec<Atom> = val
+                .own_keys(Filter::new().string())
+                .collect::<Result<_>>()
+                .unwrap();
+            assert_eq!(keys.len(), 2);
+        });
+    }
+
+    #[test]
+    fn get_opt() {
+        test_with(|ctx| {
+            let val: Object = ctx.eval("({ a: 1, n: null })").unwrap();
+
+            assert_eq!(val.get_opt::<_, i32>("a").unwrap(), Some(1));
+            assert_eq!(val.get_opt::<_, i32>("missing").unwrap(), None);
+            assert_eq!(val.get_opt::<_, Option<i32>>("n").unwrap(), Some(None));
+            assert_eq!(val.get_opt::<_, Option<i32>>("missing").unwrap(), None);
+            assert!(val.get_opt::<_, i32>("n").is_err());
+        });
+    }
+
+    #[test]
+    fn has_own() {
+        test_with(|ctx| {
+            let val: Object = ctx
+                .eval("const obj = Object.create({ inherited: 1 }); obj.own = undefined; obj")
+                .unwrap();
+
+            assert!(val.has_own("own").unwrap());
+            assert!(val.contains_key("own").unwrap());
+
+            assert!(!val.has_own("inherited").unwrap());
+            assert!(val.contains_key("inherited").unwrap());
+
+            assert!(!val.has_own("missing").unwrap());
+            assert!(!val.contains_key("missing").unwrap());
+        });
+    }
+
     #[test]
     fn from_javascript() {
         test_with(|ctx| {
@@ -674,6 +988,20 @@ mod test {
         })
     }
 
+    #[test]
+    fn atom_keys() {
+        test_with(|ctx| {
+            let key = Atom::from_str(ctx.clone(), "count").unwrap();
+            let val: Object = ctx.eval("({ count: 1 })").unwrap();
+            for _ in 0..3 {
+                let count: i32 = val.get_atom(&key).unwrap();
+                val.set_atom(&key, count + 1).unwrap();
+            }
+            assert_eq!(val.get::<_, i32>("count").unwrap(), 4);
+            assert_eq!(val.get::<_, i32>(&key).unwrap(), 4);
+        })
+    }
+
     #[test]
     fn own_keys_iter() {
         test_with(|ctx| {
@@ -804,4 +1132,26 @@ mod test {
             );
         })
     }
+
+    #[test]
+    fn date_millis() {
+        test_with(|ctx| {
+            let date: Object = ctx.eval("new Date(123456789)").unwrap();
+            assert!(date.is_date());
+            assert!(date.as_value().is_date());
+            assert_eq!(date.as_date_millis().unwrap(), 123456789.0);
+
+            let invalid: Object = ctx.eval("new Date(NaN)").unwrap();
+            assert!(invalid.is_date());
+            assert!(invalid.as_date_millis().is_err());
+
+            let obj: Object = ctx.eval("({ getTime() { return 1 } })").unwrap();
+            assert!(!obj.is_date());
+            assert!(obj.as_date_millis().is_err());
+
+            let fake: Object = ctx.eval("Object.create(Date.prototype)").unwrap();
+            assert!(!fake.is_date());
+            assert!(Date::from_object(fake).is_none());
+        })
+    }
 }
@@ -1,5 +1,8 @@
+use std::mem::MaybeUninit;
+
 use crate::{
-    function::IntoJsFunc, qjs, Ctx, Function, IntoAtom, IntoJs, Object, Result, Undefined, Value,
+    function::IntoJsFunc, qjs, Ctx, FromJs, Function, IntoAtom, IntoJs, Object, Result, Undefined,
+    Value,
 };
 
 impl<'js> Object<'js> {
@@ -52,10 +55,116 @@ impl<'js> Object<'js> {
         }
         Ok(())
     }
+
+    /// Get the descriptor of an own property of an object
+    ///
+    /// Returns `None` when the object has no own property with the key, like
+    /// `Object.getOwnPropertyDescriptor` returning `undefined`.
+    pub fn get_own_property_descriptor<K>(&self, key: K) -> Result<Option<PropertyDescriptor<'js>>>
+    where
+        K: IntoAtom<'js>,
+    {
+        let ctx = self.ctx();
+        let key = key.into_atom(ctx)?;
+        let mut desc = MaybeUninit::<qjs::JSPropertyDescriptor>::uninit();
+        unsafe {
+            let res = qjs::JS_GetOwnProperty(
+                ctx.as_ptr(),
+                desc.as_mut_ptr(),
+                self.0.as_js_value(),
+                key.atom,
+            );
+            if res < 0 {
+                return Err(self.0.ctx.raise_exception());
+            }
+            if res == 0 {
+                return Ok(None);
+            }
+            let desc = desc.assume_init();
+            Ok(Some(PropertyDescriptor {
+                flags: desc.flags,
+                value: Value::from_js_value(ctx.clone(), desc.value),
+                getter: Value::from_js_value(ctx.clone(), desc.getter),
+                setter: Value::from_js_value(ctx.clone(), desc.setter),
+            }))
+        }
+    }
 }
 
 pub type PropertyFlags = qjs::c_int;
 
+/// The descriptor of an existing property
+///
+/// Returned by [`Object::get_own_property_descriptor`]. It can be passed to [`Object::prop`] to
+/// define a property with the same configuration on another object.
+#[cfg_attr(feature = "doc-cfg", doc(cfg(feature = "properties")))]
+#[derive(Debug, Clone)]
+pub struct PropertyDescriptor<'js> {
+    flags: PropertyFlags,
+    value: Value<'js>,
+    getter: Value<'js>,
+    setter: Value<'js>,
+}
+
+impl<'js> PropertyDescriptor<'js> {
+    /// Returns whether the property can be changed or deleted
+    pub fn is_configurable(&self) -> bool {
+        self.flags & qjs::JS_PROP_CONFIGURABLE as PropertyFlags != 0
+    }
+
+    /// Returns whether the property shows up during enumeration
+    pub fn is_enumerable(&self) -> bool {
+        self.flags & qjs::JS_PROP_ENUMERABLE as PropertyFlags != 0
+    }
+
+    /// Returns whether the value of a data property can be assigned
+    ///
+    /// Always `false` for accessor properties.
+    pub fn is_writable(&self) -> bool {
+        self.flags & qjs::JS_PROP_WRITABLE as PropertyFlags != 0
+    }
+
+    /// Returns whether the property is defined by a getter and setter instead of a value
+    pub fn is_accessor(&self) -> bool {
+        self.flags & qjs::JS_PROP_GETSET as PropertyFlags != 0
+    }
+
+    /// Get the value of a data property
+    ///
+    /// Returns `undefined` for accessor properties.
+    pub fn value<V: FromJs<'js>>(&self) -> Result<V> {
+        V::from_js(self.value.ctx(), self.value.clone())
+    }
+
+    /// Get the getter of an accessor property
+    pub fn getter(&self) -> Option<Function<'js>> {
+        self.getter.clone().into_function()
+    }
+
+    /// Get the setter of an accessor property
+    pub fn setter(&self) -> Option<Function<'js>> {
+        self.setter.clone().into_function()
+    }
+}
+
+impl<'js> AsProperty<'js, PropertyDescriptor<'js>> for PropertyDescriptor<'js> {
+    fn config(
+        self,
+        _ctx: &Ctx<'js>,
+    ) -> Result<(PropertyFlags, Value<'js>, Value<'js>, Value<'js>)> {
+        let mut flags = self.flags
+            & (qjs::JS_PROP_CONFIGURABLE | qjs::JS_PROP_ENUMERABLE | qjs::JS_PROP_WRITABLE)
+                as PropertyFlags
+            | (qjs::JS_PROP_HAS_CONFIGURABLE | qjs::JS_PROP_HAS_ENUMERABLE) as PropertyFlags;
+        if self.is_accessor() {
+            flags |= (qjs::JS_PROP_HAS_GET | qjs::JS_PROP_HAS_SET) as PropertyFlags;
+        } else {
+            flags |= (qjs::JS_PROP_HAS_VALUE | qjs::JS_PROP_HAS_WRITABLE) as PropertyFlags;
+        }
+        Ok((flags, self.value, self.getter, self.setter))
+    }
+}
+
 /// The property interface
 #[cfg_attr(feature = "doc-cfg", doc(cfg(feature = "properties")))]
 pub trait AsProperty<'js, P> {
@@ -378,6 +487,69 @@ mod test {
         });
     }
 
+    #[test]
+    fn own_property_descriptor() {
+        test_with(|ctx| {
+            let obj = Object::new(ctx.clone()).unwrap();
+            obj.prop("ro", Property::from(42).enumerable()).unwrap();
+            obj.prop("get", Accessor::from(|| "str").configurable())
+                .unwrap();
+            obj.set("plain", "text").unwrap();
+
+            let desc = obj.get_own_property_descriptor("ro").unwrap().unwrap();
+            assert!(!desc.is_accessor());
+            assert!(!desc.is_writable());
+            assert!(desc.is_enumerable());
+            assert!(!desc.is_configurable());
+            assert_eq!(desc.value::<i32>().unwrap(), 42);
+            assert!(desc.getter().is_none());
+
+            let desc = obj.get_own_property_descriptor("get").unwrap().unwrap();
+            assert!(desc.is_accessor());
+            assert!(desc.is_configurable());
+            assert!(!desc.is_enumerable());
+            assert!(desc.setter().is_none());
+            let value: StdString = desc.getter().unwrap().call(()).unwrap();
+            assert_eq!(value, "str");
+
+            let desc = obj.get_own_property_descriptor("plain").unwrap().unwrap();
+            assert!(desc.is_writable() && desc.is_enumerable() && desc.is_configurable());
+
+            assert!(obj
+                .get_own_property_descriptor("missing")
+                .unwrap()
+                .is_none());
+            assert!(obj
+                .get_own_property_descriptor("toString")
+                .unwrap()
+                .is_none());
+
+            let copy = Object::new(ctx.clone()).unwrap();
+            copy.prop(
+                "ro",
+                obj.get_own_property_descriptor("ro").unwrap().unwrap(),
+            )
+            .unwrap();
+            copy.prop(
+                "get",
+                obj.get_own_property_descriptor("get").unwrap().unwrap(),
+            )
+            .unwrap();
+            ctx.globals().set("copy", copy).unwrap();
+            let same: bool = ctx
+                .eval(
+                    r#"
+                    const ro = Object.getOwnPropertyDescriptor(copy, "ro");
+                    const get = Object.getOwnPropertyDescriptor(copy, "get");
+                    ro.value === 42 && !ro.writable && ro.enumerable && !ro.configurable
+                        && copy.get === "str" && get.set === undefined && get.configurable
+                    "#,
+                )
+                .unwrap();
+            assert!(same);
+        });
+    }
+
     #[test]
     fn property_with_getter_only() {
         test_with(|ctx| {
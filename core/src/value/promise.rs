@@ -0,0 +1,165 @@
+use crate::{
+    atom::PredefinedAtom, function::This, qjs, Ctx, FromJs, Function, IntoJs, Object, Result, Value,
+};
+
+/// The state of a [`PromiseObject`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PromiseState {
+    /// The promise is neither fulfilled nor rejected yet
+    Pending,
+    /// The promise was fulfilled with a value
+    Fulfilled,
+    /// The promise was rejected with a reason
+    Rejected,
+}
+
+/// A JavaScript `Promise` object
+///
+/// Unlike the future `promise::Promise` of the `futures` feature this type can be used without
+/// it. Its state can be inspected directly and reactions can be registered with
+/// [`PromiseObject::then`], which are run by the pending jobs of the runtime.
+///
+/// Promises are of [`Type::Object`](crate::Type::Object), use [`Value::is_promise`] to check for
+/// them.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct PromiseObject<'js>(pub(crate) Object<'js>);
+
+impl<'js> PromiseObject<'js> {
+    /// Create a new pending promise together with its `resolve` and `reject` functions
+    ///
+    /// See [`Ctx::promise`] for how the functions settle the promise.
+    pub fn with_resolvers(ctx: &Ctx<'js>) -> Result<(Self, Function<'js>, Function<'js>)> {
+        let (promise, resolve, reject) = ctx.promise()?;
+        Ok((PromiseObject(promise), resolve, reject))
+    }
+
+    /// Returns the state of the promise
+    pub fn state(&self) -> PromiseState {
+        match unsafe { qjs::JS_PromiseState(self.ctx().as_ptr(), self.as_js_value()) } {
+            0 => PromiseState::Pending,
+            1 => PromiseState::Fulfilled,
+            _ => PromiseState::Rejected,
+        }
+    }
+
+    /// Returns the result of the promise if it is settled
+    ///
+    /// Returns `None` for a pending promise and the value of a fulfilled promise. The reason of a
+    /// rejected promise is thrown, so an [`Error::Exception`](crate::Error::Exception) is returned
+    /// and the reason can be retrieved with [`Ctx::catch`].
+    pub fn result<T: FromJs<'js>>(&self) -> Option<Result<T>> {
+        let state = self.state();
+        if state == PromiseState::Pending {
+            return None;
+        }
+        let ctx = self.ctx();
+        let result = unsafe {
+            Value::from_js_value(
+                ctx.clone(),
+                qjs::JS_PromiseResult(ctx.as_ptr(), self.as_js_value()),
+            )
+        };
+        Some(match state {
+            PromiseState::Fulfilled => T::from_js(ctx, result),
+            _ => Err(ctx.throw(result)),
+        })
+    }
+
+    /// Register reactions which are called when the promise is fulfilled or rejected
+    ///
+    /// Either reaction can be `undefined`. Returns the promise which is settled with the result
+    /// of the called reaction.
+    pub fn then<F, R>(&self, on_fulfilled: F, on_rejected: R) -> Result<PromiseObject<'js>>
+    where
+        F: IntoJs<'js>,
+        R: IntoJs<'js>,
+    {
+        let then: Function = self.0.get(PredefinedAtom::Then)?;
+        then.call((This(self.clone()), on_fulfilled, on_rejected))
+    }
+
+    /// Register a reaction which is called when the promise is rejected
+    pub fn catch<R>(&self, on_rejected: R) -> Result<PromiseObject<'js>>
+    where
+        R: IntoJs<'js>,
+    {
+        let catch: Function = self.0.get(PredefinedAtom::Catch)?;
+        catch.call((This(self.clone()), on_rejected))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PromiseState;
+    use crate::{
+        test_with, Context, Error, Function, PromiseObject, Result, Runtime, Type, Undefined, Value,
+    };
+
+    #[test]
+    fn with_resolvers() {
+        test_with(|ctx| {
+            let (promise, resolve, _) = PromiseObject::with_resolvers(&ctx).unwrap();
+            assert_eq!(promise.state(), PromiseState::Pending);
+            assert!(promise.result::<i32>().is_none());
+            resolve.call::<_, ()>((42,)).unwrap();
+            assert_eq!(promise.state(), PromiseState::Fulfilled);
+            assert_eq!(promise.result::<i32>().unwrap().unwrap(), 42);
+
+            let (promise, _, reject) = PromiseObject::with_resolvers(&ctx).unwrap();
+            reject.call::<_, ()>(("reason",)).unwrap();
+            assert_eq!(promise.state(), PromiseState::Rejected);
+            let err = promise.result::<i32>().unwrap().unwrap_err();
+            assert!(matches!(err, Error::Exception));
+            let reason: String = ctx.catch().get().unwrap();
+            assert_eq!(reason, "reason");
+        })
+    }
+
+    #[test]
+    fn from_js() {
+        test_with(|ctx| {
+            let value: Value = ctx.eval("Promise.resolve(1)").unwrap();
+            assert_eq!(value.type_of(), Type::Object);
+            assert!(value.is_promise());
+            let promise: PromiseObject = value.get().unwrap();
+            assert_eq!(promise.result::<i32>().unwrap().unwrap(), 1);
+
+            let not_promise: Value = ctx.eval("({ then() {} })").unwrap();
+            assert!(!not_promise.is_promise());
+            assert!(not_promise.get::<PromiseObject>().is_err());
+        })
+    }
+
+    #[test]
+    fn then() {
+        let rt = Runtime::new().unwrap();
+        let ctx = Context::full(&rt).unwrap();
+        ctx.with(|ctx| {
+            let (promise, resolve, _) = PromiseObject::with_resolvers(&ctx).unwrap();
+            let double = Function::new(ctx.clone(), |x: i32| x * 2).unwrap();
+            let doubled = promise.then(double, Undefined).unwrap();
+            let fail = Function::new(ctx.clone(), |_: i32| -> Result<()> {
+                Err(Error::new_from_js("number", "string"))
+            })
+            .unwrap();
+            let recover = Function::new(ctx.clone(), || "caught").unwrap();
+            let caught = promise
+                .then(fail, Undefined)
+                .unwrap()
+                .catch(recover)
+                .unwrap();
+            resolve.call::<_, ()>((21,)).unwrap();
+            assert_eq!(doubled.state(), PromiseState::Pending);
+            ctx.globals().set("doubled", doubled).unwrap();
+            ctx.globals().set("caught", caught).unwrap();
+        });
+        rt.execute_pending_jobs().unwrap();
+        ctx.with(|ctx| {
+            let doubled: PromiseObject = ctx.globals().get("doubled").unwrap();
+            assert_eq!(doubled.result::<i32>().unwrap().unwrap(), 42);
+            let caught: PromiseObject = ctx.globals().get("caught").unwrap();
+            assert_eq!(caught.result::<String>().unwrap().unwrap(), "caught");
+        })
+    }
+}
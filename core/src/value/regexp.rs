@@ -0,0 +1,130 @@
+//! JavaScript regular expression types.
+
+use crate::{
+    atom::PredefinedAtom, function::This, value::Constructor, Array, Ctx, Function, Object, Result,
+    StdString,
+};
+
+/// Rust representation of a JavaScript `RegExp` object.
+///
+/// Regular expressions are matched by the engine of QuickJS with the JavaScript syntax and
+/// semantics, so patterns defined by scripts, for example in configurations, behave the same when
+/// they are used from Rust.
+#[derive(Debug, PartialEq, Clone, Eq, Hash)]
+#[repr(transparent)]
+pub struct RegExp<'js>(pub(crate) Object<'js>);
+
+impl<'js> RegExp<'js> {
+    /// Create a regular expression from a pattern and flags like `new RegExp(pattern, flags)`
+    ///
+    /// An invalid pattern or flag throws a `SyntaxError`, which is returned as an
+    /// [`Error::Exception`].
+    pub fn new(ctx: Ctx<'js>, pattern: &str, flags: &str) -> Result<Self> {
+        let ctor: Constructor = ctx.globals().get(PredefinedAtom::RegExp)?;
+        ctor.construct((pattern, flags))
+    }
+
+    /// Returns whether the regular expression matches the input
+    ///
+    /// Like in JavaScript regular expressions with the `g` or `y` flag continue searching from
+    /// their [`last_index`](RegExp::last_index).
+    pub fn test(&self, input: &str) -> Result<bool> {
+        let test: Function = self.0.get("test")?;
+        test.call((This(self.0.clone()), input))
+    }
+
+    /// Search the input for a match
+    ///
+    /// Returns `None` if there is no match. Otherwise the returned array contains the matched
+    /// text followed by the captured groups, which are `undefined` when they didn't participate in
+    /// the match. The array also has the `index` of the match and the named `groups` as
+    /// properties.
+    pub fn exec(&self, input: &str) -> Result<Option<Array<'js>>> {
+        let exec: Function = self.0.get(PredefinedAtom::Exec)?;
+        exec.call((This(self.0.clone()), input))
+    }
+
+    /// Get the source text of the pattern
+    pub fn source(&self) -> Result<StdString> {
+        self.0.get(PredefinedAtom::Source)
+    }
+
+    /// Get the flags, like `"gi"`
+    pub fn flags(&self) -> Result<StdString> {
+        self.0.get(PredefinedAtom::Flags)
+    }
+
+    /// Get the index at which the next match starts for regular expressions with the `g` or `y`
+    /// flag
+    pub fn last_index(&self) -> Result<usize> {
+        self.0.get(PredefinedAtom::LastIndex)
+    }
+
+    /// Set the index at which the next match starts
+    pub fn set_last_index(&self, index: usize) -> Result<()> {
+        self.0.set(PredefinedAtom::LastIndex, index)
+    }
+
+    /// Reference as an object
+    #[inline]
+    pub fn as_object(&self) -> &Object<'js> {
+        &self.0
+    }
+
+    /// Convert into an object
+    #[inline]
+    pub fn into_object(self) -> Object<'js> {
+        self.0
+    }
+
+    /// Convert from an object if it is a regular expression
+    pub fn from_object(object: Object<'js>) -> Option<Self> {
+        object.as_value().is_regexp().then_some(Self(object))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn test_and_exec() {
+        test_with(|ctx| {
+            let re = RegExp::new(ctx.clone(), r"(?<user>\w+)@(\w+)\.com", "i").unwrap();
+            assert!(re.test("Mail ME@example.COM").unwrap());
+            assert!(!re.test("no address").unwrap());
+            assert_eq!(re.source().unwrap(), r"(?<user>\w+)@(\w+)\.com");
+            assert_eq!(re.flags().unwrap(), "i");
+
+            let found = re.exec("mail me@example.com").unwrap().unwrap();
+            assert_eq!(found.get::<StdString>(0).unwrap(), "me@example.com");
+            assert_eq!(found.get::<StdString>(2).unwrap(), "example");
+            assert_eq!(found.as_object().get::<_, usize>("index").unwrap(), 5);
+            let groups: Object = found.as_object().get("groups").unwrap();
+            assert_eq!(groups.get::<_, StdString>("user").unwrap(), "me");
+            assert!(re.exec("nothing").unwrap().is_none());
+
+            let err = RegExp::new(ctx.clone(), "(", "").unwrap_err();
+            assert!(err.is_exception());
+            ctx.catch();
+        })
+    }
+
+    #[test]
+    fn global_matching() {
+        test_with(|ctx| {
+            let re: RegExp = ctx.eval(r"/\d+/g").unwrap();
+            let first = re.exec("1 22 333").unwrap().unwrap();
+            assert_eq!(first.get::<StdString>(0).unwrap(), "1");
+            assert_eq!(re.last_index().unwrap(), 1);
+            re.set_last_index(4).unwrap();
+            let next = re.exec("1 22 333").unwrap().unwrap();
+            assert_eq!(next.get::<StdString>(0).unwrap(), "333");
+            assert!(ctx.eval::<RegExp, _>("'/a/'").is_err());
+            // Only objects of the `RegExp` class are regular expressions
+            assert!(ctx
+                .eval::<RegExp, _>("Object.create(RegExp.prototype)")
+                .is_err());
+        })
+    }
+}
@@ -0,0 +1,196 @@
+//! JavaScript set types.
+
+use super::map::JsIter;
+use crate::{
+    atom::PredefinedAtom, convert::FromIteratorJs, function::This, qjs, value::Constructor, Ctx,
+    Error, FromJs, Function, IntoAtom, IntoJs, Object, Result, Value,
+};
+use std::marker::PhantomData;
+
+/// Rust representation of a JavaScript `Set` object.
+///
+/// Like the keys of a [`Map`](crate::Map) the values of a set can be of any type and are
+/// compared by identity. Sets are created from Rust iterators with
+/// [`IteratorJs::collect_js`](crate::IteratorJs::collect_js).
+#[derive(Debug, PartialEq, Clone, Eq, Hash)]
+#[repr(transparent)]
+pub struct Set<'js>(pub(crate) Object<'js>);
+
+impl<'js> Set<'js> {
+    /// Create a new empty set
+    pub fn new(ctx: Ctx<'js>) -> Result<Self> {
+        let ctor: Constructor = ctx.globals().get(PredefinedAtom::Set)?;
+        ctor.construct(())
+    }
+
+    /// Add a value to the set
+    pub fn add<V: IntoJs<'js>>(&self, value: V) -> Result<()> {
+        self.method(PredefinedAtom::Add)?
+            .call::<_, Value>((This(self.0.clone()), value))
+            .map(|_| ())
+    }
+
+    /// Returns whether the set contains the value
+    pub fn has<V: IntoJs<'js>>(&self, value: V) -> Result<bool> {
+        self.method(PredefinedAtom::Has)?
+            .call((This(self.0.clone()), value))
+    }
+
+    /// Remove a value from the set, returns whether the value was present
+    pub fn delete<V: IntoJs<'js>>(&self, value: V) -> Result<bool> {
+        self.method(PredefinedAtom::Delete)?
+            .call((This(self.0.clone()), value))
+    }
+
+    /// Remove all values of the set
+    pub fn clear(&self) -> Result<()> {
+        self.method("clear")?.call((This(self.0.clone()),))
+    }
+
+    /// Get the number of values in the set
+    pub fn size(&self) -> Result<usize> {
+        self.0.get("size")
+    }
+
+    /// Get an iterator over the values of the set in insertion order
+    pub fn iter<V: FromJs<'js>>(&self) -> SetIter<'js, V> {
+        SetIter {
+            iter: JsIter::new(self.0.clone()),
+            marker: PhantomData,
+        }
+    }
+
+    /// Reference to value
+    #[inline]
+    pub fn as_value(&self) -> &Value<'js> {
+        self.0.as_value()
+    }
+
+    /// Convert into value
+    #[inline]
+    pub fn into_value(self) -> Value<'js> {
+        self.0.into_value()
+    }
+
+    /// Reference as an object
+    #[inline]
+    pub fn as_object(&self) -> &Object<'js> {
+        &self.0
+    }
+
+    /// Convert into an object
+    #[inline]
+    pub fn into_object(self) -> Object<'js> {
+        self.0
+    }
+
+    /// Convert from an object if it is a set
+    pub fn from_object(object: Object<'js>) -> Option<Self> {
+        let is_set = unsafe { qjs::JS_IsSet(object.as_js_value()) } != 0;
+        is_set.then_some(Self(object))
+    }
+
+    fn method<K: IntoAtom<'js>>(&self, name: K) -> Result<Function<'js>> {
+        self.0.get(name)
+    }
+}
+
+/// The iterator for a set
+pub struct SetIter<'js, V> {
+    iter: JsIter<'js>,
+    marker: PhantomData<V>,
+}
+
+impl<'js, V> Iterator for SetIter<'js, V>
+where
+    V: FromJs<'js>,
+{
+    type Item = Result<V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.iter.next()?;
+        Some(value.and_then(|value| value.get()))
+    }
+}
+
+impl<'js> IntoIterator for Set<'js> {
+    type Item = Result<Value<'js>>;
+    type IntoIter = SetIter<'js, Value<'js>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'js, V> FromIteratorJs<'js, V> for Set<'js>
+where
+    V: IntoJs<'js>,
+{
+    type Item = Value<'js>;
+
+    fn from_iter_js<T>(ctx: &Ctx<'js>, iter: T) -> Result<Self>
+    where
+        T: IntoIterator<Item = V>,
+    {
+        let set = Set::new(ctx.clone())?;
+        for value in iter {
+            set.add(value)?;
+        }
+        Ok(set)
+    }
+}
+
+impl<'js> FromJs<'js> for Set<'js> {
+    fn from_js(_: &Ctx<'js>, value: Value<'js>) -> Result<Self> {
+        let ty_name = value.type_name();
+        value
+            .into_object()
+            .and_then(Self::from_object)
+            .ok_or_else(|| Error::new_from_js(ty_name, "Set"))
+    }
+}
+
+impl<'js> IntoJs<'js> for Set<'js> {
+    fn into_js(self, _: &Ctx<'js>) -> Result<Value<'js>> {
+        Ok(self.0.into_value())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use std::collections::{BTreeSet, HashSet};
+
+    #[test]
+    fn set_methods() {
+        test_with(|ctx| {
+            let set: Set = [3, 1, 3, 2].into_iter().collect_js(&ctx).unwrap();
+            assert_eq!(set.size().unwrap(), 3);
+            assert!(set.has(1).unwrap());
+            assert!(!set.has("1").unwrap());
+            set.add("1").unwrap();
+            assert!(set.delete(3).unwrap());
+            let values = set
+                .iter::<Coerced<StdString>>()
+                .collect::<Result<Vec<_>>>()
+                .unwrap();
+            assert_eq!(
+                values.iter().map(|v| v.as_str()).collect::<Vec<_>>(),
+                ["1", "2", "1"]
+            );
+            set.clear().unwrap();
+            assert_eq!(set.size().unwrap(), 0);
+        })
+    }
+
+    #[test]
+    fn set_conversions() {
+        test_with(|ctx| {
+            let set: HashSet<i32> = ctx.eval("new Set([1, 2, 2])").unwrap();
+            assert_eq!(set, HashSet::from([1, 2]));
+            let set: BTreeSet<StdString> = ctx.eval("['b', 'a']").unwrap();
+            assert_eq!(set.len(), 2);
+            assert!(ctx.eval::<Set, _>("[1]").is_err());
+        })
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{qjs, Ctx, Error, Result, StdString, Value};
-use std::{mem, slice, str};
+use std::{fmt, mem, ops::Deref, slice, str};
 
 /// Rust representation of a JavaScript string.
 #[derive(Debug, Clone, PartialEq, Hash)]
@@ -8,7 +8,108 @@ pub struct String<'js>(pub(crate) Value<'js>);
 
 impl<'js> String<'js> {
     /// Convert the JavaScript string to a Rust string.
+    ///
+    /// JavaScript strings can contain lone surrogates which have no UTF-8 representation,
+    /// in that case [`Error::Utf8`] is returned. Use [`String::to_string_lossy`] to replace them or
+    /// [`String::to_utf16`] to keep them instead.
     pub fn to_string(&self) -> Result<StdString> {
+        self.with_bytes(|bytes| str::from_utf8(bytes).map(|s| s.into()))?
+            .map_err(Error::from)
+    }
+
+    /// Convert the JavaScript string to a Rust string replacing every lone surrogate with
+    /// `U+FFFD REPLACEMENT CHARACTER`.
+    pub fn to_string_lossy(&self) -> Result<StdString> {
+        self.with_bytes(|mut bytes| {
+            let mut result = StdString::with_capacity(bytes.len());
+            loop {
+                match str::from_utf8(bytes) {
+                    Ok(valid) => {
+                        result.push_str(valid);
+                        break;
+                    }
+                    Err(error) => {
+                        let (valid, rest) = bytes.split_at(error.valid_up_to());
+                        result.push_str(unsafe { str::from_utf8_unchecked(valid) });
+                        result.push(char::REPLACEMENT_CHARACTER);
+                        // QuickJS encodes a lone surrogate as a three byte sequence.
+                        let invalid =
+                            if rest.len() >= 3 && rest[0] == 0xED && rest[1] & 0xE0 == 0xA0 {
+                                3
+                            } else {
+                                error.error_len().unwrap_or(rest.len())
+                            };
+                        bytes = &rest[invalid..];
+                    }
+                }
+            }
+            result
+        })
+    }
+
+    /// Convert the JavaScript string to UTF-16 code units.
+    ///
+    /// This is the representation used by JavaScript itself, so unlike the conversions to Rust
+    /// strings lone surrogates are returned as they are.
+    pub fn to_utf16(&self) -> Result<Vec<u16>> {
+        self.with_bytes(|bytes| {
+            let mut units = Vec::with_capacity(bytes.len());
+            let mut idx = 0;
+            while idx < bytes.len() {
+                let cont = |n: usize| bytes.get(idx + n).map_or(0, |&b| (b & 0x3F) as u32);
+                let first = bytes[idx] as u32;
+                // The bytes are UTF-8 except for lone surrogates which are encoded like other
+                // characters of the basic multilingual plane.
+                let (code, len) = if first < 0x80 {
+                    (first, 1)
+                } else if first < 0xE0 {
+                    ((first & 0x1F) << 6 | cont(1), 2)
+                } else if first < 0xF0 {
+                    ((first & 0x0F) << 12 | cont(1) << 6 | cont(2), 3)
+                } else {
+                    (
+                        (first & 0x07) << 18 | cont(1) << 12 | cont(2) << 6 | cont(3),
+                        4,
+                    )
+                };
+                if code >= 0x10000 {
+                    let code = code - 0x10000;
+                    units.push(0xD800 | (code >> 10) as u16);
+                    units.push(0xDC00 | (code & 0x3FF) as u16);
+                } else {
+                    units.push(code as u16);
+                }
+                idx += len;
+            }
+            units
+        })
+    }
+
+    /// Borrow the UTF-8 contents of the JavaScript string without copying them into a Rust string.
+    ///
+    /// Like [`String::to_string`] this returns [`Error::Utf8`] if the string contains lone
+    /// surrogates.
+    pub fn to_str(&self) -> Result<StrRef<'js>> {
+        let (ptr, len) = self.to_c_str()?;
+        let str_ref = StrRef {
+            ctx: self.0.ctx.clone(),
+            ptr,
+            len,
+        };
+        // The guard frees the bytes if they are not valid.
+        str::from_utf8(str_ref.as_bytes())?;
+        Ok(str_ref)
+    }
+
+    /// Get the bytes of the string as encoded by QuickJS.
+    ///
+    /// The bytes are valid UTF-8 unless the string contains lone surrogates, which are encoded as
+    /// three byte sequences like other characters of the basic multilingual plane.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        self.with_bytes(|bytes| bytes.to_vec())
+    }
+
+    fn to_c_str(&self) -> Result<(*const qjs::c_char, usize)> {
         let mut len = mem::MaybeUninit::uninit();
         let ptr = unsafe {
             qjs::JS_ToCStringLen(self.0.ctx.as_ptr(), len.as_mut_ptr(), self.0.as_js_value())
@@ -19,10 +120,15 @@ impl<'js> String<'js> {
             return Err(Error::Unknown);
         }
         let len = unsafe { len.assume_init() };
-        let bytes: &[u8] = unsafe { slice::from_raw_parts(ptr as _, len as _) };
-        let result = str::from_utf8(bytes).map(|s| s.into());
+        Ok((ptr, len as _))
+    }
+
+    fn with_bytes<R>(&self, f: impl FnOnce(&[u8]) -> R) -> Result<R> {
+        let (ptr, len) = self.to_c_str()?;
+        let bytes: &[u8] = unsafe { slice::from_raw_parts(ptr as _, len) };
+        let result = f(bytes);
         unsafe { qjs::JS_FreeCString(self.0.ctx.as_ptr(), ptr) };
-        Ok(result?)
+        Ok(result)
     }
 
     /// Create a new JavaScript string from an Rust string.
@@ -37,6 +143,72 @@ impl<'js> String<'js> {
     }
 }
 
+/// The borrowed contents of a JavaScript string, returned by [`String::to_str`].
+///
+/// Dereferences to a [`str`], the contents are freed when the guard is dropped.
+pub struct StrRef<'js> {
+    ctx: Ctx<'js>,
+    ptr: *const qjs::c_char,
+    len: usize,
+}
+
+impl<'js> StrRef<'js> {
+    /// Get the contents as a string slice
+    pub fn as_str(&self) -> &str {
+        // Checked to be valid UTF-8 when the guard was created
+        unsafe { str::from_utf8_unchecked(self.as_bytes()) }
+    }
+
+    /// Get the contents as bytes
+    pub fn as_bytes(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self.ptr as _, self.len) }
+    }
+}
+
+impl<'js> Deref for StrRef<'js> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<'js> AsRef<str> for StrRef<'js> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<'js> PartialEq<str> for StrRef<'js> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<'js> PartialEq<&str> for StrRef<'js> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<'js> fmt::Debug for StrRef<'js> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+impl<'js> fmt::Display for StrRef<'js> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+impl<'js> Drop for StrRef<'js> {
+    fn drop(&mut self) {
+        unsafe { qjs::JS_FreeCString(self.ctx.as_ptr(), self.ptr) }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::{prelude::*, *};
@@ -48,6 +220,40 @@ mod test {
         });
     }
 
+    #[test]
+    fn lone_surrogates() {
+        test_with(|ctx| {
+            let s: String = ctx.eval(r#" 'a\uD800b\uDFFF' "#).unwrap();
+            assert!(matches!(s.to_string(), Err(Error::Utf8(_))));
+            assert_eq!(s.to_string_lossy().unwrap(), "a\u{FFFD}b\u{FFFD}");
+            assert_eq!(s.to_utf16().unwrap(), [0x61, 0xD800, 0x62, 0xDFFF]);
+
+            let s: String = ctx.eval(r#" 'caf\u00e9 \uD83D\uDE00' "#).unwrap();
+            assert_eq!(s.to_string().unwrap(), "caf\u{e9} \u{1F600}");
+            assert_eq!(s.to_string_lossy().unwrap(), "caf\u{e9} \u{1F600}");
+            assert_eq!(
+                s.to_utf16().unwrap(),
+                "caf\u{e9} \u{1F600}".encode_utf16().collect::<Vec<_>>()
+            );
+        });
+    }
+
+    #[test]
+    fn borrowed() {
+        test_with(|ctx| {
+            let s: String = ctx.eval(" 'caf\\u00e9' + 1 ").unwrap();
+            let str_ref = s.to_str().unwrap();
+            assert_eq!(str_ref, "caf\u{e9}1");
+            assert_eq!(str_ref.len(), 6);
+            assert!(str_ref.starts_with("caf"));
+            assert_eq!(str_ref.to_string(), "caf\u{e9}1");
+
+            let s: String = ctx.eval(r#" 'a\uD800' "#).unwrap();
+            assert!(matches!(s.to_str(), Err(Error::Utf8(_))));
+            assert_eq!(s.to_bytes().unwrap(), b"a\xED\xA0\x80");
+        });
+    }
+
     #[test]
     fn to_javascript() {
         test_with(|ctx| {
@@ -1,4 +1,5 @@
-use crate::{qjs, Atom, Ctx, Result, String, Value};
+use crate::{qjs, Atom, Ctx, FromJs, Result, String, Value};
+use std::ffi::CString;
 
 /// Rust representation of a JavaScript symbol.
 #[derive(Debug, Clone, PartialEq, Hash)]
@@ -6,6 +7,44 @@ use crate::{qjs, Atom, Ctx, Result, String, Value};
 pub struct Symbol<'js>(pub(crate) Value<'js>);
 
 impl<'js> Symbol<'js> {
+    /// Create a new unique symbol with the given description
+    pub fn new(ctx: Ctx<'js>, description: &str) -> Result<Self> {
+        Self::from_description(ctx, description, false)
+    }
+
+    /// Get the symbol registered for the key in the global symbol registry, like `Symbol.for`
+    ///
+    /// The same symbol is returned for the same key in every context of the runtime.
+    pub fn for_key(ctx: Ctx<'js>, key: &str) -> Result<Self> {
+        Self::from_description(ctx, key, true)
+    }
+
+    /// Get the key of a symbol in the global symbol registry, like `Symbol.keyFor`
+    ///
+    /// Returns `None` for symbols which are not registered.
+    pub fn key(&self) -> Result<Option<String<'js>>> {
+        let ctx = &self.0.ctx;
+        unsafe {
+            let val = qjs::JS_GetSymbolKey(ctx.as_ptr(), self.0.as_js_value());
+            let val = Value::from_js_value(ctx.clone(), ctx.handle_exception(val)?);
+            FromJs::from_js(ctx, val)
+        }
+    }
+
+    /// Create a symbol with the engine, the global `Symbol` may have been replaced by scripts
+    fn from_description(ctx: Ctx<'js>, description: &str, global: bool) -> Result<Self> {
+        let description = CString::new(description)?;
+        unsafe {
+            let val = qjs::JS_NewSymbolFromString(
+                ctx.as_ptr(),
+                description.as_ptr(),
+                global as qjs::c_int,
+            );
+            let val = ctx.handle_exception(val)?;
+            Ok(Symbol(Value::from_js_value(ctx, val)))
+        }
+    }
+
     /// Get the symbol description
     pub fn description(&self) -> Result<String<'js>> {
         let atom = Atom::from_str(self.0.ctx.clone(), "description")?;
@@ -65,6 +104,10 @@ impl_symbols! {
     unscopables => JS_ATOM_Symbol_unscopables
     /// returns the symbol for `asyncIterator`
     async_iterator => JS_ATOM_Symbol_asyncIterator
+    /// returns the symbol for `toStringTag`
+    to_string_tag => JS_ATOM_Symbol_toStringTag
+    /// returns the symbol for `isConcatSpreadable`
+    is_concat_spreadable => JS_ATOM_Symbol_isConcatSpreadable
     /// returns the symbol for `operatorSet`
     operator_set => JS_ATOM_Symbol_operatorSet
 }
@@ -81,6 +124,72 @@ mod test {
 
             let s: Symbol<'_> = ctx.eval("Symbol()").unwrap();
             assert_eq!(s.description().unwrap().to_string().unwrap(), "undefined");
+
+            let s = Symbol::new(ctx.clone(), "qux").unwrap();
+            assert_eq!(s.description().unwrap().to_string().unwrap(), "qux");
+            assert_ne!(s, Symbol::new(ctx.clone(), "qux").unwrap());
+        });
+    }
+
+    #[test]
+    fn symbol_keys() {
+        test_with(|ctx| {
+            let obj = Object::new(ctx.clone()).unwrap();
+            let key = Symbol::new(ctx.clone(), "key").unwrap();
+            obj.set(key.clone(), 42).unwrap();
+            assert_eq!(obj.get::<_, i32>(key).unwrap(), 42);
+
+            fn values<'js>(ctx: Ctx<'js>) -> Result<Object<'js>> {
+                let array = Array::new(ctx.clone())?;
+                array.extend([1, 2, 3])?;
+                let iter: Function = array.as_object().get(Symbol::iterator(ctx))?;
+                iter.call((function::This(array),))
+            }
+            let counter = Function::new(ctx.clone(), values).unwrap();
+            obj.set(Symbol::iterator(ctx.clone()), counter).unwrap();
+            ctx.globals().set("obj", obj).unwrap();
+
+            let sum: i32 = ctx
+                .eval("let sum = 0; for (const x of obj) { sum += x; } sum")
+                .unwrap();
+            assert_eq!(sum, 6);
+        });
+    }
+
+    #[test]
+    fn registry() {
+        test_with(|ctx| {
+            let a = Symbol::for_key(ctx.clone(), "app.key").unwrap();
+            let b: Symbol = ctx.eval("Symbol.for('app.key')").unwrap();
+            assert_eq!(a, b);
+            let key = a.key().unwrap().unwrap();
+            assert_eq!(key.to_string().unwrap(), "app.key");
+            assert!(Symbol::new(ctx.clone(), "app.key")
+                .unwrap()
+                .key()
+                .unwrap()
+                .is_none());
+
+            let obj = Object::new(ctx.clone()).unwrap();
+            obj.set(Symbol::to_string_tag(ctx.clone()), "Custom")
+                .unwrap();
+            ctx.globals().set("obj", obj).unwrap();
+            let tag: StdString = ctx.eval("Object.prototype.toString.call(obj)").unwrap();
+            assert_eq!(tag, "[object Custom]");
+        });
+    }
+
+    #[test]
+    fn replaced_global() {
+        test_with(|ctx| {
+            let _: () = ctx.eval("globalThis.Symbol = () => 'fake'").unwrap();
+            let symbol = Symbol::new(ctx.clone(), "local").unwrap();
+            assert_eq!(symbol.description().unwrap().to_string().unwrap(), "local");
+            let a = Symbol::for_key(ctx.clone(), "app.key").unwrap();
+            let b = Symbol::for_key(ctx.clone(), "app.key").unwrap();
+            assert_eq!(a, b);
+            assert_eq!(a.key().unwrap().unwrap().to_string().unwrap(), "app.key");
+            assert!(symbol.key().unwrap().is_none());
         });
     }
 }
@@ -166,14 +166,55 @@ impl<'js, T> TypedArray<'js, T> {
 
     /// Returns the underlying bytes of the buffer,
     ///
-    /// Returns `None` if the array is detached.
+    /// Returns `None` if the array is detached or backed by a `SharedArrayBuffer`.
     pub fn as_bytes(&self) -> Option<&[u8]> {
-        let (_, len, ptr) = Self::get_raw_bytes(self.as_value())?;
+        let (_, len, ptr, shared) = Self::get_raw_bytes(self.as_value())?;
+        if shared {
+            return None;
+        }
         Some(unsafe { slice::from_raw_parts(ptr.as_ptr(), len) })
     }
 
+    /// Returns the elements of the array
+    ///
+    /// Returns `None` if the array is detached or backed by a `SharedArrayBuffer`.
+    pub fn as_slice(&self) -> Option<&[T]>
+    where
+        T: TypedArrayItem,
+    {
+        let (len, ptr) = Self::get_raw(&self.0)?;
+        Some(unsafe { slice::from_raw_parts(ptr.as_ptr(), len) })
+    }
+
+    /// Returns the elements of the array for modification
+    ///
+    /// Returns `None` if the array is detached or backed by a `SharedArrayBuffer`. Scripts see
+    /// the changes without copying.
+    ///
+    /// # Safety
+    /// Clones of this array, other views of the same buffer and the buffer itself may access the
+    /// elements too. While the returned slice is alive no other reference to the elements may be
+    /// used, the buffer must not be detached and no JavaScript code may run.
+    pub unsafe fn as_slice_mut(&mut self) -> Option<&mut [T]>
+    where
+        T: TypedArrayItem,
+    {
+        let (len, ptr) = Self::get_raw(&self.0)?;
+        Some(slice::from_raw_parts_mut(ptr.as_ptr(), len))
+    }
+
+    /// Copy the elements of the array into a vector
+    ///
+    /// Returns `None` if the array is detached or backed by a `SharedArrayBuffer`.
+    pub fn to_vec(&self) -> Option<Vec<T>>
+    where
+        T: TypedArrayItem,
+    {
+        self.as_slice().map(|slice| slice.to_vec())
+    }
+
     pub fn as_raw(&self) -> Option<RawArrayBuffer> {
-        let (_, len, ptr) = Self::get_raw_bytes(self.as_value())?;
+        let (_, len, ptr, _) = Self::get_raw_bytes(self.as_value())?;
         Some(RawArrayBuffer { len, ptr })
     }
 
@@ -200,7 +241,9 @@ impl<'js, T> TypedArray<'js, T> {
         ctor.construct((arraybuffer,))
     }
 
-    pub(crate) fn get_raw_bytes(val: &Value<'js>) -> Option<(usize, usize, NonNull<u8>)> {
+    /// Returns the element size, the length in bytes, the pointer to the first byte and whether
+    /// the buffer is shared.
+    pub(crate) fn get_raw_bytes(val: &Value<'js>) -> Option<(usize, usize, NonNull<u8>, bool)> {
         let ctx = &val.ctx;
         let val = val.as_js_value();
         let mut off = MaybeUninit::<qjs::size_t>::uninit();
@@ -232,12 +275,13 @@ impl<'js, T> TypedArray<'js, T> {
         }
         // SAFETY: ptr was non-null and then we added an offset so it should still be non null
         let ptr = unsafe { NonNull::new_unchecked(raw.ptr.as_ptr().add(off)) };
-        Some((stp, len, ptr))
+        let shared = 0 != unsafe { qjs::JS_IsSharedArrayBuffer(buf.as_js_value()) };
+        Some((stp, len, ptr, shared))
     }
 
     pub(crate) fn get_raw(val: &Value<'js>) -> Option<(usize, NonNull<T>)> {
-        let (stp, len, ptr) = Self::get_raw_bytes(val)?;
-        if stp != mem::size_of::<T>() {
+        let (stp, len, ptr, shared) = Self::get_raw_bytes(val)?;
+        if stp != mem::size_of::<T>() || shared {
             return None;
         }
         debug_assert_eq!(ptr.as_ptr().align_offset(mem::align_of::<T>()), 0);
@@ -426,4 +470,24 @@ mod test {
             assert_eq!(val.as_bytes().unwrap(), &res)
         });
     }
+
+    #[test]
+    fn slice_access() {
+        test_with(|ctx| {
+            let mut val: TypedArray<f64> = ctx
+                .eval("globalThis.v = new Float64Array(new ArrayBuffer(32), 8, 2)")
+                .unwrap();
+            assert_eq!(val.as_slice().unwrap(), &[0.0, 0.0]);
+            unsafe { val.as_slice_mut() }
+                .unwrap()
+                .copy_from_slice(&[1.5, -2.0]);
+            let sum: f64 = ctx.eval("v[0] + v[1]").unwrap();
+            assert_eq!(sum, -0.5);
+            assert_eq!(val.to_vec().unwrap(), vec![1.5, -2.0]);
+
+            val.arraybuffer().unwrap().detach();
+            assert!(val.as_slice().is_none());
+            assert!(val.to_vec().is_none());
+        });
+    }
 }
@@ -20,6 +20,7 @@ pub(crate) struct ClassConfig {
     pub crate_: Option<String>,
     pub rename: Option<String>,
     pub rename_all: Option<Case>,
+    pub extends: Option<syn::Type>,
 }
 
 pub(crate) enum ClassOption {
@@ -27,6 +28,7 @@ pub(crate) enum ClassOption {
     Crate(ValueOption<Token![crate], LitStr>),
     Rename(ValueOption<kw::rename, LitStr>),
     RenameAll(ValueOption<kw::rename_all, Case>),
+    Extends(Box<ValueOption<kw::extends, syn::Type>>),
 }
 
 impl Parse for ClassOption {
@@ -39,6 +41,8 @@ impl Parse for ClassOption {
             input.parse().map(Self::Rename)
         } else if input.peek(kw::rename_all) {
             input.parse().map(Self::RenameAll)
+        } else if input.peek(kw::extends) {
+            input.parse().map(Box::new).map(Self::Extends)
         } else {
             Err(syn::Error::new(input.span(), "invalid class attribute"))
         }
@@ -60,6 +64,9 @@ impl ClassConfig {
             ClassOption::RenameAll(ref x) => {
                 self.rename_all = Some(x.value);
             }
+            ClassOption::Extends(ref x) => {
+                self.extends = Some(x.value.clone());
+            }
         }
     }
 
@@ -319,6 +326,30 @@ impl Class {
 
         let mutability = self.mutability();
         let props = self.expand_props(&crate_name);
+        let extends = self.config().extends.as_ref().map(|parent| {
+            quote! {
+                #crate_name::class::Class::<#parent>::register(ctx)?;
+                proto.set_prototype(#crate_name::class::Class::<#parent>::prototype(ctx.clone()).as_ref())?;
+            }
+        });
+        // Link the constructor to the constructor of the parent so static methods are inherited,
+        // preferring the constructor already defined for the parent.
+        let extends_constructor = self.config().extends.as_ref().map(|parent| {
+            quote! {
+                if let Some(constr) = constr.as_ref() {
+                    let parent_proto = #crate_name::class::Class::<#parent>::prototype(ctx.clone());
+                    let parent_constr = match parent_proto {
+                        Some(parent_proto) if parent_proto.has_own(#crate_name::atom::PredefinedAtom::Constructor)? => {
+                            Some(parent_proto.get::<_, #crate_name::Object>(#crate_name::atom::PredefinedAtom::Constructor)?)
+                        }
+                        _ => #crate_name::class::Class::<#parent>::create_constructor(ctx)?.map(|x| x.into_inner().into_inner()),
+                    };
+                    if let Some(parent_constr) = parent_constr {
+                        constr.set_prototype(Some(&parent_constr))?;
+                    }
+                }
+            }
+        });
         let reexpand = self.reexpand();
 
         quote! {
@@ -345,6 +376,7 @@ impl Class {
                         #props
                         let implementor = #crate_name::class::impl_::MethodImpl::<Self>::new();
                         (&implementor).implement(&proto)?;
+                        #extends
                         Ok(Some(proto))
                     }
 
@@ -352,7 +384,9 @@ impl Class {
                         use #crate_name::class::impl_::ConstructorCreator;
 
                         let implementor = #crate_name::class::impl_::ConstructorCreate::<Self>::new();
-                        (&implementor).create_constructor(ctx)
+                        let constr = (&implementor).create_constructor(ctx)?;
+                        #extends_constructor
+                        Ok(constr)
                     }
                 }
 
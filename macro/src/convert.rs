@@ -0,0 +1,462 @@
+use convert_case::Casing;
+use proc_macro2::{Ident, TokenStream};
+use proc_macro_error::abort;
+use quote::{format_ident, quote};
+use syn::{
+    ext::IdentExt,
+    parse::{Parse, ParseStream},
+    parse_quote, Data, DataEnum, DataStruct, DeriveInput, Generics, LitStr, Token,
+};
+
+use crate::{
+    attrs::{take_attributes, OptionList, ValueOption},
+    common::{add_js_lifetime, crate_ident, kw, AbortResultExt, Case},
+    fields::{Field, Fields},
+};
+
+/// The property holding the variant name of enums converted as tagged objects.
+const DEFAULT_TAG: &str = "type";
+
+#[derive(Debug, Default)]
+pub(crate) struct ConvertConfig {
+    pub crate_: Option<String>,
+    pub rename_all: Option<Case>,
+    pub tag: Option<String>,
+}
+
+pub(crate) enum ConvertOption {
+    Crate(ValueOption<Token![crate], LitStr>),
+    RenameAll(ValueOption<kw::rename_all, Case>),
+    Tag(ValueOption<kw::tag, LitStr>),
+}
+
+impl Parse for ConvertOption {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Token![crate]) {
+            input.parse().map(Self::Crate)
+        } else if input.peek(kw::rename_all) {
+            input.parse().map(Self::RenameAll)
+        } else if input.peek(kw::tag) {
+            input.parse().map(Self::Tag)
+        } else {
+            Err(syn::Error::new(
+                input.span(),
+                "invalid conversion attribute",
+            ))
+        }
+    }
+}
+
+impl ConvertConfig {
+    pub fn from_attributes(attrs: &mut Vec<syn::Attribute>) -> Self {
+        let mut config = Self::default();
+        take_attributes(attrs, |attr| {
+            if !attr.path().is_ident("qjs") {
+                return Ok(false);
+            }
+
+            let options: OptionList<ConvertOption> = attr.parse_args()?;
+            options.0.iter().for_each(|x| config.apply(x));
+            Ok(true)
+        })
+        .unwrap_or_abort();
+        config
+    }
+
+    pub fn apply(&mut self, option: &ConvertOption) {
+        match option {
+            ConvertOption::Crate(ref x) => {
+                self.crate_ = Some(x.value.value());
+            }
+            ConvertOption::RenameAll(ref x) => {
+                self.rename_all = Some(x.value);
+            }
+            ConvertOption::Tag(ref x) => {
+                self.tag = Some(x.value.value());
+            }
+        }
+    }
+
+    pub fn crate_name(&self) -> String {
+        self.crate_.clone().unwrap_or_else(crate_ident)
+    }
+
+    pub fn tag(&self) -> String {
+        self.tag.clone().unwrap_or_else(|| DEFAULT_TAG.to_string())
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct VariantConfig {
+    pub rename: Option<String>,
+}
+
+impl VariantConfig {
+    pub fn from_attributes(attrs: &mut Vec<syn::Attribute>) -> Self {
+        let mut config = Self::default();
+        take_attributes(attrs, |attr| {
+            if !attr.path().is_ident("qjs") {
+                return Ok(false);
+            }
+
+            let options: OptionList<ValueOption<kw::rename, LitStr>> = attr.parse_args()?;
+            options
+                .0
+                .iter()
+                .for_each(|x| config.rename = Some(x.value.value()));
+            Ok(true)
+        })
+        .unwrap_or_abort();
+        config
+    }
+}
+
+/// A variant of an enum together with its JavaScript name.
+struct Variant {
+    ident: Ident,
+    name: String,
+    fields: Fields,
+}
+
+impl Variant {
+    fn from_variant(mut variant: syn::Variant, case: Option<Case>) -> Self {
+        let config = VariantConfig::from_attributes(&mut variant.attrs);
+        let name = config
+            .rename
+            .unwrap_or_else(|| js_name(&variant.ident, case));
+        Variant {
+            ident: variant.ident,
+            name,
+            fields: Fields::from_fields(variant.fields),
+        }
+    }
+}
+
+/// The input of a conversion derive.
+enum Shape {
+    Struct(Fields),
+    StringEnum(Vec<Variant>),
+    TaggedEnum(Vec<Variant>),
+}
+
+impl Shape {
+    fn from_data(data: Data, config: &ConvertConfig) -> Self {
+        match data {
+            Data::Struct(DataStruct { fields, .. }) => Shape::Struct(Fields::from_fields(fields)),
+            Data::Enum(DataEnum { variants, .. }) => {
+                let variants = variants
+                    .into_iter()
+                    .map(|x| Variant::from_variant(x, config.rename_all))
+                    .collect::<Vec<_>>();
+                if variants.iter().all(|x| matches!(x.fields, Fields::Unit)) {
+                    Shape::StringEnum(variants)
+                } else {
+                    Shape::TaggedEnum(variants)
+                }
+            }
+            Data::Union(u) => {
+                abort!(
+                    u.union_token,
+                    "deriving conversions for unions is not supported"
+                )
+            }
+        }
+    }
+}
+
+fn js_name(ident: &Ident, case: Option<Case>) -> String {
+    let name = ident.unraw().to_string();
+    if let Some(case) = case {
+        name.to_case(case.to_convert_case())
+    } else {
+        name
+    }
+}
+
+fn field_name(field: &Field, case: Option<Case>) -> String {
+    if let Some(rename) = field.config.rename.clone() {
+        rename
+    } else {
+        js_name(field.ident.as_ref().unwrap(), case)
+    }
+}
+
+/// Expands the impl header, requiring all type parameters to implement the trait as well.
+fn impl_header(generics: &Generics, ident: &Ident, trait_: TokenStream) -> TokenStream {
+    let mut lifetime_generics = add_js_lifetime(generics);
+    let where_clause = lifetime_generics.make_where_clause();
+    for param in generics.type_params() {
+        let param = &param.ident;
+        where_clause.predicates.push(parse_quote!(#param: #trait_));
+    }
+    let (impl_generics, _, where_clause) = lifetime_generics.split_for_impl();
+    let (_, ty_generics, _) = generics.split_for_impl();
+    quote! {
+        impl #impl_generics #trait_ for #ident #ty_generics #where_clause
+    }
+}
+
+/// Expands the expression reading a field from `object`, skipped fields use their `Default`.
+fn expand_get_field(field: &Field, name: &str) -> TokenStream {
+    let ty = &field.ty;
+    if field.config.skip {
+        quote! {
+            ::std::default::Default::default()
+        }
+    } else if field.config.default {
+        quote! {
+            object.get::<_, ::std::option::Option<#ty>>(#name)?.unwrap_or_default()
+        }
+    } else {
+        quote! {
+            object.get::<_, #ty>(#name)?
+        }
+    }
+}
+
+fn expand_object_from_js(crate_name: &Ident) -> TokenStream {
+    quote! {
+        let object = <#crate_name::Object<'js> as #crate_name::FromJs<'js>>::from_js(ctx, value)?;
+    }
+}
+
+fn unsupported_skipped_newtype(field: &Field) {
+    if field.config.skip {
+        abort!(
+            field.ty,
+            "the only field of a tuple struct with one field can't be skipped"
+        )
+    }
+}
+
+fn unsupported_tuple_variant(variant: &Variant) -> ! {
+    abort!(
+        variant.ident,
+        "deriving conversions is not supported for tuple variants"
+    )
+}
+
+pub(crate) fn expand_from_js(mut input: DeriveInput) -> TokenStream {
+    let config = ConvertConfig::from_attributes(&mut input.attrs);
+    let crate_name = format_ident!("{}", config.crate_name());
+    let ident = input.ident;
+    let type_name = ident.to_string();
+    let header = impl_header(&input.generics, &ident, quote!(#crate_name::FromJs<'js>));
+
+    let body = match Shape::from_data(input.data, &config) {
+        Shape::Struct(Fields::Named(fields)) => {
+            let object = expand_object_from_js(&crate_name);
+            let fields = fields.iter().map(|field| {
+                let ident = field.ident.as_ref().unwrap();
+                let get = expand_get_field(field, &field_name(field, config.rename_all));
+                quote!(#ident: #get)
+            });
+            quote! {
+                #object
+                Ok(Self { #(#fields,)* })
+            }
+        }
+        Shape::Struct(Fields::Unnamed(fields)) if fields.len() == 1 => {
+            unsupported_skipped_newtype(&fields[0]);
+            let ty = &fields[0].ty;
+            quote! {
+                Ok(Self(<#ty as #crate_name::FromJs<'js>>::from_js(ctx, value)?))
+            }
+        }
+        Shape::Struct(Fields::Unnamed(fields)) => {
+            let mut idx = 0usize;
+            let fields = fields.iter().map(|field| {
+                if field.config.skip {
+                    return quote!(::std::default::Default::default());
+                }
+                let ty = &field.ty;
+                idx += 1;
+                let idx = idx - 1;
+                quote!(array.get::<#ty>(#idx)?)
+            });
+            quote! {
+                let array = <#crate_name::Array<'js> as #crate_name::FromJs<'js>>::from_js(ctx, value)?;
+                Ok(Self(#(#fields,)*))
+            }
+        }
+        Shape::Struct(Fields::Unit) => {
+            abort!(
+                ident,
+                "deriving conversions is not supported for unit structs"
+            )
+        }
+        Shape::StringEnum(variants) => {
+            let arms = variants.iter().map(|Variant { ident, name, .. }| {
+                quote! {
+                    #name => Ok(Self::#ident),
+                }
+            });
+            quote! {
+                let name = <::std::string::String as #crate_name::FromJs>::from_js(ctx, value)?;
+                match name.as_str() {
+                    #(#arms)*
+                    _ => Err(#crate_name::Error::new_from_js_message(
+                        "string",
+                        #type_name,
+                        ::std::format!("Unknown variant '{}'", name),
+                    )),
+                }
+            }
+        }
+        Shape::TaggedEnum(variants) => {
+            let object = expand_object_from_js(&crate_name);
+            let tag = config.tag();
+            let arms = variants.iter().map(|variant| {
+                let Variant { ident, name, .. } = variant;
+                match variant.fields {
+                    Fields::Named(ref fields) => {
+                        let fields = fields.iter().map(|field| {
+                            let ident = field.ident.as_ref().unwrap();
+                            let get = expand_get_field(field, &field_name(field, None));
+                            quote!(#ident: #get)
+                        });
+                        quote! {
+                            #name => Ok(Self::#ident { #(#fields,)* }),
+                        }
+                    }
+                    Fields::Unit => quote! {
+                        #name => Ok(Self::#ident),
+                    },
+                    Fields::Unnamed(_) => unsupported_tuple_variant(variant),
+                }
+            });
+            quote! {
+                #object
+                let name: ::std::string::String = object.get(#tag)?;
+                match name.as_str() {
+                    #(#arms)*
+                    _ => Err(#crate_name::Error::new_from_js_message(
+                        "object",
+                        #type_name,
+                        ::std::format!("Unknown variant '{}'", name),
+                    )),
+                }
+            }
+        }
+    };
+
+    quote! {
+        #header {
+            fn from_js(ctx: &#crate_name::Ctx<'js>, value: #crate_name::Value<'js>) -> #crate_name::Result<Self> {
+                #body
+            }
+        }
+    }
+}
+
+pub(crate) fn expand_into_js(mut input: DeriveInput) -> TokenStream {
+    let config = ConvertConfig::from_attributes(&mut input.attrs);
+    let crate_name = format_ident!("{}", config.crate_name());
+    let ident = input.ident;
+    let header = impl_header(&input.generics, &ident, quote!(#crate_name::IntoJs<'js>));
+
+    let body = match Shape::from_data(input.data, &config) {
+        Shape::Struct(Fields::Named(fields)) => {
+            let fields = fields
+                .iter()
+                .filter(|field| !field.config.skip)
+                .map(|field| {
+                    let ident = field.ident.as_ref().unwrap();
+                    let name = field_name(field, config.rename_all);
+                    quote!(object.set(#name, self.#ident)?;)
+                });
+            quote! {
+                let object = #crate_name::Object::new(ctx.clone())?;
+                #(#fields)*
+                Ok(object.into_value())
+            }
+        }
+        Shape::Struct(Fields::Unnamed(fields)) if fields.len() == 1 => {
+            unsupported_skipped_newtype(&fields[0]);
+            quote! {
+                #crate_name::IntoJs::into_js(self.0, ctx)
+            }
+        }
+        Shape::Struct(Fields::Unnamed(fields)) => {
+            let fields = fields
+                .iter()
+                .enumerate()
+                .filter(|(_, field)| !field.config.skip)
+                .enumerate()
+                .map(|(idx, (field_idx, _))| {
+                    let field = syn::Index::from(field_idx);
+                    quote!(array.set(#idx, self.#field)?;)
+                });
+            quote! {
+                let array = #crate_name::Array::new(ctx.clone())?;
+                #(#fields)*
+                Ok(array.into_value())
+            }
+        }
+        Shape::Struct(Fields::Unit) => {
+            abort!(
+                ident,
+                "deriving conversions is not supported for unit structs"
+            )
+        }
+        Shape::StringEnum(variants) => {
+            let arms = variants.iter().map(|Variant { ident, name, .. }| {
+                quote! {
+                    Self::#ident => #name,
+                }
+            });
+            quote! {
+                let name: &str = match self {
+                    #(#arms)*
+                };
+                #crate_name::IntoJs::into_js(name, ctx)
+            }
+        }
+        Shape::TaggedEnum(variants) => {
+            let tag = config.tag();
+            let arms = variants.iter().map(|variant| {
+                let Variant { ident, name, .. } = variant;
+                match variant.fields {
+                    Fields::Named(ref fields) => {
+                        let fields = fields
+                            .iter()
+                            .filter(|field| !field.config.skip)
+                            .collect::<Vec<_>>();
+                        let bindings = (0..fields.len())
+                            .map(|idx| format_ident!("field_{}", idx))
+                            .collect::<Vec<_>>();
+                        let idents = fields.iter().map(|field| field.ident.as_ref().unwrap());
+                        let names = fields.iter().map(|field| field_name(field, None));
+                        quote! {
+                            Self::#ident { #(#idents: #bindings,)* .. } => {
+                                object.set(#tag, #name)?;
+                                #(object.set(#names, #bindings)?;)*
+                            }
+                        }
+                    }
+                    Fields::Unit => quote! {
+                        Self::#ident => {
+                            object.set(#tag, #name)?;
+                        }
+                    },
+                    Fields::Unnamed(_) => unsupported_tuple_variant(variant),
+                }
+            });
+            quote! {
+                let object = #crate_name::Object::new(ctx.clone())?;
+                match self {
+                    #(#arms)*
+                }
+                Ok(object.into_value())
+            }
+        }
+    };
+
+    quote! {
+        #header {
+            fn into_js(self, ctx: &#crate_name::Ctx<'js>) -> #crate_name::Result<#crate_name::Value<'js>> {
+                #body
+            }
+        }
+    }
+}
@@ -45,6 +45,7 @@ impl Parse for EmbedModules {
 /// Implementation of the macro
 pub fn embed(modules: EmbedModules) -> TokenStream {
     let mut files = Vec::new();
+    let mut paths = Vec::new();
     for f in modules.0.into_iter() {
         let path = f
             .path
@@ -86,6 +87,7 @@ pub fn embed(modules: EmbedModules) -> TokenStream {
             }
         };
         files.push((f.name.value(), source));
+        paths.push(path.display().to_string());
     }
 
     let res = (|| -> Result<Vec<(String, Vec<u8>)>> {
@@ -114,8 +116,15 @@ pub fn embed(modules: EmbedModules) -> TokenStream {
     };
 
     let res = to_entries(res.into_iter());
+    let bundle = expand(&res);
 
-    expand(&res)
+    // Include the sources so cargo recompiles the bundle when one of them changes.
+    quote! {
+        {
+            #(const _: &[u8] = include_bytes!(#paths);)*
+            #bundle
+        }
+    }
 }
 
 fn to_entries(modules: impl Iterator<Item = (String, Vec<u8>)>) -> Vec<(String, TokenStream)> {
@@ -19,6 +19,8 @@ pub struct FieldConfig {
     pub configurable: bool,
     pub skip_trace: bool,
     pub rename: Option<String>,
+    pub default: bool,
+    pub skip: bool,
 }
 
 #[derive(Debug)]
@@ -29,6 +31,8 @@ pub(crate) enum FieldOption {
     Configurable(FlagOption<kw::configurable>),
     SkipTrace(FlagOption<kw::skip_trace>),
     Rename(ValueOption<kw::rename, LitStr>),
+    Default(FlagOption<kw::default>),
+    Skip(FlagOption<kw::skip>),
 }
 
 impl Parse for FieldOption {
@@ -45,6 +49,10 @@ impl Parse for FieldOption {
             input.parse().map(Self::SkipTrace)
         } else if input.peek(kw::rename) {
             input.parse().map(Self::Rename)
+        } else if input.peek(kw::default) {
+            input.parse().map(Self::Default)
+        } else if input.peek(kw::skip) {
+            input.parse().map(Self::Skip)
         } else {
             Err(syn::Error::new(
                 input.span(),
@@ -92,6 +100,12 @@ impl FieldConfig {
             FieldOption::Rename(ref x) => {
                 self.rename = Some(x.value.value());
             }
+            FieldOption::Default(ref x) => {
+                self.default = x.is_true();
+            }
+            FieldOption::Skip(ref x) => {
+                self.skip = x.is_true();
+            }
         }
     }
 }
@@ -19,6 +19,7 @@ macro_rules! assert_eq_tokens {
 mod attrs;
 mod class;
 mod common;
+mod convert;
 mod embed;
 mod fields;
 mod function;
@@ -42,6 +43,7 @@ mod trace;
 /// | `rename`     | String    | Changes the name of the implemented class on the JavaScript side.                                                                                                                       |
 /// | `rename_all` | Casing    | Converts the case of all the fields of this struct which have implement accessors. Can be one of `lowercase`, `UPPERCASE`, `camelCase`, `PascalCase`,`snake_case`, or `SCREAMING_SNAKE` |
 /// | `frozen`     | Flag      | Changes the class implementation to only allow borrowing immutably.  Trying to borrow mutably will result in an error.                                                                  |
+/// | `extends`    | Type      | Links the prototype and the constructor of the class to the ones of another Rust class so `instanceof` checks for the parent class, methods defined on its prototype in JavaScript and its static methods apply. Only the prototype chain is linked: the constructor of the child doesn't run the constructor of the parent and instances only hold the Rust data of the child, so accessors and methods of the parent implemented in Rust throw for them. |
 ///
 /// # Field options
 ///
@@ -480,6 +482,99 @@ pub fn trace(stream: TokenStream1) -> TokenStream1 {
     trace::expand(derive_input).into()
 }
 
+/// A macro for deriving [`FromJs`](rquickjs_core::FromJs) for structs and enums.
+///
+/// The shape of the JavaScript value depends on the type:
+///
+/// - Structs with named fields are converted from objects, reading each field from the property
+/// with the same name.
+/// - Tuple structs with a single field are converted like the field itself, other tuple structs
+/// are converted from arrays.
+/// - Enums with only unit variants are converted from a string with the name of the variant.
+/// - Other enums are converted from tagged objects: the variant is taken from the `type`
+/// property and the fields of the variant from the other properties of the object.
+///
+/// Converting a value which doesn't match any variant results in an error. Fields of type
+/// `Option<T>` are `None` if the property is absent.
+///
+/// # Attribute options
+///
+/// | **Option**   | **Value** | **Description**                                                                                                                                                      |
+/// |--------------|-----------|----------------------------------------------------------------------------------------------------------------------------------------------------------------------|
+/// | `crate`      | String    | Changes the name from which the macro tries to use rquickjs types.                                                                                                  |
+/// | `rename_all` | Casing    | Converts the case of all the struct fields or enum variant names. Can be one of `lowercase`, `UPPERCASE`, `camelCase`, `PascalCase`,`snake_case`, or `SCREAMING_SNAKE` |
+/// | `tag`        | String    | Changes the name of the property holding the variant of a tagged enum.                                                                                             |
+///
+/// # Field and variant options
+///
+/// | **Option** | **Value** | **Description**                                                                          |
+/// |------------|-----------|------------------------------------------------------------------------------------------|
+/// | `rename`   | String    | Changes the name of the property of a field or the name of a variant.                   |
+/// | `default`  | Flag      | Uses the `Default` value of the field if the property is absent, `undefined` or `null`. |
+/// | `skip`     | Flag      | Ignores the field, which is set to its `Default` value when converting from JavaScript. |
+///
+/// # Example
+/// ```
+/// use rquickjs::{Context, FromJs, IntoJs, Runtime};
+///
+/// #[derive(FromJs, IntoJs, Debug, PartialEq)]
+/// #[qjs(rename_all = "lowercase")]
+/// enum Color {
+///     Red,
+///     Green,
+///     #[qjs(rename = "sky")]
+///     Blue,
+/// }
+///
+/// #[derive(FromJs, IntoJs, Debug, PartialEq)]
+/// #[qjs(rename_all = "camelCase")]
+/// struct Config {
+///     display_name: String,
+///     color: Color,
+///     #[qjs(default)]
+///     retries: u32,
+///     #[qjs(skip)]
+///     cache: Vec<u8>,
+/// }
+///
+/// #[derive(FromJs, IntoJs, Debug, PartialEq)]
+/// enum Shape {
+///     Circle { radius: f64 },
+///     Empty,
+/// }
+///
+/// let rt = Runtime::new().unwrap();
+/// let ctx = Context::full(&rt).unwrap();
+/// ctx.with(|ctx| {
+///     let color: Color = ctx.eval("'green'").unwrap();
+///     assert_eq!(color, Color::Green);
+///     assert!(ctx.eval::<Color, _>("'purple'").is_err());
+///
+///     let config: Config = ctx.eval("({ displayName: 'worker', color: 'sky' })").unwrap();
+///     assert_eq!(config.retries, 0);
+///
+///     let shape: Shape = ctx.eval("({ type: 'Circle', radius: 2 })").unwrap();
+///     assert_eq!(shape, Shape::Circle { radius: 2.0 });
+/// });
+/// ```
+#[proc_macro_derive(FromJs, attributes(qjs))]
+#[proc_macro_error]
+pub fn from_js(stream: TokenStream1) -> TokenStream1 {
+    let derive_input = parse_macro_input!(stream as DeriveInput);
+    convert::expand_from_js(derive_input).into()
+}
+
+/// A macro for deriving [`IntoJs`](rquickjs_core::IntoJs) for structs and enums.
+///
+/// Produces values of the same shape as the ones accepted by the [`FromJs`](macro@FromJs)
+/// derive and takes the same options.
+#[proc_macro_derive(IntoJs, attributes(qjs))]
+#[proc_macro_error]
+pub fn into_js(stream: TokenStream1) -> TokenStream1 {
+    let derive_input = parse_macro_input!(stream as DeriveInput);
+    convert::expand_into_js(derive_input).into()
+}
+
 /// A macro for embedding JavaScript code into a binary.
 ///
 /// Compiles a JavaScript module to bytecode and then compiles the resulting bytecode into the
@@ -487,6 +582,8 @@ pub fn trace(stream: TokenStream1) -> TokenStream1 {
 /// files to be compiled into a module with an option name. Module paths are relative to the crate
 /// manifest file.
 ///
+/// The files are tracked by cargo, so the bundle is compiled again when one of them changes.
+///
 /// # Usage
 ///
 /// ```
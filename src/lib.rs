@@ -15,7 +15,9 @@
 //!
 //! As both [`Runtime`] and [`Context`] use a lock it is discouraged to use them in a async
 //! environment. Instead, when the `futures` feature is enabled this library also exposes
-//! [`AsyncRuntime`] and [`AsyncContext`] which use a future aware lock.
+//! [`AsyncRuntime`] and [`AsyncContext`] which use a future aware lock. They work with any
+//! executor, like tokio or async-std, by spawning the future returned from
+//! [`AsyncRuntime::drive`] or awaiting [`AsyncRuntime::idle`] to run pending jobs.
 //!
 //! # Converting Values
 //!
@@ -57,7 +59,12 @@
 //!
 //! - `dyn-load` adds support for loadable native modules (so/dll/dylib).
 //!
-//! - `array-buffer` adds support for [`ArrayBuffer`] and [`TypedArray`].
+//! - `http` adds [`HttpResolver`](loader::HttpResolver) and [`HttpLoader`](loader::HttpLoader)
+//!   which load modules from allowed `https://` URLs through a transport provided by the
+//!   application.
+//!
+//! - `array-buffer` adds support for [`ArrayBuffer`], including shared ones backed by
+//!   [`SharedMemory`], and [`TypedArray`].
 //!
 //! - `futures` adds support for async Rust. When enabled the library exports [`AsyncRuntime`] and
 //! [`AsyncContext`]. These are the asynchronous variants of the normal runtime and context. In
@@ -85,6 +92,14 @@
 //! - `indexmap` adds [`FromJs`]/[`IntoJs`] implementations for [`IndexSet`](`indexmap::IndexSet`)
 //! and [`IndexMap`](`indexmap_rs::IndexMap`)
 //!
+//! - `num-bigint` adds conversions between [`BigInt`] and arbitrary precision integers of the
+//!   `num-bigint` crate
+//!
+//! - `serde` adds the [`js_serde`] module which converts types implementing `Serialize` and
+//!   `Deserialize` directly to and from JavaScript values
+//!
+//! - `serde_json` adds [`FromJs`]/[`IntoJs`] implementations for `serde_json::Value`
+//!
 //! ## Bindings
 //!
 //! The bindings are pre-generated for the following platforms:
@@ -135,7 +150,7 @@ pub use rquickjs_core::*;
 
 #[cfg_attr(feature = "doc-cfg", doc(cfg(feature = "macro")))]
 #[cfg(feature = "macro")]
-pub use rquickjs_macro::{class, embed, function, methods, module};
+pub use rquickjs_macro::{class, embed, function, methods, module, FromJs, IntoJs};
 
 pub mod class {
     //! JavaScript classes defined from Rust.
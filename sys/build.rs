@@ -144,10 +144,19 @@ fn main() {
     let mut patch_files = vec![
         "error_column_number.patch",
         "get_function_proto.patch",
+        "promise_state.patch",
+        "value_equality.patch",
+        "class_checks.patch",
+        "private_symbol.patch",
+        "out_of_memory_flag.patch",
         "check_stack_overflow.patch",
         "infinity_handling.patch",
         "atomic_new_class_id.patch",
         "dynamic_import_sync.patch",
+        "pending_job_count.patch",
+        "new_proxy.patch",
+        "big_int_from_string.patch",
+        "symbols.patch",
     ];
 
     let mut defines = vec![
@@ -1265,6 +1265,9 @@ extern "C" {
 extern "C" {
     pub fn JS_ThrowOutOfMemory(ctx: *mut JSContext) -> JSValue;
 }
+extern "C" {
+    pub fn JS_TakeOutOfMemory(rt: *mut JSRuntime) -> ::std::os::raw::c_int;
+}
 extern "C" {
     pub fn __JS_FreeValue(ctx: *mut JSContext, v: JSValue);
 }
@@ -1532,6 +1535,68 @@ extern "C" {
 extern "C" {
     pub fn JS_GetFunctionProto(ctx: *mut JSContext) -> JSValue;
 }
+extern "C" {
+    pub fn JS_PromiseState(ctx: *mut JSContext, promise: JSValue) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn JS_PromiseResult(ctx: *mut JSContext, promise: JSValue) -> JSValue;
+}
+extern "C" {
+    pub fn JS_NewPrivateSymbol(
+        ctx: *mut JSContext,
+        description: *const ::std::os::raw::c_char,
+    ) -> JSValue;
+}
+extern "C" {
+    pub fn JS_NewProxy(ctx: *mut JSContext, target: JSValue, handler: JSValue) -> JSValue;
+}
+extern "C" {
+    pub fn JS_NewBigIntFromString(
+        ctx: *mut JSContext,
+        str_: *const ::std::os::raw::c_char,
+    ) -> JSValue;
+}
+extern "C" {
+    pub fn JS_NewSymbolFromString(
+        ctx: *mut JSContext,
+        description: *const ::std::os::raw::c_char,
+        global: ::std::os::raw::c_int,
+    ) -> JSValue;
+}
+extern "C" {
+    pub fn JS_GetSymbolKey(ctx: *mut JSContext, symbol: JSValue) -> JSValue;
+}
+extern "C" {
+    pub fn JS_StrictEq(ctx: *mut JSContext, op1: JSValue, op2: JSValue) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn JS_LooseEq(ctx: *mut JSContext, op1: JSValue, op2: JSValue) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn JS_SameValue(ctx: *mut JSContext, op1: JSValue, op2: JSValue) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn JS_SameValueZero(
+        ctx: *mut JSContext,
+        op1: JSValue,
+        op2: JSValue,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn JS_IsSharedArrayBuffer(val: JSValue) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn JS_IsMap(val: JSValue) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn JS_IsSet(val: JSValue) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn JS_IsRegExp(val: JSValue) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn JS_IsDate(val: JSValue) -> ::std::os::raw::c_int;
+}
 extern "C" {
     pub fn JS_IsInstanceOf(
         ctx: *mut JSContext,
@@ -1832,6 +1897,9 @@ extern "C" {
 extern "C" {
     pub fn JS_IsJobPending(rt: *mut JSRuntime) -> ::std::os::raw::c_int;
 }
+extern "C" {
+    pub fn JS_PendingJobCount(rt: *mut JSRuntime) -> ::std::os::raw::c_int;
+}
 extern "C" {
     pub fn JS_ExecutePendingJob(
         rt: *mut JSRuntime,
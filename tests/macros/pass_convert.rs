@@ -0,0 +1,167 @@
+use rquickjs::{Context, FromJs, IntoJs, Object, Runtime, Value};
+
+#[derive(FromJs, IntoJs, Debug, PartialEq, Clone, Copy)]
+#[qjs(rename_all = "lowercase")]
+pub enum Color {
+    Red,
+    Green,
+    #[qjs(rename = "sky-blue")]
+    Blue,
+}
+
+#[derive(FromJs, IntoJs, Debug, PartialEq)]
+pub enum Verbatim {
+    FooBar,
+}
+
+#[derive(FromJs, IntoJs, Debug, PartialEq)]
+#[qjs(rename_all = "camelCase")]
+pub struct Config {
+    display_name: String,
+    color: Color,
+    #[qjs(rename = "max")]
+    limit: u32,
+    #[qjs(default)]
+    retries: u32,
+    comment: Option<String>,
+    r#type: Vec<i32>,
+    #[qjs(skip)]
+    cache: Vec<u8>,
+}
+
+#[derive(FromJs, IntoJs, Debug, PartialEq)]
+pub struct Meters(f64);
+
+#[derive(FromJs, IntoJs, Debug, PartialEq)]
+pub struct Pair<T>(T, T);
+
+#[derive(FromJs, IntoJs, Debug, PartialEq)]
+pub struct Labeled(String, #[qjs(skip)] u32, i32);
+
+#[derive(FromJs, IntoJs, Debug, PartialEq)]
+pub enum Shape {
+    Circle {
+        radius: f64,
+    },
+    Rect {
+        width: f64,
+        height: f64,
+        #[qjs(default)]
+        rounded: bool,
+    },
+    Empty,
+}
+
+#[derive(FromJs, IntoJs, Debug, PartialEq)]
+#[qjs(tag = "kind", rename_all = "snake_case")]
+pub enum Event<'js> {
+    KeyDown { key: String },
+    Custom { payload: Object<'js> },
+}
+
+fn main() {
+    let rt = Runtime::new().unwrap();
+    let ctx = Context::full(&rt).unwrap();
+
+    ctx.with(|ctx| {
+        let color: Color = ctx.eval("'green'").unwrap();
+        assert_eq!(color, Color::Green);
+
+        let value = Color::Green.into_js(&ctx).unwrap();
+        assert_eq!(value.as_string().unwrap().to_string().unwrap(), "green");
+
+        let value = Color::Blue.into_js(&ctx).unwrap();
+        assert_eq!(value.as_string().unwrap().to_string().unwrap(), "sky-blue");
+        assert_eq!(Color::from_js(&ctx, value).unwrap(), Color::Blue);
+
+        assert!(ctx.eval::<Color, _>("'purple'").is_err());
+        assert!(ctx.eval::<Color, _>("'Green'").is_err());
+        assert!(ctx.eval::<Color, _>("1").is_err());
+
+        let value: Value = ctx.eval("'FooBar'").unwrap();
+        assert_eq!(Verbatim::from_js(&ctx, value).unwrap(), Verbatim::FooBar);
+
+        let config: Config = ctx
+            .eval("({ displayName: 'test', color: 'red', max: 3, type: [1, 2], cache: 'x' })")
+            .unwrap();
+        assert_eq!(
+            config,
+            Config {
+                display_name: "test".to_string(),
+                color: Color::Red,
+                limit: 3,
+                retries: 0,
+                comment: None,
+                r#type: vec![1, 2],
+                cache: Vec::new(),
+            }
+        );
+        assert!(ctx.eval::<Config, _>("({ color: 'red', max: 3 })").is_err());
+
+        ctx.globals().set("config", config).unwrap();
+        let res: bool = ctx
+            .eval(
+                "config.displayName === 'test' && config.color === 'red' && config.max === 3 \
+                 && config.retries === 0 && config.comment === undefined && config.type[1] === 2 \
+                 && !('cache' in config)",
+            )
+            .unwrap();
+        assert!(res);
+
+        let meters: Meters = ctx.eval("1.5").unwrap();
+        assert_eq!(meters, Meters(1.5));
+        let pair: Pair<i32> = ctx.eval("[1, 2]").unwrap();
+        assert_eq!(pair, Pair(1, 2));
+        let value = pair.into_js(&ctx).unwrap();
+        assert_eq!(Vec::<i32>::from_js(&ctx, value).unwrap(), vec![1, 2]);
+
+        let labeled: Labeled = ctx.eval("['a', 2]").unwrap();
+        assert_eq!(labeled, Labeled("a".to_string(), 0, 2));
+        let value = Labeled("b".to_string(), 7, 3).into_js(&ctx).unwrap();
+        let array = rquickjs::Array::from_value(value).unwrap();
+        assert_eq!(array.len(), 2);
+        assert_eq!(array.get::<i32>(1).unwrap(), 3);
+
+        let shape: Shape = ctx
+            .eval("({ type: 'Rect', width: 2, height: 3 })")
+            .unwrap();
+        assert_eq!(
+            shape,
+            Shape::Rect {
+                width: 2.0,
+                height: 3.0,
+                rounded: false
+            }
+        );
+        let shape: Shape = ctx.eval("({ type: 'Empty' })").unwrap();
+        assert_eq!(shape, Shape::Empty);
+        assert!(ctx.eval::<Shape, _>("({ type: 'Triangle' })").is_err());
+
+        ctx.globals()
+            .set("shape", Shape::Circle { radius: 1.0 })
+            .unwrap();
+        let res: bool = ctx
+            .eval("shape.type === 'Circle' && shape.radius === 1")
+            .unwrap();
+        assert!(res);
+
+        let event: Event = ctx
+            .eval("({ kind: 'custom', payload: { a: 1 } })")
+            .unwrap();
+        match event {
+            Event::Custom { payload } => assert_eq!(payload.get::<_, i32>("a").unwrap(), 1),
+            _ => panic!("expected custom event"),
+        }
+        let value = Event::KeyDown {
+            key: "a".to_string(),
+        }
+        .into_js(&ctx)
+        .unwrap();
+        let res: bool = ctx
+            .eval::<rquickjs::Function, _>("(e) => e.kind === 'key_down' && e.key === 'a'")
+            .unwrap()
+            .call((value,))
+            .unwrap();
+        assert!(res);
+    });
+}
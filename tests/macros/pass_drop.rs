@@ -0,0 +1,59 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use rquickjs::{class::Trace, CatchResultExt, Class, Context, Runtime};
+
+#[derive(Trace)]
+#[rquickjs::class]
+pub struct Resource {
+    #[qjs(skip_trace)]
+    drops: Arc<AtomicUsize>,
+}
+
+impl Drop for Resource {
+    fn drop(&mut self) {
+        self.drops.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[rquickjs::methods]
+impl Resource {
+    #[qjs(get)]
+    pub fn alive(&self) -> bool {
+        true
+    }
+}
+
+pub fn main() {
+    let drops = Arc::new(AtomicUsize::new(0));
+    let rt = Runtime::new().unwrap();
+    let ctx = Context::full(&rt).unwrap();
+
+    ctx.with(|ctx| {
+        Class::<Resource>::register(&ctx).unwrap();
+        for _ in 0..3 {
+            let resource = Class::instance(
+                ctx.clone(),
+                Resource {
+                    drops: drops.clone(),
+                },
+            )
+            .unwrap();
+            ctx.globals().set("resource", resource).unwrap();
+            ctx.eval::<(), _>(
+                r#"
+                if(!resource.alive){
+                    throw new Error("getter should be available")
+                }
+                globalThis.resource = undefined;
+            "#,
+            )
+            .catch(&ctx)
+            .unwrap();
+        }
+    });
+    rt.run_gc();
+    assert_eq!(drops.load(Ordering::SeqCst), 3);
+}
@@ -0,0 +1,91 @@
+use rquickjs::{class::Trace, CatchResultExt, Class, Context, Runtime};
+
+#[derive(Trace)]
+#[rquickjs::class]
+pub struct Animal {
+    #[qjs(get)]
+    legs: u32,
+}
+
+#[rquickjs::methods]
+impl Animal {
+    #[qjs(constructor)]
+    pub fn new(legs: u32) -> Self {
+        Animal { legs }
+    }
+
+    #[qjs(static)]
+    pub fn kingdom() -> String {
+        "animalia".to_string()
+    }
+}
+
+#[derive(Trace)]
+#[rquickjs::class(extends = Animal)]
+pub struct Dog {
+    #[qjs(get)]
+    name: String,
+}
+
+#[rquickjs::methods]
+impl Dog {
+    #[qjs(constructor)]
+    pub fn new(name: String) -> Self {
+        Dog { name }
+    }
+
+    pub fn bark(&self) -> String {
+        format!("{} barks", self.name)
+    }
+}
+
+pub fn main() {
+    let rt = Runtime::new().unwrap();
+    let ctx = Context::full(&rt).unwrap();
+
+    ctx.with(|ctx| {
+        Class::<Animal>::define(&ctx.globals()).unwrap();
+        Class::<Dog>::define(&ctx.globals()).unwrap();
+
+        ctx.eval::<(), _>(
+            r#"
+            Animal.prototype.describe = function() {
+                return "an animal";
+            };
+            let dog = new Dog("Rex");
+            if(!(dog instanceof Dog) || !(dog instanceof Animal)){
+                throw new Error(1)
+            }
+            if(dog.bark() !== "Rex barks" || dog.describe() !== "an animal"){
+                throw new Error(2)
+            }
+            if(new Animal(4) instanceof Dog){
+                throw new Error(3)
+            }
+            if(Object.getPrototypeOf(Dog) !== Animal || Dog.kingdom() !== "animalia"){
+                throw new Error(5)
+            }
+            let threw = false;
+            try {
+                dog.legs
+            } catch(e) {
+                threw = true;
+            }
+            if(!threw){
+                throw new Error(6)
+            }
+            class Puppy extends Dog {
+                wag() {
+                    return this.name + " wags";
+                }
+            }
+            let puppy = new Puppy("Bit");
+            if(!(puppy instanceof Animal) || puppy.wag() !== "Bit wags" || puppy.bark() !== "Bit barks"){
+                throw new Error(4)
+            }
+        "#,
+        )
+        .catch(&ctx)
+        .unwrap();
+    });
+}